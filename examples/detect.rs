@@ -0,0 +1,30 @@
+//! Simulates the default scene with all objects enabled and prints the detections.
+//!
+//! Run with `cargo run --example detect`.
+
+use fmcw_radar_demo0::dsp::range_for_beat;
+use fmcw_radar_demo0::{AnalysisSettings, App, simulate};
+
+#[expect(clippy::print_stdout, reason = "printing the detections is the point")]
+fn main() {
+    let mut scene = App::default().scene();
+    for obj in &mut scene.objects {
+        obj.enabled = true;
+        println!("Object at {:.1} m, {:.1} m/s", obj.range, obj.velocity);
+    }
+
+    let result = simulate(&scene.config, &AnalysisSettings::default(), &scene.objects);
+    for (i, (detections, chirp)) in result
+        .detections
+        .iter()
+        .zip(&result.config.chirps)
+        .enumerate()
+    {
+        println!("Chirp {} ({:.0} µs):", i + 1, chirp.duration * 1e6);
+        for &(beat, magnitude) in detections {
+            let ramp = result.config.waveform.ramp_duration(chirp.duration);
+            let range = range_for_beat(beat, ramp, result.config.chirp_bandwidth(i));
+            println!("  {:8.3} MHz  |{magnitude:.3}|  ~{range:.1} m", beat * 1e-6);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! Times 1000 2048-point spectra, with a fresh FFT plan per call against the cached plans of
+//! `fftspectrum`.
+//!
+//! Run with `cargo run --release --example fft_benchmark`.
+
+use std::time::Instant;
+
+use fmcw_radar_demo0::dsp::{Normalization, Window, fftspectrum};
+
+const N: usize = 2048;
+const RUNS: usize = 1000;
+
+#[expect(clippy::print_stdout, reason = "printing the timings is the point")]
+fn main() {
+    let signal: Vec<f64> = (0..N).map(|i| (0.1 * i as f64).sin()).collect();
+
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        let fft = realfft::RealFftPlanner::<f64>::new().plan_fft_forward(N);
+        let mut input = signal.clone();
+        let mut output = fft.make_output_vec();
+        fft.process(&mut input, &mut output)
+            .expect("buffers are sized for the plan");
+        std::hint::black_box(output);
+    }
+    let fresh = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        std::hint::black_box(fftspectrum(
+            &signal,
+            1.0,
+            N,
+            Window::Rectangular,
+            Normalization::None,
+        ));
+    }
+    let cached = start.elapsed();
+
+    println!("{RUNS} × {N}-point FFT");
+    println!("  planned every call: {fresh:?}");
+    println!("  cached plan:        {cached:?}");
+}
@@ -1,198 +1,731 @@
+use std::ops::RangeInclusive;
+
 use itertools::izip;
+use rand::{Rng as _, SeedableRng as _};
+
+use crate::detection_log::DetectionLog;
+use crate::dsp::{
+    Complex, DopplerModel, Normalization, SPEED_OF_LIGHT, VelocityConvention, Window,
+    ambiguity_function, angle_spectrum, apply_iq_imbalance, autocorrelate, beat_bins,
+    beat_per_meter, chain_group_delay, doppler_for_beat, doppler_per_chirp, doppler_shift,
+    fftspectrum, idx_at_t, measure_3db_width, noise_floor, range_doppler, range_for_beat,
+    sample_array_signal, sample_signal, snr_db, spectrogram, two_sided_spectrum,
+    velocity_for_doppler, window_response,
+};
+use crate::export::{open_file, save_file, wav_file};
+use crate::presets::Preset;
+use crate::simulation::{
+    AnalysisSettings, Chirp, ELEMENT_SPACING_RANGE, NEW_CHIRP_DURATION, PHASE_NOISE_OFFSET,
+    PathLoss, RX_ELEMENTS_RANGE, RadarConfig, RadarObject, SAMPLING_DURATION_RANGE,
+    SWEEP_NONLINEARITY_RANGE, Sampling, Scene, SignalSource, Simulation, SimulationResult,
+    SwerlingModel, Waveform, saw,
+};
+use crate::svg::SvgPlot;
 
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)] // if we add new fields, give them default values when deserializing old state
 pub struct App {
-    objects: Vec<(f64, f64, egui::Color32, bool, Vec<f64>)>,
+    /// The targets in the scene. State persisted while these were still
+    /// `(range, velocity, color, enabled, beat_freqs)` tuples does not load; the app then starts
+    /// from the default scene.
+    objects: Vec<RadarObject>,
+    /// Object that clicks into the scene plot move.
+    selected_object: usize,
+    /// Move the objects by their velocity every frame.
+    playing: bool,
+    /// Simulated seconds per second of playback.
+    time_scale: f64,
+    /// Ranges of the objects when playback started, restored by Stop. `None` while stopped.
+    play_start_ranges: Option<Vec<f64>>,
+    /// Number of objects of a random scene.
+    random_object_count: usize,
+    /// Seed of the next random scene, advanced by every draw.
+    random_scene_seed: u64,
 
     carrier_frequency: f64,
     bandwidth: f64,
     sampling_frequency: f64,
     sampling_duration: f64,
+    /// Number of receive antennas in the uniform linear array.
+    rx_elements: usize,
+    /// Spacing of the receive antennas, in wavelengths.
+    element_spacing: f64,
+    velocity_convention: VelocityConvention,
+    /// Formula of the Doppler shift of the echoes.
+    doppler_model: DopplerModel,
+    /// Largest deviation of the sweep from a linear ramp, as a fraction of the bandwidth.
+    sweep_nonlinearity: f64,
+    /// Shape of the frequency ramp within each chirp.
+    waveform: Waveform,
+    /// How the echoes are received and analyzed. Settings persisted before they were grouped here
+    /// do not load; they start from their defaults.
+    analysis: AnalysisSettings,
+    /// Which of the plots derived from the simulation are shown, and how. Like `analysis`, these
+    /// start from their defaults when restoring state saved before they were grouped.
+    views: ViewSettings,
+    /// What the axes of `my_plot` show.
+    scene_view: SceneView,
     plot_lines: bool,
-    t: Vec<f64>,
-    chirps: Vec<f64>,
-    ffts: Vec<Vec<(f64, f64)>>,
-    fft_peaks: Vec<Vec<(f64, f64)>>,
-    f: Vec<f64>,
+    /// `(range, velocity)` of every peak of the chirp selected for `my_plot3`, only computed while
+    /// they are shown.
+    #[serde(skip)]
+    velocity_estimates: Vec<(f64, f64)>,
+    /// Plot only the beat frequencies in `my_plot2` instead of the received ramps.
+    beat_only: bool,
+    chirps: Vec<Chirp>,
+    /// The last run of the pipeline. It is recomputed by every `update`, so saving it would only
+    /// bloat the stored state.
+    #[serde(skip)]
+    simulation: Simulation,
+    /// Expected and detected beat of every reflection on every chirp.
+    #[serde(skip)]
+    beat_comparisons: Vec<BeatComparison>,
+    /// What the current parameters can resolve.
+    #[serde(skip)]
+    resolution: Resolution,
+    /// Indices of the enabled objects whose beat exceeds Nyquist on some chirp.
+    #[serde(skip)]
+    aliasing_objects: Vec<usize>,
+    #[serde(skip)]
     bf: Vec<f64>,
+    #[serde(skip)]
     lines: Vec<((f64, f64), (f64, f64))>,
+    /// Overlay the windowed samples on the raw ones in `my_plot3`.
+    compare_window: bool,
+    /// Points of the continuous trace in `my_plot3` per ADC sample.
+    signal_oversampling: usize,
+    /// Color the FFT peaks by their estimated range instead of by chirp.
+    color_peaks_by_range: bool,
+    /// Plot the FFT magnitudes in dB instead of linearly.
+    fft_in_db: bool,
+    /// Whether the pointer is dragging the peak threshold line instead of panning the FFT plot.
+    #[serde(skip)]
+    dragging_peak_threshold: bool,
+    #[serde(skip)]
+    iq_fft: Vec<(f64, f64)>,
+    /// `[threshold_db, detections]` points, only computed while they are shown.
+    #[serde(skip)]
+    threshold_curve: Vec<[f64; 2]>,
+    /// `(range, azimuth)` of every peak of the chirp selected for `my_plot3`, with the azimuth
+    /// estimated across the array, only computed while the top view is shown.
+    #[serde(skip)]
+    azimuth_estimates: Vec<(f64, f64)>,
+    /// Ambiguity function per Doppler bin and delay, only computed while it is shown.
+    #[serde(skip)]
+    ambiguity: Vec<Vec<f64>>,
+    #[serde(skip)]
+    ambiguity_texture: Option<egui::TextureHandle>,
+    /// Range-Doppler map per Doppler bin and beat bin, only computed while it is shown.
+    #[serde(skip)]
+    range_doppler: Vec<Vec<f64>>,
+    #[serde(skip)]
+    range_doppler_texture: Option<egui::TextureHandle>,
+    /// Spectrogram magnitudes per frame and bin, only computed while it is shown.
+    #[serde(skip)]
+    spectrogram: Vec<Vec<f64>>,
+    #[serde(skip)]
+    spectrogram_texture: Option<egui::TextureHandle>,
+    /// Normalized autocorrelation per lag of one sample, only computed while it is shown.
+    #[serde(skip)]
+    autocorrelation: Vec<f64>,
+    /// Mark the beat of every reflection of the enabled objects on the FFT plot.
+    show_expected_beats: bool,
+    #[serde(skip)]
+    theoretical_responses: Vec<(egui::Color32, Vec<[f64; 2]>)>,
+    /// Frozen copies of earlier spectra (one per chirp), oldest first.
+    #[serde(skip)]
+    pinned_ffts: Vec<Vec<Vec<(f64, f64)>>>,
+    /// Baseband spectrum of the transmitted chirp, only computed while it is shown.
+    #[serde(skip)]
+    tx_fft: Vec<(f64, f64)>,
+    /// Indices of the chirps whose sampling window runs past their end.
+    #[serde(skip)]
+    overlong_chirps: Vec<usize>,
+    #[serde(skip)]
+    export_error: Option<String>,
+    /// Write the IF WAV at [`WAV_LISTENING_RATE`] instead of the sampling rate, so audio players
+    /// accept it and play it slowed down.
+    wav_at_listening_rate: bool,
+    /// Append the detections above `log_threshold_db` to the CSV file at `log_path`.
+    log_detections: bool,
+    log_path: String,
+    /// SNR in dB a detection needs to be logged.
+    log_threshold_db: f64,
+    #[serde(skip)]
+    detection_log: Option<DetectionLog>,
+    #[serde(skip)]
+    log_error: Option<String>,
+    /// Contents of the scene JSON text box.
+    #[serde(skip)]
+    scene_json: String,
+    #[serde(skip)]
+    scene_json_error: Option<String>,
+    #[serde(skip)]
+    import_error: Option<String>,
+    plot_heights: PlotHeights,
+    inverse: Inverse,
+    /// Hash of the state the simulation results were last computed from, see
+    /// [`App::update_if_changed`]. `None` forces the next frame to recompute.
+    #[serde(skip)]
+    simulated_inputs: Option<u64>,
+}
+
+/// Feeds everything written to it into a hasher, to hash a value through its serialization.
+struct HashWriter<H>(H);
+
+impl<H: std::hash::Hasher> std::io::Write for HashWriter<H> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The display settings [`App::update`] reads, because it only computes the plots that are
+/// shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+struct ViewSettings {
+    /// Subtract the range bias caused by the receive chain from the range lines.
+    correct_range_bias: bool,
+    /// Plot every peak of the chirp selected for `my_plot3` at the range and the velocity
+    /// estimated from its phase across repetitions of that chirp.
+    show_velocity_estimates: bool,
+    /// Build the range lines from the interpolated instead of the bin frequencies of the peaks.
+    interpolate_range_lines: bool,
+    /// Chirp whose sampling window `my_plot3` shows.
+    signal_plot_chirp: usize,
+    show_tx_spectrum: bool,
+    /// Show the two-sided spectrum of the complex beat signal of the chirp selected for `my_plot3`.
+    show_iq_spectrum: bool,
+    /// Show how many detections reach each SNR threshold.
+    show_threshold_curve: bool,
+    /// Show the objects and the detections at their estimated azimuth from above.
+    show_top_view: bool,
+    /// Show the ambiguity function of the first chirp.
+    show_ambiguity: bool,
+    /// Show the range-Doppler map of the chirp selected for `my_plot3`.
+    show_range_doppler: bool,
+    /// Show the spectrogram of the whole chirp selected for `my_plot3`.
+    show_spectrogram: bool,
+    /// Samples of the spectrogram window.
+    spectrogram_window: usize,
+    /// Samples between the centers of successive spectrogram windows.
+    spectrogram_hop: usize,
+    /// Show the autocorrelation of the beat signal of the chirp selected for `my_plot3`.
+    show_autocorrelation: bool,
+    /// Overlay the continuous response of the windowed FFT at the true beat frequencies.
+    show_theoretical_response: bool,
+}
+
+impl Default for ViewSettings {
+    fn default() -> Self {
+        Self {
+            correct_range_bias: true,
+            show_velocity_estimates: true,
+            interpolate_range_lines: true,
+            signal_plot_chirp: 0,
+            show_tx_spectrum: false,
+            show_iq_spectrum: false,
+            show_threshold_curve: false,
+            show_top_view: false,
+            show_ambiguity: false,
+            show_range_doppler: false,
+            show_spectrogram: false,
+            spectrogram_window: 64,
+            spectrogram_hop: 8,
+            show_autocorrelation: false,
+            show_theoretical_response: false,
+        }
+    }
+}
+
+/// Everything [`App::update`] reads: the radar parameters, the objects, the analysis settings and
+/// the settings of the plots it computes, but none of the playback or layout settings. The beats of
+/// the objects are not serialized, so they do not count either.
+#[derive(serde::Serialize)]
+struct SimulationInputs<'a> {
+    config: RadarConfig,
+    objects: &'a [RadarObject],
+    analysis: &'a AnalysisSettings,
+    views: &'a ViewSettings,
+}
+
+/// The analytically expected beat of one reflection on one chirp and the closest FFT peak.
+struct BeatComparison {
+    chirp: usize,
+    object: usize,
+    expected: f64,
+    detected: Option<f64>,
+    /// Spacing of the FFT bins of the chirp, in Hz.
+    bin_width: f64,
+}
+
+/// What the radar parameters can resolve, derived from them on every update.
+#[derive(Clone, Copy, Debug, Default)]
+struct Resolution {
+    /// Smallest range separation of two targets, `c/2B`, in meters.
+    range: f64,
+    /// Largest range before the beat of the shortest chirp reaches Nyquist, in meters.
+    max_range: f64,
+    /// Smallest velocity separation of two targets in the range-Doppler map, in m/s.
+    velocity: f64,
+    /// Largest unambiguous velocity `λ/4T` for the sawtooth period `T`, in m/s.
+    max_velocity: f64,
+    /// Highest beat of any enabled reflection on any chirp, in Hz.
+    max_beat: f64,
+}
+
+/// Which quantity the inverse panel solves for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+enum InverseSolve {
+    /// Range of a stationary target.
+    Range,
+    /// Velocity of a target at its current range.
+    Velocity,
+}
+
+/// Settings of the panel that places a target to produce a given beat frequency.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+struct Inverse {
+    beat: f64,
+    chirp: usize,
+    object: usize,
+    solve: InverseSolve,
+}
+
+impl Default for Inverse {
+    fn default() -> Self {
+        Self {
+            beat: 1e6,
+            chirp: 0,
+            object: 0,
+            solve: InverseSolve::Range,
+        }
+    }
+}
+
+/// Heights of the stacked plots, in points.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+struct PlotHeights {
+    scene: f32,
+    frequency: f32,
+    signal: f32,
+    fft: f32,
+}
+
+impl Default for PlotHeights {
+    fn default() -> Self {
+        Self {
+            scene: 500.0,
+            frequency: 100.0,
+            signal: 100.0,
+            fft: 120.0,
+        }
+    }
 }
 
 impl Default for App {
     fn default() -> Self {
+        let config = RadarConfig::default();
         Self {
-            carrier_frequency: 77E9,
-            bandwidth: 1.6E9,
-            sampling_frequency: 50E6f64,
-            sampling_duration: 40E-6,
+            carrier_frequency: config.carrier_frequency,
+            bandwidth: config.bandwidth,
+            sampling_frequency: config.sampling_frequency,
+            sampling_duration: config.sampling_duration,
+            rx_elements: config.rx_elements,
+            element_spacing: config.element_spacing,
+            velocity_convention: config.velocity_convention,
+            doppler_model: config.doppler_model,
+            sweep_nonlinearity: config.sweep_nonlinearity,
+            waveform: config.waveform,
+            analysis: AnalysisSettings::default(),
+            views: ViewSettings::default(),
+            selected_object: 0,
+            playing: false,
+            time_scale: 1.0,
+            play_start_ranges: None,
+            random_object_count: 10,
+            random_scene_seed: 0,
             objects: vec![
-                (10.0, 0.0, egui::Color32::GREEN, true, vec![]),
-                (30.0, 20.0, egui::Color32::BLUE, false, vec![]),
-                (40.0, -10.0, egui::Color32::RED, false, vec![]),
+                RadarObject::new(10.0, 0.0, egui::Color32::GREEN, true),
+                RadarObject::new(30.0, 20.0, egui::Color32::BLUE, false),
+                RadarObject::new(40.0, -10.0, egui::Color32::RED, false),
             ],
+            scene_view: SceneView::default(),
             plot_lines: true,
-            t: vec![],
-            chirps: vec![40e-6, 20e-6, 60e-6],
-            f: vec![],
-            ffts: vec![],
-            fft_peaks: vec![],
+            velocity_estimates: vec![],
+            beat_only: false,
+            chirps: config.chirps,
+            simulation: Simulation::default(),
+            beat_comparisons: vec![],
+            resolution: Resolution::default(),
+            aliasing_objects: vec![],
             bf: vec![],
             lines: vec![],
+            compare_window: false,
+            signal_oversampling: 8,
+            color_peaks_by_range: false,
+            fft_in_db: false,
+            dragging_peak_threshold: false,
+            iq_fft: vec![],
+            threshold_curve: vec![],
+            azimuth_estimates: vec![],
+            ambiguity: vec![],
+            ambiguity_texture: None,
+            range_doppler: vec![],
+            range_doppler_texture: None,
+            spectrogram: vec![],
+            spectrogram_texture: None,
+            autocorrelation: vec![],
+            show_expected_beats: true,
+            theoretical_responses: vec![],
+            pinned_ffts: vec![],
+            tx_fft: vec![],
+            overlong_chirps: vec![],
+            export_error: None,
+            wav_at_listening_rate: false,
+            log_detections: false,
+            log_path: "detections.csv".to_owned(),
+            log_threshold_db: 20.0,
+            detection_log: None,
+            log_error: None,
+            scene_json: String::new(),
+            scene_json_error: None,
+            import_error: None,
+            plot_heights: PlotHeights::default(),
+            inverse: Inverse::default(),
+            simulated_inputs: None,
         }
     }
 }
 
-const SPEED_OF_LIGHT: f64 = 299999000.0;
-
-fn saw(t_: &[f64], tc: &[f64]) -> Vec<f64> {
-    // period of the sawtooth
-    let period = tc;
-    t_.iter()
-        .map(|&t| {
-            // Find which Tc interval we're in, wrapping around if needed
-            let total_duration: f64 = period.iter().sum();
-            let t_wrapped = t % total_duration;
-            let mut total_period = 0.0;
-            let mut current_period = period[0];
-            for &p in period {
-                if t_wrapped < total_period + p {
-                    current_period = p;
-                    break;
-                }
-                total_period += p;
-            }
-            // normalized time within current period
-            let t_mod = t_wrapped - total_period;
-            // sawtooth value from 0.0 to 1.0
-            t_mod / current_period
-        })
-        .collect()
+/// What the axes of the scene plot show.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+enum SceneView {
+    /// Range against velocity, where the range lines of the peaks live.
+    #[default]
+    RangeVelocity,
+    /// The positions of the objects seen from above, with the radar at the origin.
+    Spatial,
 }
 
-fn doppler_shift(frequency: f64, velocity: f64) -> f64 {
-    // Calculate the Doppler shift for a given frequency and velocity
-    // Positive velocity means receding (redshift), negative means approaching (blueshift)
-    frequency * ((SPEED_OF_LIGHT - velocity) / (SPEED_OF_LIGHT + velocity) - 1.0)
+impl SceneView {
+    const ALL: [Self; 2] = [Self::RangeVelocity, Self::Spatial];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::RangeVelocity => "Range-velocity",
+            Self::Spatial => "Spatial",
+        }
+    }
 }
 
-fn beat_frequencies(
-    t: &[f64],
-    f: &[f64],
-    range: f64,
-    velocity: f64,
-    carrier_frequency: f64,
-    bandwidth: f64,
-    chirps: &[f64],
-) -> Vec<f64> {
-    // Time shift due to range
-    let timeshift_due_to_range = 2.0 * range / SPEED_OF_LIGHT;
-    let time_at_range = &t
-        .iter()
-        .map(|ti| ti - timeshift_due_to_range)
-        .collect::<Vec<f64>>();
-    let saw_values_at_range = saw(time_at_range, chirps);
-    let range_frequencies: Vec<f64> = saw_values_at_range
-        .iter()
-        .map(|&s| s * bandwidth + carrier_frequency)
-        .collect();
+/// Angular resolution in radians of a uniform linear array of `elements` antennas spaced
+/// `spacing` wavelengths apart, for a target at `azimuth` radians off boresight.
+fn angular_resolution(elements: usize, spacing: f64, azimuth: f64) -> f64 {
+    1.0 / (elements as f64 * spacing * azimuth.cos())
+}
+
+/// Largest range of an object, in meters.
+const MAX_RANGE: f64 = 100.0;
 
-    // Calculate beat frequency at each time sample
-    let mut beat_freqs = vec![0.0; t.len()];
-    for i in 0..t.len() {
-        let range_shift = range_frequencies[i] - f[i];
-        beat_freqs[i] = doppler_shift(f[i], velocity) + range_shift;
+/// Largest speed of an object, in m/s.
+const MAX_VELOCITY: f64 = 50.0;
+
+/// Segments of the arc that closes the field of view in the spatial view.
+const FOV_ARC_POINTS: usize = 64;
+
+// Bounds of the sliders, which imported scenes are clamped to
+const OBJECT_LENGTH_RANGE: RangeInclusive<f64> = 0.1..=20.0;
+const RCS_RANGE: RangeInclusive<f64> = 0.01..=100.0;
+const MICRO_AMP_RANGE: RangeInclusive<f64> = 0.0..=0.1;
+const MICRO_FREQ_RANGE: RangeInclusive<f64> = 1.0..=100e3;
+const CARRIER_FREQUENCY_RANGE: RangeInclusive<f64> = 10e9..=100e9;
+const BANDWIDTH_RANGE: RangeInclusive<f64> = 0.05e9..=4e9;
+const CHIRP_DURATION_RANGE: RangeInclusive<f64> = 1e-6..=100e-3;
+const SAMPLING_FREQUENCY_RANGE: RangeInclusive<f64> = 10e6..=100e6;
+const SAMPLE_START_FRACTION_RANGE: RangeInclusive<f64> = 0.0..=1.0;
+const PHASE_NOISE_RANGE: RangeInclusive<f64> = -140.0..=-60.0;
+const ADC_BITS_RANGE: RangeInclusive<u32> = 0..=24;
+const ADC_FULLSCALE_RANGE: RangeInclusive<f64> = 0.01..=100.0;
+const ATMOSPHERIC_LOSS_RANGE: RangeInclusive<f64> = 0.0..=100.0;
+const RADAR_HEIGHT_RANGE: RangeInclusive<f64> = 0.1..=20.0;
+const WELCH_SEGMENT_RANGE: RangeInclusive<usize> = 16..=1 << 16;
+const WELCH_OVERLAP_RANGE: RangeInclusive<f64> = 0.0..=0.9;
+
+/// `scene` with every parameter clamped into the bounds of its slider.
+fn clamp_to_sliders(mut scene: Scene) -> Scene {
+    let clamp = |value: f64, range: RangeInclusive<f64>| value.clamp(*range.start(), *range.end());
+    let config = &mut scene.config;
+    config.carrier_frequency = clamp(config.carrier_frequency, CARRIER_FREQUENCY_RANGE);
+    config.bandwidth = clamp(config.bandwidth, BANDWIDTH_RANGE);
+    config.sweep_nonlinearity = clamp(config.sweep_nonlinearity, SWEEP_NONLINEARITY_RANGE);
+    for chirp in &mut config.chirps {
+        chirp.duration = clamp(chirp.duration, CHIRP_DURATION_RANGE);
+        if let Some(bandwidth) = &mut chirp.bandwidth {
+            *bandwidth = clamp(*bandwidth, BANDWIDTH_RANGE);
+        }
+        if let Some(start) = &mut chirp.start_freq {
+            *start = clamp(*start, CARRIER_FREQUENCY_RANGE);
+        }
+    }
+    config.sampling_frequency = clamp(config.sampling_frequency, SAMPLING_FREQUENCY_RANGE);
+    config.sampling_duration = clamp(config.sampling_duration, SAMPLING_DURATION_RANGE);
+    config.rx_elements = config
+        .rx_elements
+        .clamp(*RX_ELEMENTS_RANGE.start(), *RX_ELEMENTS_RANGE.end());
+    config.element_spacing = clamp(config.element_spacing, ELEMENT_SPACING_RANGE);
+    for obj in &mut scene.objects {
+        obj.range = obj.range.clamp(0.0, MAX_RANGE);
+        obj.velocity = obj.velocity.clamp(-MAX_VELOCITY, MAX_VELOCITY);
+        obj.length = clamp(obj.length, OBJECT_LENGTH_RANGE);
+        obj.rcs = clamp(obj.rcs, RCS_RANGE);
+        obj.azimuth = clamp(obj.azimuth, AZIMUTH_RANGE);
+        obj.micro_amp = clamp(obj.micro_amp, MICRO_AMP_RANGE);
+        obj.micro_freq = clamp(obj.micro_freq, MICRO_FREQ_RANGE);
     }
+    scene
+}
 
-    // Return both the time vector, frequencies sent and the beat frequencies
-    beat_freqs
+/// Maps `value` in `0.0..=1.0` onto a perceptually uniform color scale (viridis).
+fn colormap(value: f64) -> egui::Color32 {
+    const STOPS: [[f64; 3]; 5] = [
+        [68.0, 1.0, 84.0],
+        [59.0, 82.0, 139.0],
+        [33.0, 145.0, 140.0],
+        [94.0, 201.0, 98.0],
+        [253.0, 231.0, 37.0],
+    ];
+    let position = value.clamp(0.0, 1.0) * (STOPS.len() - 1) as f64;
+    let lower = (position.floor() as usize).min(STOPS.len() - 2);
+    let fraction = position - lower as f64;
+    let (Some(a), Some(b)) = (STOPS.get(lower), STOPS.get(lower + 1)) else {
+        return egui::Color32::WHITE;
+    };
+    let channel = |c: usize| {
+        let (a, b) = (
+            a.get(c).copied().unwrap_or(0.0),
+            b.get(c).copied().unwrap_or(0.0),
+        );
+        (a + fraction * (b - a)).round() as u8
+    };
+    egui::Color32::from_rgb(channel(0), channel(1), channel(2))
 }
 
-fn sample_signal(t: &[f64], frequencies: &[f64]) -> Vec<f64> {
-    // For each timestamp in t, sum sin(2π f t) for all frequencies and return a Vec
-    t.iter()
-        .map(|&t_val| {
-            let mut sum = 0.0;
-            for &f in frequencies {
-                sum += (2.0 * std::f64::consts::PI * f * t_val).sin();
-            }
-            sum
+/// Bounds of the playback time scale.
+const TIME_SCALE_RANGE: RangeInclusive<f64> = 0.01..=100.0;
+
+/// Bounds of the azimuth of an object, in degrees off boresight.
+const AZIMUTH_RANGE: RangeInclusive<f64> = -90.0..=90.0;
+
+/// Points of the angle FFT across the receive array.
+const ANGLE_FFT_BINS: usize = 128;
+
+/// Range and velocity step of the arrow keys, and with shift held.
+const NUDGE_STEPS: (f64, f64) = (0.1, 1.0);
+
+/// Highest SNR threshold of the detections-vs-threshold curve, in dB.
+const MAX_THRESHOLD_DB: f64 = 60.0;
+
+/// Steps of the detections-vs-threshold curve per dB.
+const THRESHOLD_STEPS_PER_DB: usize = 4;
+
+/// Samples of the chirp whose ambiguity function is shown, which sets its time-bandwidth product.
+const AMBIGUITY_SAMPLES: usize = 128;
+
+/// Doppler bins of the ambiguity function, spanning the whole sweep.
+const AMBIGUITY_DOPPLER_BINS: usize = 128;
+
+/// Successive identical chirps of the range-Doppler map, which sets its Doppler resolution.
+const RANGE_DOPPLER_CHIRPS: usize = 64;
+
+/// Lowest level of the range-Doppler map in dB below its peak, shown at the bottom of the colormap.
+const RANGE_DOPPLER_DB_FLOOR: f64 = -60.0;
+
+const SPECTROGRAM_WINDOW_RANGE: RangeInclusive<usize> = 8..=4096;
+const SPECTROGRAM_HOP_RANGE: RangeInclusive<usize> = 1..=4096;
+
+/// Most samples of a chirp the spectrogram covers, the rest of longer chirps is cut off.
+const SPECTROGRAM_MAX_SAMPLES: usize = 1 << 20;
+
+/// Most frames of the spectrogram, one column of its image each.
+const SPECTROGRAM_MAX_FRAMES: usize = 1024;
+
+/// Lowest level of the spectrogram in dB below its peak, shown at the bottom of the colormap.
+const SPECTROGRAM_DB_FLOOR: f64 = -60.0;
+
+/// Complex baseband linear chirp of `n` samples sweeping half the sampling rate, centered at 0 Hz.
+///
+/// Its time-bandwidth product is `n/2`. The ambiguity function of a linear chirp only depends
+/// on that product once delays are measured in chirp durations and Doppler shifts in
+/// bandwidths, so this stands in for chirps that are far too long to sample.
+fn scaled_chirp(n: usize) -> Vec<Complex<f64>> {
+    (0..n)
+        .map(|i| {
+            // Instantaneous frequency from -1/4 to 1/4 cycles per sample
+            let i = i as f64;
+            let phase = std::f64::consts::PI * (i * i / (2.0 * n as f64) - i / 2.0);
+            Complex::from_polar(1.0, phase)
         })
         .collect()
 }
 
-fn fftspectrum(signal: &[f64], sampling_rate: f64) -> Vec<(f64, f64)> {
-    let n = signal.len();
-    // Compute FFT using rustfft
-    // Import rustfft types
-    use rustfft::{FftPlanner, num_complex::Complex};
-    let mut planner = FftPlanner::<f64>::new();
-    let fft = planner.plan_fft_forward(n);
-
-    // Prepare input: convert real signal to complex
-    let mut buffer: Vec<Complex<f64>> =
-        signal.iter().map(|&x| Complex { re: x, im: 0.0 }).collect();
-    fft.process(&mut buffer);
-
-    // Compute magnitude spectrum (normalize)
-    let norm = n as f64;
-    buffer
+/// Most chirps the UI adds, so that every chirp keeps its own color.
+const MAX_CHIRPS: usize = CHIRP_COLORS.len();
+
+/// Colors of the spectra and peaks of the chirps, in chirp order.
+const CHIRP_COLORS: [egui::Color32; 10] = [
+    egui::Color32::LIGHT_GREEN,
+    egui::Color32::LIGHT_BLUE,
+    egui::Color32::YELLOW,
+    egui::Color32::RED,
+    egui::Color32::WHITE,
+    egui::Color32::LIGHT_RED,
+    egui::Color32::LIGHT_YELLOW,
+    egui::Color32::LIGHT_GRAY,
+    egui::Color32::GRAY,
+    egui::Color32::BLUE,
+];
+
+/// Points per FFT bin of the theoretical response overlay.
+const RESPONSE_OVERSAMPLING: usize = 16;
+
+/// Half width in FFT bins of the theoretical response overlay around each beat frequency.
+const RESPONSE_HALF_WIDTH_BINS: f64 = 8.0;
+
+/// Sample rate written into IF WAV files meant for audio players, in Hz.
+const WAV_LISTENING_RATE: u32 = 48_000;
+
+/// Lowest magnitude shown on the FFT plot in dB, so empty bins do not plot at -∞.
+const FFT_DB_FLOOR: f64 = -120.0;
+/// How close (in points) to the peak threshold line a drag has to start to move the line.
+const PEAK_THRESHOLD_GRAB_DISTANCE: f32 = 6.0;
+
+/// Largest number of spectra that can be pinned to the FFT plot.
+const MAX_PINNED_FFTS: usize = 4;
+
+/// Number of equal steps across the range axis at which new objects may be placed.
+const NEW_OBJECT_RANGES: usize = 20;
+
+const RANDOM_OBJECT_COUNT_RANGE: RangeInclusive<usize> = 1..=100;
+
+/// Colors handed out to new objects, in order.
+const OBJECT_PALETTE: [egui::Color32; 12] = [
+    egui::Color32::GREEN,
+    egui::Color32::BLUE,
+    egui::Color32::RED,
+    egui::Color32::YELLOW,
+    egui::Color32::from_rgb(255, 0, 255),
+    egui::Color32::from_rgb(0, 255, 255),
+    egui::Color32::from_rgb(255, 128, 0),
+    egui::Color32::from_rgb(128, 0, 255),
+    egui::Color32::from_rgb(0, 128, 128),
+    egui::Color32::from_rgb(255, 128, 192),
+    egui::Color32::from_rgb(128, 255, 128),
+    egui::Color32::from_rgb(160, 120, 60),
+];
+
+/// Largest number of points of the continuous trace in `my_plot3`.
+const MAX_TRACE_POINTS: usize = 50_000;
+
+/// Largest number of samples used to synthesize the transmitted chirp.
+const MAX_TX_SAMPLES: usize = 1 << 16;
+
+/// Single-sided spectrum of the first transmitted chirp at baseband, i.e. relative to the bottom
+/// of its band.
+///
+/// The chirp sweeps the whole bandwidth, so unlike the beat signal it is sampled well above the
+/// ADC rate, and the window is shortened if it would need more than [`MAX_TX_SAMPLES`].
+fn tx_spectrum(
+    config: &RadarConfig,
+    duration: f64,
+    window: Window,
+    normalization: Normalization,
+) -> Vec<(f64, f64)> {
+    let bandwidth = config.chirp_bandwidth(0);
+    let sampling_rate = 2.5 * bandwidth;
+    let duration = duration.min(MAX_TX_SAMPLES as f64 / sampling_rate);
+    let n = (duration * sampling_rate).round() as usize;
+    let t: Vec<f64> = (0..n).map(|i| i as f64 / sampling_rate).collect();
+    // Integrate the instantaneous frequency to get the phase of the chirp
+    let mut phase = 0.0_f64;
+    let signal: Vec<f64> = saw(&t, config)
         .iter()
-        .take(n / 2)
-        .enumerate()
-        .map(|(i, c)| {
-            let freq = i as f64 * sampling_rate / n as f64;
-            let mag = (c.norm() / norm) * 2.0; // scale for single-sided spectrum
-            (freq, mag)
+        .map(|&s| {
+            let sample = phase.cos();
+            phase += 2.0 * std::f64::consts::PI * s * bandwidth / sampling_rate;
+            sample
         })
-        .collect()
+        .collect();
+    fftspectrum(&signal, sampling_rate, signal.len(), window, normalization)
 }
 
-fn idx_at_t(v: &[f64], t: f64) -> usize {
-    // Collect the beat frequencies at the found index for all enabled objects
-    v.iter()
-        .enumerate()
-        .min_by(|(_, a), (_, b)| (*a - t).abs().partial_cmp(&(*b - t).abs()).unwrap())
-        .map(|(i, _)| i)
-        .unwrap_or(0)
+/// A logarithmic slider in steps of `step` for coarse changes, next to a field to type the exact
+/// value in. Both keep `value` within `range`.
+fn frequency_input(
+    ui: &mut egui::Ui,
+    value: &mut f64,
+    range: RangeInclusive<f64>,
+    step: f64,
+    text: &str,
+) {
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::Slider::new(value, range.clone())
+                .logarithmic(true)
+                .step_by(step)
+                .show_value(false),
+        );
+        ui.add(egui::DragValue::new(value).range(range).speed(step))
+            .on_hover_text("Drag or click to type an exact value");
+        ui.label(text);
+    });
 }
 
-// Find multiple peaks in a signal above the baseline (average)
-// Returns a Vec<usize> of indices of the peaks
-fn multiple_peak_finding(signal: &[f64]) -> Vec<usize> {
-    let mut peak_indices = Vec::new();
-    let mut peak_index: Option<usize> = None;
-    let mut peak_value: Option<f64> = None;
-
-    for (index, &value) in signal.iter().enumerate() {
-        let baseline = if signal.is_empty() {
-            0.0
-        } else {
-            // Calculate average in surrounding (next 20 indices)
-            let start = index;
-            let end = (index + 2).min(signal.len());
-            if start < end {
-                signal[start..end].iter().sum::<f64>() / (end - start) as f64
-            } else {
-                0.0
+/// Swerling model of `obj`, the object at index `i`.
+fn fluctuation_ui(ui: &mut egui::Ui, obj: &mut RadarObject, i: usize) {
+    egui::ComboBox::new(("swerling", i), "RCS Fluctuation")
+        .selected_text(obj.swerling.name())
+        .show_ui(ui, |ui| {
+            for model in SwerlingModel::ALL {
+                ui.selectable_value(&mut obj.swerling, model, model.name());
             }
-        };
-        if value > baseline {
-            if peak_value.is_none() || value > peak_value.unwrap() {
-                peak_index = Some(index);
-                peak_value = Some(value);
-            }
-        } else if value < baseline && peak_index.is_some() {
-            peak_indices.push(peak_index.unwrap());
-            peak_index = None;
-            peak_value = None;
-        }
-    }
-    if peak_index.is_some() {
-        peak_indices.push(peak_index.unwrap());
-    }
-    peak_indices
+        })
+        .response
+        .on_hover_text(
+            "Draws the RCS around its set value from the noise seed, exponentially \
+             distributed for many similar scatterers (I, II), chi-square with four degrees of \
+             freedom for one dominant scatterer (III, IV)",
+        );
+}
+
+/// Amplitude and frequency of the vibration of `obj`.
+fn micro_motion_ui(ui: &mut egui::Ui, obj: &mut RadarObject) {
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::Slider::new(&mut obj.micro_amp, MICRO_AMP_RANGE)
+                .logarithmic(true)
+                .text("Vibration (m)"),
+        )
+        .on_hover_text(
+            "Sinusoidal motion of the range, like a propeller or a beating heart. Its speed \
+             shifts the beat back and forth, which spreads micro-Doppler sidebands around the \
+             peak, most visible in the spectrogram.",
+        );
+        ui.add_enabled(
+            obj.micro_amp > 0.0,
+            egui::Slider::new(&mut obj.micro_freq, MICRO_FREQ_RANGE)
+                .logarithmic(true)
+                .text("at (Hz)"),
+        );
+    });
 }
 
 impl App {
@@ -211,369 +744,3223 @@ impl App {
     }
 
     pub fn update(&mut self) {
-        let samples = 1000;
-        let duration: f64 = self.chirps.iter().sum::<f64>() * 3.0;
-        self.t = (0..samples)
-            .map(|i| i as f64 * duration / samples as f64)
-            .collect();
-        // Calculate frequencies across the time vector
-        let saw_values = saw(&self.t, &self.chirps);
-        self.f = saw_values
-            .iter()
-            .map(|&s| s * self.bandwidth + self.carrier_frequency)
+        self.simulation = Simulation::new(&self.config(), &self.analysis, &self.objects);
+        let start_times = self.simulation.start_times();
+
+        // A sampling window that runs past the end of its chirp mixes different ramps (and the
+        // jump back to the start frequency) into one FFT, which corrupts the spectrum.
+        self.overlong_chirps = self
+            .durations()
+            .enumerate()
+            .filter(|&(_, chirp)| {
+                self.simulation.window_duration(chirp) > self.simulation.time_left_to_sample(chirp)
+            })
+            .map(|(i, _)| i)
             .collect();
 
-        for obj in &mut self.objects {
-            obj.4 = beat_frequencies(
-                &self.t,
-                &self.f,
-                obj.0,
-                obj.1,
-                self.carrier_frequency,
-                self.bandwidth,
-                &self.chirps,
-            );
+        self.beat_comparisons = self.compare_beats(&start_times);
+        self.resolution = self.compute_resolution(&start_times);
+        self.aliasing_objects = self.find_aliasing_objects(&start_times);
+        let peaks = if self.views.interpolate_range_lines {
+            &self.simulation.interpolated_peaks
+        } else {
+            &self.simulation.fft_peaks
+        };
+        self.lines = self.range_lines(&start_times, peaks);
+        self.update_optional_plots(&start_times);
+    }
+
+    /// Run [`Self::update`] unless nothing it depends on changed since the last run, so an idle
+    /// frame only draws the plots. The [`SimulationInputs`] are compared by hash; anything that
+    /// edits them, like playback moving the objects, triggers a recompute. Clearing
+    /// `simulated_inputs` forces one regardless. Returns whether it recomputed.
+    pub fn update_if_changed(&mut self) -> bool {
+        let inputs = self.inputs_hash();
+        if inputs.is_some() && inputs == self.simulated_inputs {
+            return false;
         }
+        self.update();
+        self.simulated_inputs = inputs;
+        true
+    }
 
-        // FFT of the sampled signal (from my_plot3)
-        // Use the same sampled signal as in my_plot3 overlay
-        // Create FFT spectra for multiple different start times
-        // For each chirp, compute the start time as the sum of previous chirp durations plus 98% of the current chirp duration
-        let mut start_times = Vec::new();
-        // sum of chirps (except the last one)
-        for (i, &chirp) in self.chirps.iter().enumerate() {
-            let sum = {
-                if i > 0 {
-                    self.chirps.iter().take(i).sum()
-                } else {
-                    0.0
-                }
-            };
-            let sum = sum + chirp * 0.98;
-            start_times.push(sum);
+    fn simulation_inputs(&self) -> SimulationInputs<'_> {
+        SimulationInputs {
+            config: self.config(),
+            objects: &self.objects,
+            analysis: &self.analysis,
+            views: &self.views,
         }
+    }
 
-        let n = (self.sampling_duration * self.sampling_frequency).round() as usize;
+    /// Hash of the [`SimulationInputs`], or `None` if they do not serialize.
+    fn inputs_hash(&self) -> Option<u64> {
+        let mut writer = HashWriter(std::hash::DefaultHasher::new());
+        serde_json::to_writer(&mut writer, &self.simulation_inputs()).ok()?;
+        Some(std::hash::Hasher::finish(&writer.0))
+    }
 
-        self.ffts = start_times
+    /// What the current parameters can resolve, with the chirps starting at `start_times`.
+    fn compute_resolution(&self, start_times: &[f64]) -> Resolution {
+        let wavelength = SPEED_OF_LIGHT / self.carrier_frequency;
+        // The shortest chirp has the steepest slope, so its beats reach Nyquist first
+        let config = self.config();
+        let max_range = self
+            .durations()
+            .enumerate()
+            .map(|(i, chirp)| {
+                let ramp = self.waveform.ramp_duration(chirp);
+                range_for_beat(
+                    self.sampling_frequency / 2.0,
+                    ramp,
+                    config.chirp_bandwidth(i),
+                )
+            })
+            .fold(f64::INFINITY, f64::min);
+        // The range-Doppler map observes the target over that many repetitions of one chirp
+        let observation = self
+            .chirps
+            .get(self.views.signal_plot_chirp)
+            .map_or(0.0, |chirp| RANGE_DOPPLER_CHIRPS as f64 * chirp.duration);
+        // Every chirp repeats once per sawtooth period
+        let period: f64 = self.durations().sum();
+        let max_beat = start_times
             .iter()
-            .map(|&start| {
-                let t: Vec<f64> = (0..n)
-                    .map(|i| start + i as f64 * self.sampling_duration / (n - 1) as f64)
-                    .collect();
+            .flat_map(|&start| {
+                self.simulation
+                    .reflections_at(idx_at_t(&self.simulation.t, start))
+            })
+            .fold(0.0_f64, |max, (beat, _)| max.max(beat.abs()));
+        // The widest sweep resolves the finest
+        let bandwidth = (0..self.chirps.len())
+            .map(|i| config.chirp_bandwidth(i))
+            .reduce(f64::max)
+            .unwrap_or(self.bandwidth);
+        Resolution {
+            range: SPEED_OF_LIGHT / (2.0 * bandwidth),
+            max_range,
+            velocity: wavelength / (2.0 * observation),
+            max_velocity: wavelength / (4.0 * period),
+            max_beat,
+        }
+    }
 
-                // Collect the beat frequencies at the found index for all enabled objects
-                let idx = idx_at_t(&self.t, start);
+    /// Indices of the enabled objects with a beat above Nyquist at any of the `start_times`, which
+    /// folds back into the spectrum as a ghost peak at the wrong range.
+    fn find_aliasing_objects(&self, start_times: &[f64]) -> Vec<usize> {
+        let nyquist = self.sampling_frequency / 2.0;
+        let indices: Vec<usize> = start_times
+            .iter()
+            .map(|&start| idx_at_t(&self.simulation.t, start))
+            .collect();
+        self.simulation
+            .objects
+            .iter()
+            .enumerate()
+            .filter(|(_, obj)| obj.enabled)
+            .filter(|(_, obj)| {
+                indices
+                    .iter()
+                    .flat_map(|&idx| obj.reflection_beat_freqs(idx))
+                    .any(|beat| beat.abs() > nyquist)
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
 
-                let mut frequencies: Vec<f64> = Vec::new();
-                for obj in self.objects.iter().take(3) {
-                    if obj.3 && obj.4.len() > idx {
-                        frequencies.push(obj.4[idx]);
-                    }
+    /// Compute the data of the optional plots that are shown and drop that of the hidden ones.
+    fn update_optional_plots(&mut self, start_times: &[f64]) {
+        self.tx_fft = if self.views.show_tx_spectrum {
+            let duration = self.chirps.first().map_or(self.sampling_duration, |chirp| {
+                self.sampling_duration.min(chirp.duration)
+            });
+            tx_spectrum(
+                &self.config(),
+                duration,
+                self.analysis.window,
+                self.analysis.normalization,
+            )
+        } else {
+            vec![]
+        };
+
+        self.autocorrelation = if self.views.show_autocorrelation {
+            let chirp = self.views.signal_plot_chirp;
+            match (start_times.get(chirp), self.chirps.get(chirp)) {
+                (Some(&start), Some(chirp)) => {
+                    autocorrelate(&self.simulation.build_signal(start, chirp.duration))
                 }
-                let signal = sample_signal(&t, &frequencies);
+                _ => vec![],
+            }
+        } else {
+            vec![]
+        };
 
-                fftspectrum(&signal, self.sampling_frequency)
-            })
+        self.theoretical_responses = if self.views.show_theoretical_response {
+            self.compute_theoretical_responses(start_times)
+        } else {
+            vec![]
+        };
+
+        self.threshold_curve = if self.views.show_threshold_curve {
+            self.detections_vs_threshold()
+        } else {
+            vec![]
+        };
+
+        self.ambiguity = if self.views.show_ambiguity {
+            ambiguity_function(
+                &scaled_chirp(AMBIGUITY_SAMPLES),
+                AMBIGUITY_SAMPLES as f64,
+                AMBIGUITY_DOPPLER_BINS,
+            )
+        } else {
+            vec![]
+        };
+
+        self.range_doppler = if self.views.show_range_doppler {
+            let chirp = self.views.signal_plot_chirp;
+            match (start_times.get(chirp), self.chirps.get(chirp)) {
+                (Some(&start), Some(&Chirp { duration, .. })) => range_doppler(
+                    &self.range_doppler_frames(chirp, start, duration),
+                    self.analysis.window,
+                ),
+                _ => vec![],
+            }
+        } else {
+            vec![]
+        };
+
+        self.spectrogram = if self.views.show_spectrogram {
+            self.compute_spectrogram()
+        } else {
+            vec![]
+        };
+
+        self.azimuth_estimates = if self.views.show_top_view {
+            let chirp = self.views.signal_plot_chirp;
+            match (start_times.get(chirp), self.chirps.get(chirp)) {
+                (Some(&start), Some(&Chirp { duration, .. })) => {
+                    self.estimate_azimuths(chirp, start, duration)
+                }
+                _ => vec![],
+            }
+        } else {
+            vec![]
+        };
+
+        self.velocity_estimates = if self.views.show_velocity_estimates {
+            let chirp = self.views.signal_plot_chirp;
+            match (start_times.get(chirp), self.chirps.get(chirp)) {
+                (Some(&start), Some(&Chirp { duration, .. })) => {
+                    self.estimate_velocities(chirp, start, duration)
+                }
+                _ => vec![],
+            }
+        } else {
+            vec![]
+        };
+
+        self.iq_fft = if self.views.show_iq_spectrum {
+            let chirp = self.views.signal_plot_chirp;
+            match (start_times.get(chirp), self.chirps.get(chirp)) {
+                (Some(&start), Some(chirp)) => self.iq_spectrum(start, chirp.duration),
+                _ => vec![],
+            }
+        } else {
+            vec![]
+        };
+    }
+
+    /// Spectrogram of the clean beat signal over the whole chirp selected for `my_plot3`, from its
+    /// start to its end rather than only the sampling window.
+    fn compute_spectrogram(&self) -> Vec<Vec<f64>> {
+        let Some(&Chirp { duration, .. }) = self.chirps.get(self.views.signal_plot_chirp) else {
+            return vec![];
+        };
+        let start: f64 = self.durations().take(self.views.signal_plot_chirp).sum();
+        let n = self.spectrogram_samples(duration);
+        let t: Vec<f64> = (0..n)
+            .map(|i| start + i as f64 / self.sampling_frequency)
             .collect();
-        // Find peaks in each FFT using multiple_peak_finding
-        // Find peaks in each FFT and return their actual frequencies (in Hz)
-        self.fft_peaks = self
-            .ffts
+        let signal = self.simulation.chirped_beat_signal(&t, &vec![0.0; n]);
+        spectrogram(
+            &signal,
+            self.sampling_frequency,
+            self.views.spectrogram_window,
+            self.effective_spectrogram_hop(n),
+            self.analysis.window,
+            self.analysis.normalization,
+        )
+    }
+
+    /// Samples of the spectrogram of a chirp of `chirp` seconds, at most
+    /// [`SPECTROGRAM_MAX_SAMPLES`].
+    fn spectrogram_samples(&self, chirp: f64) -> usize {
+        ((chirp * self.sampling_frequency).round() as usize).min(SPECTROGRAM_MAX_SAMPLES)
+    }
+
+    /// The spectrogram hop, raised so that `samples` make at most [`SPECTROGRAM_MAX_FRAMES`].
+    fn effective_spectrogram_hop(&self, samples: usize) -> usize {
+        self.views
+            .spectrogram_hop
+            .max(samples.div_ceil(SPECTROGRAM_MAX_FRAMES))
+            .max(1)
+    }
+
+    /// The expected beat of every reflection of the enabled objects on every chirp, next to the
+    /// closest detected peak.
+    fn compare_beats(&self, start_times: &[f64]) -> Vec<BeatComparison> {
+        let mut comparisons = vec![];
+        for (chirp, ((&start, peaks), &size)) in start_times
             .iter()
-            .map(|fft| {
-                let mags: Vec<f64> = fft.iter().map(|&(_, mag)| mag).collect();
-                let peak_indices = multiple_peak_finding(&mags);
-                peak_indices
-                    .into_iter()
-                    .map(|idx| (fft[idx].0, fft[idx].1))
-                    .collect::<Vec<(f64, f64)>>()
-            })
-            .collect();
+            .zip(&self.simulation.fft_peaks)
+            .zip(&self.simulation.fft_sizes)
+            .enumerate()
+        {
+            let idx = idx_at_t(&self.simulation.t, start);
+            let bin_width = self.sampling_frequency / size as f64;
+            for (object, obj) in self
+                .simulation
+                .objects
+                .iter()
+                .enumerate()
+                .filter(|(_, obj)| obj.enabled)
+            {
+                for beat in obj.reflection_beat_freqs(idx) {
+                    let expected = beat.abs();
+                    let detected = peaks
+                        .iter()
+                        .map(|&(freq, _)| freq)
+                        .min_by(|a, b| (a - expected).abs().total_cmp(&(b - expected).abs()));
+                    comparisons.push(BeatComparison {
+                        chirp,
+                        object,
+                        expected,
+                        detected,
+                        bin_width,
+                    });
+                }
+            }
+        }
+        comparisons
+    }
 
-        let v_min = -50.0;
-        let v_max = 50.0;
+    /// The line of ranges and velocities that explains each of the `peaks` of every chirp,
+    /// across the velocity span of the scene plot.
+    fn range_lines(
+        &self,
+        start_times: &[f64],
+        peaks: &[Vec<(f64, f64)>],
+    ) -> Vec<((f64, f64), (f64, f64))> {
+        let v_min = -MAX_VELOCITY;
+        let v_max = MAX_VELOCITY;
 
+        let config = self.config();
         let mut lines = vec![];
-        for (i, peaks) in self.fft_peaks.iter().enumerate() {
+        for (i, ((peaks, &start), chirp)) in peaks
+            .iter()
+            .zip(start_times)
+            .zip(self.durations())
+            .enumerate()
+        {
             // fetch the carry frequency at sample time
-            let idx = idx_at_t(&self.t, start_times[i]);
-            let f0 = self.f[idx];
+            let idx = idx_at_t(&self.simulation.t, start);
+            let Some(&f0) = self.simulation.f.get(idx) else {
+                continue;
+            };
 
             for &(bf, _) in peaks {
-                let r0 = -(doppler_shift(f0, v_min) - bf) * self.chirps[i] / self.bandwidth / 2.0
-                    * SPEED_OF_LIGHT;
-                let r1 = -(doppler_shift(f0, v_max) - bf) * self.chirps[i] / self.bandwidth / 2.0
-                    * SPEED_OF_LIGHT;
-                lines.push(((r0, -v_min), (r1, -v_max)));
+                let range_at = |v| self.peak_range(&config, bf, f0, i, chirp, v);
+                lines.push(((range_at(v_min), v_min), (range_at(v_max), v_max)));
             }
         }
-        self.lines = lines;
+        lines
     }
-}
 
-impl eframe::App for App {
-    /// Called by the framework to save state before shutdown.
-    fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        eframe::set_value(storage, eframe::APP_KEY, self);
+    /// The range that, together with the Doppler shift at `velocity`, explains a peak at `beat`
+    /// Hz on the chirp of `config` at index `chirp`, `duration` seconds long, starting at the
+    /// carrier frequency `f0`.
+    ///
+    /// On a rising chirp the range lowers the beat and the Doppler shift raises it, on a
+    /// falling one the range raises it as well, so the Doppler shift counts against it.
+    fn peak_range(
+        &self,
+        config: &RadarConfig,
+        beat: f64,
+        f0: f64,
+        chirp: usize,
+        duration: f64,
+        velocity: f64,
+    ) -> f64 {
+        let correction = if self.views.correct_range_bias {
+            self.analysis.range_bias()
+        } else {
+            0.0
+        };
+        let doppler = doppler_shift(f0, velocity, self.velocity_convention, self.doppler_model);
+        let doppler = if config.is_falling(chirp) {
+            -doppler
+        } else {
+            doppler
+        };
+        (beat + doppler) * self.waveform.ramp_duration(duration)
+            / config.chirp_bandwidth(chirp)
+            / 2.0
+            * SPEED_OF_LIGHT
+            - correction
     }
 
-    /// Called each time the UI needs repainting, which may be many times per second.
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        self.update();
-        egui::CentralPanel::default().show(ctx, |ui| {
-            // The central panel the region left after adding TopPanel's and SidePanel's
-            ui.heading("FMCW Radar demo 0");
+    /// RMS distance in meters between each enabled object and the closest range line of each
+    /// chirp at the object's velocity, for the bin and the interpolated peak frequencies.
+    fn range_line_residuals(&self) -> Option<(f64, f64)> {
+        let config = self.config();
+        let residual = |peaks: &[Vec<(f64, f64)>]| {
+            let mut squares = vec![];
+            for (i, ((peaks, &start), chirp)) in peaks
+                .iter()
+                .zip(&self.simulation.start_times())
+                .zip(self.durations())
+                .enumerate()
+            {
+                let Some(&f0) = self.simulation.f.get(idx_at_t(&self.simulation.t, start)) else {
+                    continue;
+                };
+                for obj in self.objects.iter().filter(|obj| obj.enabled) {
+                    let closest = peaks
+                        .iter()
+                        .map(|&(bf, _)| {
+                            let range = self.peak_range(&config, bf, f0, i, chirp, obj.velocity);
+                            (range - obj.range).abs()
+                        })
+                        .min_by(f64::total_cmp);
+                    squares.extend(closest.map(|distance| distance * distance));
+                }
+            }
+            (!squares.is_empty())
+                .then(|| (squares.iter().sum::<f64>() / squares.len() as f64).sqrt())
+        };
+        Some((
+            residual(&self.simulation.fft_peaks)?,
+            residual(&self.simulation.interpolated_peaks)?,
+        ))
+    }
 
-            egui::SidePanel::left("left_panel").show_inside(ui, |ui| {
-                ui.heading("Objects");
-                for (i, obj) in self.objects.iter_mut().enumerate() {
-                    ui.horizontal(|ui| {
-                        ui.label(
-                            egui::RichText::new(format!("Object {}", i + 1))
-                                .color(obj.2)
-                                .background_color(egui::Color32::BLACK),
-                        );
-                        ui.add(egui::Checkbox::new(&mut obj.3, ""));
-                    });
-                    ui.add(egui::Slider::new(&mut obj.0, 0.0..=100.0).text("Range"));
-                    ui.add(egui::Slider::new(&mut obj.1, -50.0..=50.0).text("Velocity"));
+    /// Two-sided spectrum of the complex beat signal of the sampling window starting at `start`,
+    /// after the IQ imbalance of the receiver.
+    fn iq_spectrum(&self, start: f64, chirp: f64) -> Vec<(f64, f64)> {
+        let t = self.simulation.sample_times(start, chirp);
+        let reflections = self
+            .simulation
+            .reflections_at(idx_at_t(&self.simulation.t, start));
+        let mut iq: Vec<Complex<f64>> = t
+            .iter()
+            .map(|&t| {
+                reflections
+                    .iter()
+                    .map(|&(f, amplitude)| {
+                        Complex::from_polar(amplitude, 2.0 * std::f64::consts::PI * f * t)
+                    })
+                    .sum()
+            })
+            .collect();
+        apply_iq_imbalance(
+            &mut iq,
+            self.analysis.iq_gain_imbalance_db,
+            self.analysis.iq_phase_imbalance_deg,
+        );
+        for (sample, w) in iq
+            .iter_mut()
+            .zip(self.analysis.window.coefficients(t.len()))
+        {
+            *sample *= w;
+        }
+        two_sided_spectrum(&iq, self.sampling_frequency)
+    }
+
+    /// Continuous response of the windowed FFT to every reflection of the enabled objects and their
+    /// ghosts, centered at their true beat frequency on every chirp.
+    fn compute_theoretical_responses(
+        &self,
+        start_times: &[f64],
+    ) -> Vec<(egui::Color32, Vec<[f64; 2]>)> {
+        let mut responses = vec![];
+        for (&start, chirp) in start_times.iter().zip(self.durations()) {
+            // Averaging transforms one segment at a time
+            let n = if self.analysis.welch {
+                self.simulation
+                    .sample_count(chirp)
+                    .min(self.simulation.fft_length(chirp))
+            } else {
+                self.simulation.sample_count(chirp)
+            };
+            // Offsets from the beat frequency, the same for every reflection on this chirp
+            let shape = window_response(
+                &self.analysis.window.coefficients(n),
+                self.sampling_frequency,
+                RESPONSE_OVERSAMPLING,
+                RESPONSE_HALF_WIDTH_BINS * self.sampling_frequency / n as f64,
+            );
+            // A sine splits into two halves at ±f, the single-sided spectrum shows one of them,
+            // rescaled by the coherent gain like the spectra
+            let scale =
+                0.5 * self.analysis.normalization.scale(n) / self.analysis.window.coherent_gain(n);
+            let idx = idx_at_t(&self.simulation.t, start);
+            let ghosts = self.simulation.ghosts.iter().map(|(i, ghost)| (*i, ghost));
+            for (i, obj) in self.simulation.objects.iter().enumerate().chain(ghosts) {
+                if !obj.enabled {
+                    continue;
                 }
-                ui.separator();
-                ui.heading("Radar Parameters");
-                ui.add(
-                    egui::Slider::new(&mut self.carrier_frequency, 10e9..=100e9)
-                        .text("Carrier Frequency (Hz)")
-                        .logarithmic(true)
-                        .step_by(1e6),
-                );
-                ui.add(
-                    egui::Slider::new(&mut self.bandwidth, 0.05e9..=4e9)
-                        .text("Bandwidth (Hz)")
-                        .logarithmic(true)
-                        .step_by(0.05e9),
-                );
-                for (i, chirp) in self.chirps.iter_mut().enumerate() {
-                    ui.add(
-                        egui::Slider::new(chirp, 1e-6..=100e-3)
-                            .text(format!("Chirp {} Duration (s)", i + 1))
-                            .logarithmic(true)
-                            .step_by(1e-6),
-                    );
+                let rcs = self.simulation.look_rcs(i, obj, start);
+                for (beat, range) in obj.reflection_beat_freqs(idx).zip(obj.reflection_ranges()) {
+                    let scale = scale * self.simulation.echo_amplitude(range, rcs);
+                    let response = shape
+                        .iter()
+                        .map(|&(offset, mag)| [beat.abs() + offset, mag * scale])
+                        .filter(|&[freq, _]| freq >= 0.0)
+                        .collect();
+                    responses.push((obj.color, response));
                 }
-                ui.add(
-                    egui::Slider::new(&mut self.sampling_frequency, 10e6..=100e6)
-                        .text("Sampling Rate (Hz)")
-                        .logarithmic(true)
-                        .step_by(1e6),
-                );
-                ui.add(
-                    egui::Slider::new(&mut self.sampling_duration, 1e-6..=1e-3)
-                        .text("Sampling Duration (s)")
-                        .logarithmic(true)
-                        .step_by(1e-6),
+            }
+        }
+        responses
+    }
+
+    /// Beat signals of [`RANGE_DOPPLER_CHIRPS`] copies of the sampling window starting at `start`
+    /// on the chirp at index `chirp`, `duration` seconds long, one chirp after another.
+    ///
+    /// Within a chirp every reflection beats at its frequency at `start`. From one chirp to the
+    /// next the target moves, which advances the phase of its beat by its Doppler shift at the
+    /// center of the band times the chirp duration.
+    fn range_doppler_frames(&self, chirp: usize, start: f64, duration: f64) -> Vec<Vec<f64>> {
+        let idx = idx_at_t(&self.simulation.t, start);
+        let center = self.config().chirp_center_frequency(chirp);
+        // (beat, Doppler shift, amplitude) of every reflection
+        let reflections: Vec<(f64, f64, f64)> = self
+            .simulation
+            .synthesized_objects()
+            .flat_map(|(i, obj)| {
+                let doppler = doppler_shift(
+                    center,
+                    obj.velocity,
+                    self.velocity_convention,
+                    self.doppler_model,
                 );
-                ui.separator();
-                ui.label(format!(
-                    "Sample count: {} (sampling_duration × sampling_frequency)",
-                    (self.sampling_duration * self.sampling_frequency).round() as usize
-                ));
-                ui.separator();
-            });
-            ui.add(egui::Checkbox::new(&mut self.plot_lines, "Plot Lines"));
-            egui_plot::Plot::new("my_plot")
-                .height(500.0)
-                .auto_bounds(false)
-                .default_x_bounds(0.0, 100.0)
-                .default_y_bounds(-60.0, 60.0)
-                .show(ui, |plot_ui| {
-                    if self.plot_lines {
-                        for (i, line) in self.lines.iter().enumerate() {
-                            let color = egui::Color32::from_rgb(200, 200, 200); // light gray for lines
-                            let plot_line = egui_plot::Line::new(
-                                format!("line_{i}"),
-                                egui_plot::PlotPoints::from_iter([
-                                    [line.0.0, line.0.1],
-                                    [line.1.0, line.1.1],
-                                ]),
-                            )
-                            .color(color)
-                            .width(2.0)
-                            .name(format!("Line {i}"));
-                            plot_ui.line(plot_line);
+                let rcs = self.simulation.look_rcs(i, obj, start);
+                obj.reflection_beat_freqs(idx)
+                    .zip(obj.reflection_ranges())
+                    .map(move |(beat, range)| {
+                        (
+                            beat.abs(),
+                            doppler,
+                            self.simulation.echo_amplitude(range, rcs),
+                        )
+                    })
+            })
+            .collect();
+        let fast_time = self.simulation.sample_times(0.0, duration);
+        (0..RANGE_DOPPLER_CHIRPS)
+            .map(|k| {
+                let slow_time = k as f64 * duration;
+                fast_time
+                    .iter()
+                    .map(|&t| {
+                        reflections
+                            .iter()
+                            .map(|&(beat, doppler, amplitude)| {
+                                let cycles = beat * t + doppler * slow_time;
+                                amplitude * (2.0 * std::f64::consts::PI * cycles).cos()
+                            })
+                            .sum()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// `(range, azimuth)` of every peak of the chirp at index `chirp`, which starts at `start` and
+    /// lasts `duration` seconds.
+    ///
+    /// Every element of the array samples the ideal beats; the spectrum of each element at the
+    /// bin of a peak gives one value per element, whose angle FFT peaks at the azimuth.
+    fn estimate_azimuths(&self, chirp: usize, start: f64, duration: f64) -> Vec<(f64, f64)> {
+        if self.rx_elements < 2 {
+            return vec![];
+        }
+        let idx = idx_at_t(&self.simulation.t, start);
+        let reflections: Vec<(f64, f64, f64)> = self
+            .simulation
+            .synthesized_objects()
+            .flat_map(|(i, obj)| {
+                let rcs = self.simulation.look_rcs(i, obj, start);
+                obj.reflection_beat_freqs(idx)
+                    .zip(obj.reflection_ranges())
+                    .map(move |(beat, range)| {
+                        (
+                            beat,
+                            self.simulation.echo_amplitude(range, rcs),
+                            obj.azimuth,
+                        )
+                    })
+            })
+            .collect();
+        let fft_size = self.simulation.fft_length(duration);
+        let element_bins: Vec<Vec<Complex<f64>>> = sample_array_signal(
+            &self.simulation.sample_times(start, duration),
+            &reflections,
+            self.rx_elements,
+            self.element_spacing,
+        )
+        .iter()
+        .map(|signal| beat_bins(signal, fft_size, self.analysis.window))
+        .collect();
+        let bin_width = self.sampling_frequency / fft_size as f64;
+        let ramp = self.waveform.ramp_duration(duration);
+        let bandwidth = self.config().chirp_bandwidth(chirp);
+        self.simulation
+            .fft_peaks
+            .get(chirp)
+            .into_iter()
+            .flatten()
+            .filter_map(|&(freq, _)| {
+                let bin = (freq / bin_width).round() as usize;
+                let snapshot: Vec<Complex<f64>> = element_bins
+                    .iter()
+                    .filter_map(|bins| bins.get(bin).copied())
+                    .collect();
+                let (azimuth, _) = angle_spectrum(&snapshot, self.element_spacing, ANGLE_FFT_BINS)
+                    .into_iter()
+                    .max_by(|a, b| a.1.total_cmp(&b.1))?;
+                Some((range_for_beat(freq, ramp, bandwidth), azimuth))
+            })
+            .collect()
+    }
+
+    /// `(range, velocity)` of every peak of the chirp at index `chirp`, which starts at `start` and
+    /// lasts `duration` seconds.
+    ///
+    /// A single chirp only gives the line of ranges and velocities that explain a peak. Over
+    /// [`RANGE_DOPPLER_CHIRPS`] repetitions of the chirp the bin of the peak advances in phase by
+    /// the Doppler shift, which gives the velocity, and with it the point on that line.
+    fn estimate_velocities(&self, chirp: usize, start: f64, duration: f64) -> Vec<(f64, f64)> {
+        let Some(peaks) = self.simulation.fft_peaks.get(chirp) else {
+            return vec![];
+        };
+        let Some(&f0) = self.simulation.f.get(idx_at_t(&self.simulation.t, start)) else {
+            return vec![];
+        };
+        let fft_size = self.simulation.fft_length(duration);
+        let bin_width = self.sampling_frequency / fft_size as f64;
+        let bins: Vec<usize> = peaks
+            .iter()
+            .map(|&(freq, _)| (freq / bin_width).round() as usize)
+            .collect();
+        let frames = self.range_doppler_frames(chirp, start, duration);
+        let config = self.config();
+        let center = config.chirp_center_frequency(chirp);
+        peaks
+            .iter()
+            .zip(doppler_per_chirp(
+                &frames,
+                &bins,
+                fft_size,
+                self.analysis.window,
+            ))
+            .map(|(&(freq, _), cycles)| {
+                let velocity = velocity_for_doppler(
+                    cycles / duration,
+                    center,
+                    self.velocity_convention,
+                    self.doppler_model,
+                );
+                (
+                    self.peak_range(&config, freq, f0, chirp, duration, velocity),
+                    velocity,
+                )
+            })
+            .collect()
+    }
+
+    /// Measured and theoretical -3 dB width in Hz of the strongest bin across all spectra.
+    fn main_lobe_widths(&self) -> Option<(f64, f64)> {
+        let (chirp, spectrum, peak_idx) = self
+            .durations()
+            .zip(&self.simulation.ffts)
+            .filter_map(|(chirp, spectrum)| {
+                let (peak_idx, _) = spectrum
+                    .iter()
+                    .enumerate()
+                    .max_by(|(_, a), (_, b)| a.1.total_cmp(&b.1))?;
+                Some((chirp, spectrum, peak_idx))
+            })
+            .max_by(|(_, a, i), (_, b, j)| {
+                let peak = |spectrum: &[(f64, f64)], idx: usize| {
+                    spectrum.get(idx).map_or(0.0, |&(_, mag)| mag)
+                };
+                peak(a.as_slice(), *i).total_cmp(&peak(b.as_slice(), *j))
+            })?;
+        let measured = measure_3db_width(spectrum, peak_idx);
+        let theoretical =
+            self.analysis.window.half_power_width_bins() / self.simulation.window_duration(chirp);
+        Some((measured, theoretical))
+    }
+
+    /// Duration in seconds of every chirp, in chirp order.
+    fn durations(&self) -> impl Iterator<Item = f64> + '_ {
+        self.chirps.iter().map(|chirp| chirp.duration)
+    }
+
+    /// The radar parameters currently set in the UI.
+    pub fn config(&self) -> RadarConfig {
+        RadarConfig {
+            carrier_frequency: self.carrier_frequency,
+            bandwidth: self.bandwidth,
+            sampling_frequency: self.sampling_frequency,
+            sampling_duration: self.sampling_duration,
+            rx_elements: self.rx_elements,
+            element_spacing: self.element_spacing,
+            chirps: self.chirps.clone(),
+            velocity_convention: self.velocity_convention,
+            doppler_model: self.doppler_model,
+            sweep_nonlinearity: self.sweep_nonlinearity,
+            waveform: self.waveform,
+        }
+    }
+
+    /// Replace the radar parameters, [sanitized](RadarConfig::sanitized).
+    pub fn set_config(&mut self, config: RadarConfig) {
+        let config = config.sanitized();
+        self.carrier_frequency = config.carrier_frequency;
+        self.bandwidth = config.bandwidth;
+        self.sampling_frequency = config.sampling_frequency;
+        self.sampling_duration = config.sampling_duration;
+        self.rx_elements = config.rx_elements;
+        self.element_spacing = config.element_spacing;
+        self.chirps = config.chirps;
+        self.velocity_convention = config.velocity_convention;
+        self.doppler_model = config.doppler_model;
+        self.sweep_nonlinearity = config.sweep_nonlinearity;
+        self.waveform = config.waveform;
+    }
+
+    /// The radar parameters and objects, without the computed beat frequencies.
+    pub fn scene(&self) -> Scene {
+        Scene {
+            config: self.config(),
+            objects: self
+                .objects
+                .iter()
+                .map(|obj| RadarObject {
+                    beat_freqs: vec![],
+                    scatterer_beat_freqs: vec![],
+                    ..obj.clone()
+                })
+                .collect(),
+        }
+    }
+
+    /// Replace the radar parameters and objects.
+    pub fn set_scene(&mut self, scene: Scene) {
+        self.set_config(scene.config);
+        self.objects = scene.objects;
+    }
+
+    /// Everything computed by the last [`Self::update`].
+    pub fn simulation_result(&self) -> SimulationResult {
+        self.simulation.result()
+    }
+
+    /// Replace the radar parameters and objects with the scene in `json`, clamped into the bounds
+    /// of the sliders.
+    ///
+    /// # Errors
+    ///
+    /// If `json` is not a valid scene.
+    pub fn import_scene_json(&mut self, json: &str) -> serde_json::Result<()> {
+        let scene = serde_json::from_str(json)?;
+        self.set_scene(clamp_to_sliders(scene));
+        Ok(())
+    }
+
+    fn export_scene_json(&self) -> std::io::Result<()> {
+        let json = serde_json::to_vec_pretty(&self.scene())?;
+        save_file("scene.json", &json)
+    }
+
+    /// Imports the scene from a JSON file the user picks; cancelling is not an error.
+    fn import_scene_file(&mut self) -> std::io::Result<()> {
+        let Some(contents) = open_file()? else {
+            return Ok(());
+        };
+        let json = String::from_utf8(contents).map_err(std::io::Error::other)?;
+        self.import_scene_json(&json)?;
+        self.scene_json.clear();
+        Ok(())
+    }
+
+    fn export_simulation_json(&self) -> std::io::Result<()> {
+        let json = serde_json::to_vec_pretty(&self.simulation_result())?;
+        save_file("simulation.json", &json)
+    }
+
+    /// One CSV row per enabled object and chirp with the true parameters of the object and the
+    /// quantities derived from them.
+    pub fn target_info_csv(&self) -> String {
+        let nyquist = self.sampling_frequency / 2.0;
+        let mut rows = vec![
+            "object,range_m,velocity_mps,rcs_m2,chirp,chirp_duration_s,beat_frequency_hz,expected_bin,aliases"
+                .to_owned(),
+        ];
+        for (chirp_idx, (&start, chirp)) in self
+            .simulation
+            .start_times()
+            .iter()
+            .zip(self.durations())
+            .enumerate()
+        {
+            let bin_width = self.sampling_frequency / self.simulation.fft_length(chirp) as f64;
+            let idx = idx_at_t(&self.simulation.t, start);
+            for (obj_idx, obj) in self
+                .simulation
+                .objects
+                .iter()
+                .enumerate()
+                .filter(|(_, obj)| obj.enabled)
+            {
+                for beat in obj.reflection_beat_freqs(idx) {
+                    let beat = beat.abs();
+                    rows.push(format!(
+                        "{},{},{},{},{},{chirp},{beat},{},{}",
+                        obj_idx + 1,
+                        obj.range,
+                        obj.velocity,
+                        obj.rcs,
+                        chirp_idx + 1,
+                        (beat / bin_width).round(),
+                        beat > nyquist
+                    ));
+                }
+            }
+        }
+        let mut csv = rows.join("\n");
+        csv.push('\n');
+        csv
+    }
+
+    fn export_target_info_csv(&self) -> std::io::Result<()> {
+        save_file("target_info.csv", self.target_info_csv().as_bytes())
+    }
+
+    /// The spectrum of every chirp as CSV, one row per frequency and one magnitude column per
+    /// chirp.
+    ///
+    /// Chirps with a different FFT length have their bins at different frequencies, so their
+    /// cells stay empty on the rows of frequencies they have no bin at.
+    fn fft_csv(&self) -> String {
+        let mut freqs: Vec<f64> = self
+            .simulation
+            .ffts
+            .iter()
+            .flatten()
+            .map(|&(freq, _)| freq)
+            .collect();
+        freqs.sort_by(f64::total_cmp);
+        freqs.dedup();
+
+        let mut header = "frequency_hz".to_owned();
+        for (i, chirp) in self
+            .durations()
+            .enumerate()
+            .take(self.simulation.ffts.len())
+        {
+            header.push_str(&format!(",chirp_{}_{:.1}us_magnitude", i + 1, chirp * 1e6));
+        }
+        let mut rows = vec![header];
+        for freq in freqs {
+            let mut row = freq.to_string();
+            for fft in &self.simulation.ffts {
+                row.push(',');
+                // The bins are in ascending frequency order
+                if let Ok(idx) = fft.binary_search_by(|&(f, _)| f.total_cmp(&freq))
+                    && let Some((_, mag)) = fft.get(idx)
+                {
+                    row.push_str(&mag.to_string());
+                }
+            }
+            rows.push(row);
+        }
+        let mut csv = rows.join("\n");
+        csv.push('\n');
+        csv
+    }
+
+    fn export_fft_csv(&self) -> std::io::Result<()> {
+        save_file("fft.csv", self.fft_csv().as_bytes())
+    }
+
+    /// The spectra and peaks of all chirps, for the SVG and PNG exports.
+    fn fft_export_plot(&self) -> SvgPlot {
+        let mut plot = SvgPlot::new("Frequency (MHz)", "Magnitude");
+        for (fft, &color) in self.simulation.ffts.iter().zip(CHIRP_COLORS.iter().cycle()) {
+            plot.line(
+                color,
+                fft.iter().map(|&(freq, mag)| [freq * 1e-6, mag]).collect(),
+            );
+        }
+        for (peaks, &color) in self
+            .simulation
+            .fft_peaks
+            .iter()
+            .zip(CHIRP_COLORS.iter().cycle())
+        {
+            for &(freq, mag) in peaks {
+                plot.point(color, [freq * 1e-6, mag]);
+            }
+        }
+        plot
+    }
+
+    fn export_fft_plot_svg(&self) -> std::io::Result<()> {
+        save_file("fft.svg", self.fft_export_plot().render().as_bytes())
+    }
+
+    fn export_fft_plot_png(&self) -> std::io::Result<()> {
+        let png = self
+            .fft_export_plot()
+            .render_png()
+            .map_err(std::io::Error::other)?;
+        save_file("fft.png", &png)
+    }
+
+    /// Saves the beat signal that goes into the FFT of the chirp selected for `my_plot3` as a WAV
+    /// file.
+    fn export_if_wav(&self) -> std::io::Result<()> {
+        let Some((&start, chirp)) = self
+            .simulation
+            .start_times()
+            .get(self.views.signal_plot_chirp)
+            .zip(self.durations().nth(self.views.signal_plot_chirp))
+        else {
+            return Ok(());
+        };
+        let sample_rate = if self.wav_at_listening_rate {
+            WAV_LISTENING_RATE
+        } else {
+            self.sampling_frequency.round() as u32
+        };
+        let wav = wav_file(&self.simulation.build_signal(start, chirp), sample_rate)
+            .map_err(std::io::Error::other)?;
+        save_file(
+            &format!("if_chirp{}.wav", self.views.signal_plot_chirp + 1),
+            &wav,
+        )
+    }
+
+    /// `(chirp, frequency, magnitude, snr_db)` of every detection at least `log_threshold_db`
+    /// above the noise floor of its spectrum.
+    fn strong_detections(&self) -> Vec<(usize, f64, f64, f64)> {
+        self.simulation
+            .ffts
+            .iter()
+            .zip(&self.simulation.fft_peaks)
+            .enumerate()
+            .flat_map(|(chirp, (fft, peaks))| {
+                let floor = noise_floor(fft);
+                peaks.iter().filter_map(move |&(freq, mag)| {
+                    let snr = snr_db(mag, floor);
+                    (snr >= self.log_threshold_db).then_some((chirp, freq, mag, snr))
+                })
+            })
+            .collect()
+    }
+
+    /// Number of detections across all chirps whose SNR reaches each threshold from 0 dB to
+    /// [`MAX_THRESHOLD_DB`].
+    fn detections_vs_threshold(&self) -> Vec<[f64; 2]> {
+        let mut snrs: Vec<f64> = self
+            .simulation
+            .ffts
+            .iter()
+            .zip(&self.simulation.fft_peaks)
+            .flat_map(|(fft, peaks)| {
+                let floor = noise_floor(fft);
+                peaks.iter().map(move |&(_, mag)| snr_db(mag, floor))
+            })
+            .collect();
+        snrs.sort_by(f64::total_cmp);
+        let steps = MAX_THRESHOLD_DB as usize * THRESHOLD_STEPS_PER_DB;
+        (0..=steps)
+            .map(|step| {
+                let threshold = step as f64 / THRESHOLD_STEPS_PER_DB as f64;
+                let below = snrs.partition_point(|&snr| snr < threshold);
+                [threshold, (snrs.len() - below) as f64]
+            })
+            .collect()
+    }
+
+    /// Append the strong detections of the last update to the log file, opening it if needed.
+    ///
+    /// File errors stop logging and are shown next to the toggle.
+    fn log_strong_detections(&mut self) {
+        if !self.log_detections {
+            self.detection_log = None;
+            return;
+        }
+        if self
+            .detection_log
+            .as_ref()
+            .is_none_or(|log| log.path() != self.log_path)
+        {
+            self.detection_log = match DetectionLog::open(&self.log_path) {
+                Ok(log) => Some(log),
+                Err(error) => {
+                    self.log_error = Some(error.to_string());
+                    self.log_detections = false;
+                    return;
+                }
+            };
+            self.log_error = None;
+        }
+        let detections = self.strong_detections();
+        if let Some(log) = &mut self.detection_log
+            && let Err(error) = log.append(&detections)
+        {
+            self.log_error = Some(error.to_string());
+            self.log_detections = false;
+            self.detection_log = None;
+        }
+    }
+
+    /// Move every object by its velocity times `dt` seconds, wrapping around at the ends of the
+    /// scene.
+    pub fn advance_objects(&mut self, dt: f64) {
+        for obj in &mut self.objects {
+            let receding = self.velocity_convention.receding(obj.velocity);
+            obj.range = (obj.range + receding * dt).rem_euclid(MAX_RANGE);
+        }
+    }
+
+    /// Advance the objects by the time since the last frame while playing.
+    fn animate_objects(&mut self, ctx: &egui::Context) {
+        if !self.playing {
+            return;
+        }
+        let dt = f64::from(ctx.input(|input| input.stable_dt));
+        self.advance_objects(dt * self.time_scale);
+        ctx.request_repaint();
+    }
+
+    fn playback_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let label = if self.playing {
+                "⏸ Pause"
+            } else {
+                "▶ Play"
+            };
+            if ui.button(label).clicked() {
+                self.playing = !self.playing;
+                if self.play_start_ranges.is_none() {
+                    self.play_start_ranges =
+                        Some(self.objects.iter().map(|obj| obj.range).collect());
+                }
+            }
+            if ui
+                .add_enabled(
+                    self.play_start_ranges.is_some(),
+                    egui::Button::new("⏹ Stop"),
+                )
+                .on_hover_text("Stop and move the objects back to where playback started")
+                .clicked()
+            {
+                self.playing = false;
+                for (obj, range) in self
+                    .objects
+                    .iter_mut()
+                    .zip(self.play_start_ranges.take().unwrap_or_default())
+                {
+                    obj.range = range;
+                }
+            }
+            ui.add(
+                egui::Slider::new(&mut self.time_scale, TIME_SCALE_RANGE)
+                    .text("Time Scale (×)")
+                    .logarithmic(true),
+            );
+        });
+    }
+
+    /// Move the selected object with the arrow keys: left/right change its range, up/down its
+    /// velocity. Shift takes larger steps.
+    ///
+    /// Keys go to the focused widget instead, e.g. a slider or text field.
+    fn nudge_selected_object(&mut self, ctx: &egui::Context) {
+        if ctx.memory(|memory| memory.focused().is_some()) {
+            return;
+        }
+        let (range_step, velocity_step) = ctx.input(|input| {
+            let step = if input.modifiers.shift {
+                NUDGE_STEPS.1
+            } else {
+                NUDGE_STEPS.0
+            };
+            let direction = |negative, positive| {
+                f64::from(
+                    i8::from(input.key_pressed(positive)) - i8::from(input.key_pressed(negative)),
+                )
+            };
+            (
+                step * direction(egui::Key::ArrowLeft, egui::Key::ArrowRight),
+                step * direction(egui::Key::ArrowDown, egui::Key::ArrowUp),
+            )
+        });
+        if (range_step, velocity_step) == (0.0, 0.0) {
+            return;
+        }
+        if let Some(obj) = self.objects.get_mut(self.selected_object) {
+            obj.range = (obj.range + range_step).clamp(0.0, MAX_RANGE);
+            obj.velocity = (obj.velocity + velocity_step).clamp(-MAX_VELOCITY, MAX_VELOCITY);
+        }
+    }
+
+    /// Which way a positive velocity points and how it shifts the echoes.
+    fn doppler_ui(&mut self, ui: &mut egui::Ui) {
+        egui::ComboBox::from_label("Velocity Convention")
+            .selected_text(self.velocity_convention.label())
+            .show_ui(ui, |ui| {
+                for convention in VelocityConvention::ALL {
+                    ui.selectable_value(
+                        &mut self.velocity_convention,
+                        convention,
+                        convention.label(),
+                    );
+                }
+            });
+        egui::ComboBox::from_label("Doppler Model")
+            .selected_text(self.doppler_model.name())
+            .show_ui(ui, |ui| {
+                for model in DopplerModel::ALL {
+                    ui.selectable_value(&mut self.doppler_model, model, model.name())
+                        .on_hover_text(model.description());
+                }
+            });
+    }
+
+    /// How strong the echoes are and how they weaken with range.
+    fn echo_ui(&mut self, ui: &mut egui::Ui) {
+        egui::ComboBox::from_label("Path Loss")
+            .selected_text(self.analysis.path_loss.name())
+            .show_ui(ui, |ui| {
+                for path_loss in PathLoss::ALL {
+                    ui.selectable_value(&mut self.analysis.path_loss, path_loss, path_loss.name());
+                }
+            });
+        ui.add(
+            egui::Slider::new(&mut self.analysis.echo_gain, 1e-2..=1e8)
+                .text("Echo Gain")
+                .logarithmic(true),
+        )
+        .on_hover_text("Amplitude of the echo of a 1 m² target at 1 m, to keep the plots in range");
+        ui.add(
+            egui::Slider::new(&mut self.analysis.atmospheric_loss, ATMOSPHERIC_LOSS_RANGE)
+                .text("Atmospheric loss (dB/km)"),
+        )
+        .on_hover_text(
+            "Attenuation on the way to the target and back. Clear air takes about 0.4 dB/km at \
+             77 GHz, heavy rain 10 to 30 dB/km.",
+        );
+        ui.horizontal(|ui| {
+            ui.add(egui::Checkbox::new(
+                &mut self.analysis.multipath,
+                "Multipath",
+            ))
+            .on_hover_text(
+                "Add a weaker ghost behind every object, from its echo bouncing off a flat \
+                     ground on the way there or back",
+            );
+            ui.add_enabled(
+                self.analysis.multipath,
+                egui::Slider::new(&mut self.analysis.radar_height, RADAR_HEIGHT_RANGE)
+                    .text("Radar height (m)"),
+            )
+            .on_hover_text("Height of the radar and the targets above the ground");
+        });
+    }
+
+    fn objects_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Objects");
+        self.doppler_ui(ui);
+        self.echo_ui(ui);
+        ui.add(egui::Checkbox::new(
+            &mut self.analysis.merge_coincident,
+            "Merge coincident objects",
+        ))
+        .on_hover_text("Synthesize objects at the same range and velocity only once");
+        for &(first, dup) in &self.simulation.coincident_objects {
+            let consequence = if self.analysis.merge_coincident {
+                "merged into one"
+            } else {
+                "adding up to one peak of double height"
+            };
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                format!(
+                    "⚠ Objects {} and {} coincide, {consequence}.",
+                    first + 1,
+                    dup + 1
+                ),
+            );
+        }
+        let mut removed = None;
+        for (i, obj) in self.objects.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.selectable_value(
+                    &mut self.selected_object,
+                    i,
+                    egui::RichText::new(format!("Object {}", i + 1))
+                        .color(obj.color)
+                        .background_color(egui::Color32::BLACK),
+                )
+                .on_hover_text(
+                    "Select to place this object by clicking into the scene plot or to move it \
+                     with the arrow keys (shift for larger steps)",
+                );
+                ui.add(egui::Checkbox::new(&mut obj.enabled, ""));
+                if ui.button("Remove").clicked() {
+                    removed = Some(i);
+                }
+            });
+            ui.add(egui::Slider::new(&mut obj.range, 0.0..=MAX_RANGE).text("Range"));
+            ui.add(
+                egui::Slider::new(&mut obj.velocity, -MAX_VELOCITY..=MAX_VELOCITY)
+                    .text(format!("Velocity ({})", self.velocity_convention.label())),
+            );
+            ui.add(
+                egui::Slider::new(&mut obj.rcs, RCS_RANGE)
+                    .text("RCS (m²)")
+                    .logarithmic(true),
+            );
+            ui.add(egui::Slider::new(&mut obj.azimuth, AZIMUTH_RANGE).text("Azimuth (°)"));
+            ui.horizontal(|ui| {
+                ui.add(egui::Checkbox::new(&mut obj.extended, "Extended"))
+                    .on_hover_text("Reflect at the front and the rear instead of the center");
+                ui.add_enabled(
+                    obj.extended,
+                    egui::Slider::new(&mut obj.length, OBJECT_LENGTH_RANGE).text("Length (m)"),
+                );
+            });
+            micro_motion_ui(ui, obj);
+            fluctuation_ui(ui, obj, i);
+        }
+        if let Some(i) = removed {
+            self.objects.remove(i);
+            // Keep the same object selected, or the one that took the place of the removed one
+            if self.selected_object > i || self.selected_object == self.objects.len() {
+                self.selected_object = self.selected_object.saturating_sub(1);
+            }
+        }
+        if ui.button("Add Object").clicked() {
+            self.objects.push(self.next_object());
+        }
+        self.random_scene_ui(ui);
+        self.playback_ui(ui);
+    }
+
+    fn random_scene_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui
+                .button("Randomize Scene")
+                .on_hover_text(
+                    "Replace the objects with ones at random ranges and velocities, to stress the \
+                     detector. The seed advances after every scene; set it back to draw one again.",
+                )
+                .clicked()
+            {
+                self.randomize_scene(self.random_object_count, self.random_scene_seed);
+                self.random_scene_seed = self.random_scene_seed.wrapping_add(1);
+            }
+            ui.add(
+                egui::Slider::new(&mut self.random_object_count, RANDOM_OBJECT_COUNT_RANGE)
+                    .text("Objects"),
+            );
+            ui.label("Seed");
+            ui.add(egui::DragValue::new(&mut self.random_scene_seed));
+        });
+    }
+
+    /// Replace the objects with `count` enabled objects at random ranges and velocities within the
+    /// bounds of their sliders, in random colors. The same `seed` always draws the same scene.
+    pub fn randomize_scene(&mut self, count: usize, seed: u64) {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        self.objects = (0..count)
+            .map(|_| {
+                let range = rng.random_range(0.0..=MAX_RANGE);
+                let velocity = rng.random_range(-MAX_VELOCITY..=MAX_VELOCITY);
+                let hue = rng.random::<f32>();
+                let color = egui::ecolor::Hsva::new(hue, 0.8, 1.0, 1.0).into();
+                RadarObject::new(range, velocity, color, true)
+            })
+            .collect();
+        self.selected_object = 0;
+        self.play_start_ranges = None;
+        self.update();
+    }
+
+    /// A new stationary object at the range farthest from all existing objects, in the first
+    /// palette color not in use yet.
+    fn next_object(&self) -> RadarObject {
+        let candidates =
+            (0..=NEW_OBJECT_RANGES).map(|i| i as f64 * MAX_RANGE / NEW_OBJECT_RANGES as f64);
+        let clearance = |range: f64| {
+            self.objects
+                .iter()
+                .map(|obj| (obj.range - range).abs())
+                .fold(f64::INFINITY, f64::min)
+        };
+        let range = candidates
+            .max_by(|&a, &b| clearance(a).total_cmp(&clearance(b)))
+            .unwrap_or(MAX_RANGE / 2.0);
+        let color = OBJECT_PALETTE
+            .iter()
+            .copied()
+            .find(|&color| self.objects.iter().all(|obj| obj.color != color))
+            .unwrap_or_else(|| {
+                let i = self.objects.len() % OBJECT_PALETTE.len();
+                OBJECT_PALETTE
+                    .get(i)
+                    .copied()
+                    .unwrap_or(egui::Color32::WHITE)
+            });
+        RadarObject::new(range, 0.0, color, true)
+    }
+
+    /// Ramp shape and the duration of every chirp, with the beat slope each one gives.
+    fn chirps_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Waveform:");
+            for waveform in Waveform::ALL {
+                ui.radio_value(&mut self.waveform, waveform, waveform.name());
+            }
+        })
+        .response
+        .on_hover_text(
+            "A triangle sweeps up over the first half of each chirp and back down over the \
+             second, so Doppler shifts the up- and down-ramp beats in opposite directions.",
+        );
+        let (carrier_frequency, global_bandwidth) = (self.carrier_frequency, self.bandwidth);
+        for (i, chirp) in self.chirps.iter_mut().enumerate() {
+            let Chirp {
+                duration,
+                falling,
+                bandwidth,
+                start_freq: start,
+            } = chirp;
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::Slider::new(duration, CHIRP_DURATION_RANGE)
+                        .text(format!("Chirp {} Duration (s)", i + 1))
+                        .logarithmic(true)
+                        .step_by(1e-6),
+                );
+                ui.checkbox(falling, "Falling")
+                    .on_hover_text("Ramp down from the top of the band instead of up");
+                let mut own_band = bandwidth.is_some() || start.is_some();
+                if ui
+                    .checkbox(&mut own_band, "Own band")
+                    .on_hover_text(
+                        "Sweep another band than the carrier frequency and bandwidth above, \
+                         for stepped-frequency or multi-band chirp sequences",
+                    )
+                    .changed()
+                {
+                    *start = own_band.then_some(carrier_frequency);
+                    *bandwidth = own_band.then_some(global_bandwidth);
+                }
+            });
+            if let Some(start) = start {
+                let text = format!("Chirp {} Start Frequency (Hz)", i + 1);
+                frequency_input(ui, start, CARRIER_FREQUENCY_RANGE, 1e6, &text);
+            }
+            if let Some(bandwidth) = bandwidth {
+                let text = format!("Chirp {} Bandwidth (Hz)", i + 1);
+                frequency_input(ui, bandwidth, BANDWIDTH_RANGE, 0.05e9, &text);
+            }
+            // Where a target lands in the FFT: its range times this slope
+            ui.label(format!(
+                "Chirp {}: {:.2} kHz of beat per meter",
+                i + 1,
+                beat_per_meter(
+                    self.waveform.ramp_duration(*duration),
+                    bandwidth.unwrap_or(global_bandwidth)
+                ) * 1e-3
+            ));
+        }
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(self.chirps.len() < MAX_CHIRPS, egui::Button::new("+"))
+                .on_hover_text(format!("Add a chirp of {} µs", NEW_CHIRP_DURATION * 1e6))
+                .clicked()
+            {
+                self.chirps.push(Chirp::new(NEW_CHIRP_DURATION));
+            }
+            if ui
+                .add_enabled(self.chirps.len() > 1, egui::Button::new("−"))
+                .on_hover_text("Remove the last chirp")
+                .clicked()
+            {
+                self.chirps.pop();
+            }
+            ui.label(format!("{} chirps", self.chirps.len()));
+        });
+        self.views.signal_plot_chirp = self
+            .views
+            .signal_plot_chirp
+            .min(self.chirps.len().saturating_sub(1));
+    }
+
+    /// Phase noise, receiver noise and the seed they and the fluctuating cross sections are drawn
+    /// from.
+    fn noise_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add(egui::Checkbox::new(
+                &mut self.analysis.phase_noise,
+                "Phase noise",
+            ));
+            ui.add_enabled(
+                self.analysis.phase_noise,
+                egui::Slider::new(&mut self.analysis.phase_noise_dbc_hz, PHASE_NOISE_RANGE)
+                    .text("Phase Noise (dBc/Hz)"),
+            )
+            .on_hover_text(format!(
+                "Single-sideband phase noise of the carrier {} MHz from it. The random walk of \
+                 the phase raises a noise pedestal around every peak that falls off with 1/f².",
+                PHASE_NOISE_OFFSET * 1e-6
+            ));
+        });
+        ui.horizontal(|ui| {
+            ui.add(egui::Checkbox::new(&mut self.analysis.no_noise, "No noise"));
+            ui.add_enabled(
+                !self.analysis.no_noise,
+                egui::Slider::new(&mut self.analysis.snr_db, -20.0..=60.0).text("SNR (dB)"),
+            )
+            .on_hover_text("White receiver noise this far below the power of the beat signal");
+        });
+        ui.horizontal(|ui| {
+            ui.add(egui::Slider::new(&mut self.analysis.adc_bits, ADC_BITS_RANGE).text("ADC Bits"))
+                .on_hover_text("Resolution of the ADC, 0 for no quantization");
+            ui.add_enabled(
+                self.analysis.adc_bits > 0,
+                egui::Slider::new(&mut self.analysis.adc_fullscale, ADC_FULLSCALE_RANGE)
+                    .text("Full Scale")
+                    .logarithmic(true),
+            )
+            .on_hover_text("Amplitude beyond which the samples saturate");
+        });
+        ui.horizontal(|ui| {
+            ui.label("Seed");
+            ui.add(egui::DragValue::new(&mut self.analysis.seed));
+            if ui
+                .button("Reroll")
+                .on_hover_text(
+                    "Draw new phase and receiver noise and a new scan of fluctuating RCS",
+                )
+                .clicked()
+            {
+                self.analysis.seed = self.analysis.seed.wrapping_add(1);
+            }
+        });
+    }
+
+    fn radar_parameters_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Radar Parameters");
+        frequency_input(
+            ui,
+            &mut self.carrier_frequency,
+            CARRIER_FREQUENCY_RANGE,
+            1e6,
+            "Carrier Frequency (Hz)",
+        );
+        frequency_input(
+            ui,
+            &mut self.bandwidth,
+            BANDWIDTH_RANGE,
+            0.05e9,
+            "Bandwidth (Hz)",
+        );
+        ui.add(
+            egui::Slider::new(&mut self.sweep_nonlinearity, SWEEP_NONLINEARITY_RANGE)
+                .text("Sweep Nonlinearity (× bandwidth)"),
+        )
+        .on_hover_text(
+            "Largest deviation of the sweep from a linear ramp, halfway through the chirp. \
+             It makes beats drift within the sampling window, more so for distant targets.",
+        );
+        self.chirps_ui(ui);
+        frequency_input(
+            ui,
+            &mut self.sampling_frequency,
+            SAMPLING_FREQUENCY_RANGE,
+            1e6,
+            "Sampling Rate (Hz)",
+        );
+        ui.add(
+            egui::Slider::new(&mut self.sampling_duration, SAMPLING_DURATION_RANGE)
+                .text("Sampling Duration (s)")
+                .logarithmic(true)
+                .step_by(1e-6),
+        );
+        ui.add(
+            egui::Slider::new(
+                &mut self.analysis.sample_start_fraction,
+                SAMPLE_START_FRACTION_RANGE,
+            )
+            .text("Sample Start (fraction of chirp)"),
+        )
+        .on_hover_text("Where within each chirp the ADC window begins");
+        ui.add(egui::Checkbox::new(
+            &mut self.analysis.clamp_sampling_window,
+            "Clamp sampling window to chirp",
+        ))
+        .on_hover_text("End the sampling window no later than its chirp");
+        egui::ComboBox::from_label("Window")
+            .selected_text(self.analysis.window.name())
+            .show_ui(ui, |ui| {
+                for window in Window::ALL {
+                    ui.selectable_value(&mut self.analysis.window, window, window.name());
+                }
+            });
+        self.dc_removal_ui(ui);
+        egui::ComboBox::from_label("FFT Normalization")
+            .selected_text(self.analysis.normalization.name())
+            .show_ui(ui, |ui| {
+                for normalization in Normalization::ALL {
+                    ui.selectable_value(
+                        &mut self.analysis.normalization,
+                        normalization,
+                        normalization.name(),
+                    )
+                    .on_hover_text(normalization.description());
+                }
+            });
+        egui::ComboBox::from_label("Signal Source")
+            .selected_text(self.analysis.signal_source.name())
+            .show_ui(ui, |ui| {
+                for source in SignalSource::ALL {
+                    ui.selectable_value(&mut self.analysis.signal_source, source, source.name())
+                        .on_hover_text(source.description());
+                }
+            });
+        self.noise_ui(ui);
+        ui.separator();
+        ui.label(format!(
+            "Sample count: {} (sampling_duration × sampling_frequency)",
+            (self.sampling_duration * self.sampling_frequency).round() as usize
+        ));
+        self.resolution_ui(ui);
+        self.fft_size_ui(ui);
+        self.overrun_warning_ui(ui);
+        ui.separator();
+    }
+
+    fn dc_removal_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add(egui::Checkbox::new(
+                &mut self.analysis.remove_dc,
+                "Remove DC",
+            ))
+            .on_hover_text(
+                "Subtract the mean of the samples before the FFT, so an offset does not hide \
+                     targets in the lowest bins",
+            );
+            ui.add_enabled(
+                self.analysis.remove_dc,
+                egui::Checkbox::new(&mut self.analysis.remove_trend, "and linear trend"),
+            )
+            .on_hover_text(
+                "Subtract the best-fitting straight line instead, which also removes a drift",
+            );
+        });
+    }
+
+    /// A warning for the chirps whose sampling window runs past their end.
+    fn overrun_warning_ui(&self, ui: &mut egui::Ui) {
+        if self.overlong_chirps.is_empty() {
+            return;
+        }
+        let chirps = self
+            .overlong_chirps
+            .iter()
+            .filter_map(|&i| {
+                let chirp = self.chirps.get(i)?;
+                Some(format!("Chirp {} ({:.1} µs)", i + 1, chirp.duration * 1e6))
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let consequence = if self.analysis.clamp_sampling_window {
+            "even the shortest window starts too late"
+        } else {
+            "the FFT window crosses the frequency jump at the chirp boundary"
+        };
+        ui.colored_label(
+            egui::Color32::RED,
+            format!(
+                "⚠ Sampling window ({:.1} µs from {:.0}% of the chirp) runs past the end of \
+                 {chirps}: {consequence}.",
+                self.sampling_duration * 1e6,
+                self.analysis.sample_start_fraction * 100.0
+            ),
+        );
+    }
+
+    /// What the current parameters can resolve, with a warning once the beats alias.
+    fn resolution_ui(&self, ui: &mut egui::Ui) {
+        let resolution = &self.resolution;
+        ui.label(format!(
+            "Range resolution: {:.3} m (c/2B of the widest chirp)",
+            resolution.range
+        ));
+        ui.label(format!(
+            "Unambiguous range: {:.1} m (c·fs·Tc/4B of the shortest chirp)",
+            resolution.max_range
+        ))
+        .on_hover_text("Range whose beat reaches Nyquist, fs/2, on the steepest ramp");
+        ui.label(format!(
+            "Velocity resolution: {:.2} m/s (λ/2MTc over M = {RANGE_DOPPLER_CHIRPS} chirps)",
+            resolution.velocity
+        ))
+        .on_hover_text("Of the range-Doppler map of the chirp selected for the signal plot");
+        let nyquist = self.sampling_frequency / 2.0;
+        let beats = format!(
+            "Highest beat: {:.2} MHz, Nyquist {:.2} MHz",
+            resolution.max_beat * 1e-6,
+            nyquist * 1e-6
+        );
+        if resolution.max_beat > nyquist {
+            ui.colored_label(egui::Color32::RED, format!("⚠ {beats}: beats alias"));
+        } else {
+            ui.label(beats);
+        }
+        if !self.aliasing_objects.is_empty() {
+            let objects = self
+                .aliasing_objects
+                .iter()
+                .map(|i| format!("Object {}", i + 1))
+                .collect::<Vec<_>>()
+                .join(", ");
+            ui.colored_label(
+                egui::Color32::RED,
+                format!(
+                    "⚠ {objects} beat above Nyquist and alias: their peaks show up at the wrong \
+                     range."
+                ),
+            );
+        }
+    }
+
+    /// Length of the FFT and cap on it, with a warning for chirps whose samples do not fit.
+    fn fft_size_ui(&mut self, ui: &mut egui::Ui) {
+        let mut zero_padding = self.analysis.fft_size != 0;
+        if ui
+            .checkbox(&mut zero_padding, "Custom FFT size")
+            .on_hover_text(
+                "Pads the samples with zeros up to the FFT size, which interpolates the spectrum \
+                 onto a finer grid, or truncates them if they are longer",
+            )
+            .changed()
+        {
+            self.analysis.fft_size = if zero_padding {
+                self.durations()
+                    .map(|chirp| self.simulation.sample_count(chirp))
+                    .max()
+                    .unwrap_or(1)
+                    .next_power_of_two()
+            } else {
+                0
+            };
+        }
+        if zero_padding {
+            ui.add(
+                egui::Slider::new(&mut self.analysis.fft_size, 16..=1 << 20)
+                    .text("FFT Size")
+                    .logarithmic(true),
+            );
+        }
+        ui.add(
+            egui::Slider::new(&mut self.analysis.max_fft_size, 256..=1 << 20)
+                .text("Max FFT Size")
+                .logarithmic(true),
+        );
+        ui.checkbox(&mut self.analysis.welch, "Welch averaging")
+            .on_hover_text(
+                "Average the spectra of overlapping segments of the samples, which steadies the \
+                 noise floor at the cost of the wider bins of the shorter FFT",
+            );
+        if self.analysis.welch {
+            ui.add(
+                egui::Slider::new(&mut self.analysis.welch_segment, WELCH_SEGMENT_RANGE)
+                    .text("Segment Length")
+                    .logarithmic(true),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.analysis.welch_overlap, WELCH_OVERLAP_RANGE)
+                    .text("Segment Overlap"),
+            );
+        }
+        let capped: Vec<String> = self
+            .durations()
+            .zip(&self.simulation.fft_sizes)
+            .enumerate()
+            .filter(|&(_, (chirp, &size))| {
+                !self.analysis.welch && self.simulation.sample_count(chirp) > size
+            })
+            .map(|(i, (chirp, &size))| {
+                format!(
+                    "Chirp {}: {size} of {}",
+                    i + 1,
+                    self.simulation.sample_count(chirp)
+                )
+            })
+            .collect();
+        if !capped.is_empty() {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                format!(
+                    "⚠ Only the first samples are transformed ({}), which widens the bins.",
+                    capped.join(", ")
+                ),
+            );
+        }
+    }
+
+    fn receiver_chain_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Receiver Chain");
+        let mut delay_ns = self.analysis.rx_delay * 1e9;
+        if ui
+            .add(egui::Slider::new(&mut delay_ns, 0.0..=100.0).text("Receiver Delay (ns)"))
+            .changed()
+        {
+            self.analysis.rx_delay = delay_ns * 1e-9;
+        }
+        ui.add(egui::Checkbox::new(
+            &mut self.views.correct_range_bias,
+            "Correct range bias",
+        ));
+        let delay = chain_group_delay(&self.analysis.chain_stage_delays());
+        let status = if self.views.correct_range_bias {
+            "subtracted from the range lines"
+        } else {
+            "not corrected"
+        };
+        ui.label(format!(
+            "Group delay {:.1} ns → range bias {:.2} m, {status}",
+            delay * 1e9,
+            self.analysis.range_bias()
+        ));
+        ui.separator();
+    }
+
+    fn detection_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Detection (CA-CFAR)");
+        ui.add(egui::Slider::new(&mut self.analysis.cfar_guard, 0..=16).text("Guard Bins"))
+            .on_hover_text("Bins next to the bin under test left out of its noise estimate");
+        ui.add(egui::Slider::new(&mut self.analysis.cfar_train, 1..=64).text("Training Bins"))
+            .on_hover_text("Bins on either side averaged into the noise estimate");
+        ui.add(
+            egui::Slider::new(&mut self.analysis.cfar_alpha, 1.0..=20.0)
+                .text("Threshold Factor")
+                .logarithmic(true),
+        )
+        .on_hover_text("A bin is detected above this factor times its noise estimate");
+        ui.separator();
+    }
+
+    fn antenna_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Antenna Array");
+        ui.add(egui::Slider::new(&mut self.rx_elements, RX_ELEMENTS_RANGE).text("RX Elements"));
+        ui.add(
+            egui::Slider::new(&mut self.element_spacing, ELEMENT_SPACING_RANGE)
+                .text("Element Spacing (λ)")
+                .step_by(0.05),
+        );
+        let wavelength = SPEED_OF_LIGHT / self.carrier_frequency;
+        ui.label(format!(
+            "Wavelength: {:.2} mm, element spacing: {:.2} mm",
+            wavelength * 1e3,
+            self.element_spacing * wavelength * 1e3
+        ));
+        ui.label(format!(
+            "Angular resolution: {:.1}° at boresight, {:.1}° at ±45° (λ/(N·d·cos θ))",
+            angular_resolution(self.rx_elements, self.element_spacing, 0.0).to_degrees(),
+            angular_resolution(
+                self.rx_elements,
+                self.element_spacing,
+                std::f64::consts::FRAC_PI_4
+            )
+            .to_degrees()
+        ));
+        if self.element_spacing > 0.5 {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                "⚠ Element spacing above λ/2 produces grating lobes.",
+            );
+        }
+        ui.separator();
+    }
+
+    fn inverse_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Inverse");
+        let inverse = &mut self.inverse;
+        ui.add(
+            egui::DragValue::new(&mut inverse.beat)
+                .range(0.0..=self.sampling_frequency / 2.0)
+                .speed(1e3)
+                .prefix("Beat: ")
+                .suffix(" Hz"),
+        );
+        egui::ComboBox::from_label("Chirp")
+            .selected_text(format!("Chirp {}", inverse.chirp + 1))
+            .show_ui(ui, |ui| {
+                for i in 0..self.chirps.len() {
+                    ui.selectable_value(&mut inverse.chirp, i, format!("Chirp {}", i + 1));
+                }
+            });
+        egui::ComboBox::from_label("Object")
+            .selected_text(format!("Object {}", inverse.object + 1))
+            .show_ui(ui, |ui| {
+                for i in 0..self.objects.len() {
+                    ui.selectable_value(&mut inverse.object, i, format!("Object {}", i + 1));
+                }
+            });
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut inverse.solve, InverseSolve::Range, "Range (v = 0)");
+            ui.radio_value(
+                &mut inverse.solve,
+                InverseSolve::Velocity,
+                "Velocity (at current range)",
+            );
+        });
+
+        let inverse = self.inverse;
+        let config = self.config();
+        let (Some(chirp), Some(&start), Some(obj)) = (
+            self.durations().nth(inverse.chirp),
+            self.simulation.start_times().get(inverse.chirp),
+            self.objects.get(inverse.object),
+        ) else {
+            return;
+        };
+        let (range, velocity) = match inverse.solve {
+            InverseSolve::Range => (
+                range_for_beat(
+                    inverse.beat,
+                    self.waveform.ramp_duration(chirp),
+                    config.chirp_bandwidth(inverse.chirp),
+                ),
+                0.0,
+            ),
+            InverseSolve::Velocity => {
+                let Some(&frequency) = self.simulation.f.get(idx_at_t(&self.simulation.t, start))
+                else {
+                    return;
+                };
+                let doppler = doppler_for_beat(
+                    inverse.beat,
+                    obj.range,
+                    self.waveform.ramp_duration(chirp),
+                    config.is_falling(inverse.chirp),
+                    config.chirp_bandwidth(inverse.chirp),
+                );
+                let velocity = velocity_for_doppler(
+                    doppler,
+                    frequency,
+                    self.velocity_convention,
+                    self.doppler_model,
+                );
+                (obj.range, velocity)
+            }
+        };
+        ui.label(format!("R = {range:.2} m, v = {velocity:.2} m/s"));
+        if ui.button("Place Object").clicked()
+            && let Some(obj) = self.objects.get_mut(inverse.object)
+        {
+            obj.range = range;
+            obj.velocity = velocity;
+            obj.enabled = true;
+        }
+        ui.separator();
+    }
+
+    fn scene_json_ui(&mut self, ui: &mut egui::Ui) {
+        egui::ComboBox::from_label("Preset")
+            .selected_text("Load a preset…")
+            .show_ui(ui, |ui| {
+                for preset in Preset::ALL {
+                    if ui
+                        .selectable_label(false, preset.name())
+                        .on_hover_text(preset.description())
+                        .clicked()
+                    {
+                        self.set_scene(preset.scene());
+                        self.update();
+                    }
+                }
+            });
+        ui.horizontal(|ui| {
+            if ui.button("Export Scene").clicked() {
+                self.import_error = self.export_scene_json().err().map(|e| e.to_string());
+            }
+            if ui
+                .button("Import Scene")
+                .on_hover_text("Values outside the ranges of the sliders are clamped")
+                .clicked()
+            {
+                self.import_error = self.import_scene_file().err().map(|e| e.to_string());
+            }
+        });
+        if let Some(error) = &self.import_error {
+            ui.colored_label(egui::Color32::RED, format!("Scene file failed: {error}"));
+        }
+        ui.collapsing("Scene JSON", |ui| {
+            if self.scene_json.is_empty() || ui.button("Show Current Scene").clicked() {
+                self.scene_json =
+                    serde_json::to_string_pretty(&self.scene()).unwrap_or_else(|e| e.to_string());
+                self.scene_json_error = None;
+            }
+            ui.add(
+                egui::TextEdit::multiline(&mut self.scene_json)
+                    .code_editor()
+                    .desired_rows(10)
+                    .desired_width(f32::INFINITY),
+            );
+            if ui.button("Apply").clicked() {
+                let json = std::mem::take(&mut self.scene_json);
+                self.scene_json_error = self.import_scene_json(&json).err().map(|e| e.to_string());
+                self.scene_json = json;
+            }
+            if let Some(error) = &self.scene_json_error {
+                ui.colored_label(egui::Color32::RED, format!("Invalid scene: {error}"));
+            }
+        });
+        ui.separator();
+    }
+
+    fn export_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Export");
+        if ui.button("Export Simulation JSON").clicked() {
+            self.export_error = self.export_simulation_json().err().map(|e| e.to_string());
+        }
+        if ui
+            .button("Export Target Info CSV")
+            .on_hover_text(
+                "True parameters, beat frequency, expected FFT bin and aliasing of every object on \
+                 every chirp",
+            )
+            .clicked()
+        {
+            self.export_error = self.export_target_info_csv().err().map(|e| e.to_string());
+        }
+        if ui.button("Export FFT CSV").clicked() {
+            self.export_error = self.export_fft_csv().err().map(|e| e.to_string());
+        }
+        if ui.button("Export FFT Plot SVG").clicked() {
+            self.export_error = self.export_fft_plot_svg().err().map(|e| e.to_string());
+        }
+        if ui.button("Export FFT Plot PNG").clicked() {
+            self.export_error = self.export_fft_plot_png().err().map(|e| e.to_string());
+        }
+        ui.horizontal(|ui| {
+            if ui
+                .button("Export IF WAV")
+                .on_hover_text(format!(
+                    "The beat signal of chirp {} that goes into its FFT, as 16-bit PCM",
+                    self.views.signal_plot_chirp + 1
+                ))
+                .clicked()
+            {
+                self.export_error = self.export_if_wav().err().map(|e| e.to_string());
+            }
+            ui.add(egui::Checkbox::new(
+                &mut self.wav_at_listening_rate,
+                format!("At {} kHz", WAV_LISTENING_RATE / 1000),
+            ))
+            .on_hover_text(
+                "Write an audio rate into the header instead of the sampling rate, which most \
+                 players reject. The samples stay the same, so the beat plays slowed down.",
+            );
+        });
+        if let Some(error) = &self.export_error {
+            ui.colored_label(egui::Color32::RED, format!("Export failed: {error}"));
+        }
+        ui.add(egui::Checkbox::new(
+            &mut self.log_detections,
+            "Log detections",
+        ))
+        .on_hover_text("Append every detection above the SNR threshold to a CSV file");
+        ui.horizontal(|ui| {
+            ui.label("Log file");
+            ui.text_edit_singleline(&mut self.log_path);
+        });
+        ui.add(
+            egui::Slider::new(&mut self.log_threshold_db, 0.0..=60.0).text("SNR threshold (dB)"),
+        );
+        if let Some(error) = &self.log_error {
+            ui.colored_label(egui::Color32::RED, format!("Logging failed: {error}"));
+        }
+    }
+
+    fn beat_table_ui(&self, ui: &mut egui::Ui) {
+        ui.collapsing("Expected vs Detected Beats", |ui| {
+            egui::Grid::new("beat_table").striped(true).show(ui, |ui| {
+                for header in [
+                    "Chirp",
+                    "Object",
+                    "Expected (MHz)",
+                    "Detected (MHz)",
+                    "Error (kHz)",
+                    "Error (bins)",
+                ] {
+                    ui.strong(header);
+                }
+                ui.end_row();
+                for comparison in &self.beat_comparisons {
+                    ui.label(format!("{}", comparison.chirp + 1));
+                    ui.label(format!("{}", comparison.object + 1));
+                    ui.label(format!("{:.4}", comparison.expected * 1e-6));
+                    if let Some(detected) = comparison.detected {
+                        let error = detected - comparison.expected;
+                        ui.label(format!("{:.4}", detected * 1e-6));
+                        ui.label(format!("{:+.1}", error * 1e-3));
+                        let bins = error / comparison.bin_width;
+                        // More than half a bin off means the peak belongs to something else
+                        let text = format!("{bins:+.2}");
+                        if bins.abs() > 0.5 {
+                            ui.colored_label(egui::Color32::YELLOW, text);
+                        } else {
+                            ui.label(text);
                         }
+                    } else {
+                        ui.label("–");
+                        ui.label("–");
+                        ui.label("–");
                     }
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    /// One line with the key derived numbers of the current configuration.
+    fn summary_ui(&self, ui: &mut egui::Ui) {
+        let Resolution {
+            range: resolution,
+            max_range,
+            max_velocity,
+            ..
+        } = self.resolution;
+        let targets = self.objects.iter().filter(|obj| obj.enabled).count();
+        let detections: usize = self.simulation.fft_peaks.iter().map(Vec::len).sum();
+        ui.label(
+            egui::RichText::new(format!(
+                "ΔR {resolution:.3} m · R_max {max_range:.1} m · v_max ±{max_velocity:.2} m/s · \
+                 {targets} targets · {detections} detections"
+            ))
+            .monospace(),
+        )
+        .on_hover_text(
+            "Range resolution c/2B, largest range before the beat of the shortest chirp aliases, \
+             largest unambiguous velocity λ/4T for the sawtooth period T, enabled objects and \
+             FFT peaks across all chirps",
+        );
+    }
+
+    fn layout_ui(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("Plot Heights", |ui| {
+            let heights = &mut self.plot_heights;
+            for (height, label) in [
+                (&mut heights.scene, "Scene"),
+                (&mut heights.frequency, "Frequency"),
+                (&mut heights.signal, "Signal"),
+                (&mut heights.fft, "FFT"),
+            ] {
+                ui.add(
+                    egui::DragValue::new(height)
+                        .range(50.0..=1000.0)
+                        .speed(1.0)
+                        .prefix(format!("{label}: "))
+                        .suffix(" px"),
+                );
+            }
+            if ui.button("Reset").clicked() {
+                *heights = PlotHeights::default();
+            }
+        });
+    }
+
+    fn scene_plot(&mut self, ui: &mut egui::Ui) {
+        let clicked = match self.scene_view {
+            SceneView::RangeVelocity => self.range_velocity_plot(ui),
+            SceneView::Spatial => self.spatial_plot(ui),
+        };
+
+        // Place the selected object where the plot was clicked
+        if let Some(point) = clicked
+            && let Some(obj) = self.objects.get_mut(self.selected_object)
+        {
+            match self.scene_view {
+                SceneView::RangeVelocity => {
+                    obj.range = point.x.clamp(0.0, MAX_RANGE);
+                    obj.velocity = point.y.clamp(-MAX_VELOCITY, MAX_VELOCITY);
+                }
+                SceneView::Spatial => {
+                    obj.range = point.x.hypot(point.y).min(MAX_RANGE);
+                    obj.azimuth = point
+                        .y
+                        .atan2(point.x)
+                        .to_degrees()
+                        .clamp(*AZIMUTH_RANGE.start(), *AZIMUTH_RANGE.end());
+                }
+            }
+        }
+    }
 
-                    for (i, obj) in self.objects.iter().enumerate() {
-                        if !obj.3 {
-                            continue;
-                        }
-                        // Draw a sphere for each object as a circle on the plot
-                        let sphere =
-                            egui_plot::Points::new(format!("sphere_{i}"), vec![[obj.0, obj.1]])
-                                .radius(8.0)
-                                .color(obj.2);
-                        plot_ui.points(sphere);
+    /// The scene with range against velocity, with the range lines and velocity estimates of the
+    /// peaks. Returns where it was clicked.
+    fn range_velocity_plot(&self, ui: &mut egui::Ui) -> Option<egui_plot::PlotPoint> {
+        egui_plot::Plot::new("my_plot")
+            .height(self.plot_heights.scene)
+            .auto_bounds(false)
+            .default_x_bounds(0.0, MAX_RANGE)
+            .default_y_bounds(-60.0, 60.0)
+            .x_axis_label("Range (m)")
+            .y_axis_label(format!("Velocity ({})", self.velocity_convention.label()))
+            .label_formatter(|_, point| format!("R = {:.1} m, v = {:.1} m/s", point.x, point.y))
+            .show(ui, |plot_ui| {
+                if self.plot_lines {
+                    for (i, line) in self.lines.iter().enumerate() {
+                        let color = egui::Color32::from_rgb(200, 200, 200); // light gray for lines
+                        let plot_line = egui_plot::Line::new(
+                            format!("line_{i}"),
+                            egui_plot::PlotPoints::from_iter([
+                                [line.0.0, line.0.1],
+                                [line.1.0, line.1.1],
+                            ]),
+                        )
+                        .color(color)
+                        .width(2.0)
+                        .name(format!("Line {i}"));
+                        plot_ui.line(plot_line);
                     }
+                }
+
+                if self.views.show_velocity_estimates {
+                    let estimates = egui_plot::Points::new(
+                        "Velocity estimates",
+                        self.velocity_estimates
+                            .iter()
+                            .map(|&(range, velocity)| [range, velocity])
+                            .collect::<Vec<_>>(),
+                    )
+                    .shape(egui_plot::MarkerShape::Cross)
+                    .radius(6.0)
+                    .color(egui::Color32::YELLOW);
+                    plot_ui.points(estimates);
+                }
+
+                self.plot_objects(plot_ui, |obj| [obj.range, obj.velocity]);
+                if plot_ui.response().clicked() {
+                    plot_ui.pointer_coordinate()
+                } else {
+                    None
+                }
+            })
+            .inner
+    }
+
+    /// The scene from above with the radar at the origin looking along x, and the field of view
+    /// of its array. Returns where it was clicked.
+    fn spatial_plot(&self, ui: &mut egui::Ui) -> Option<egui_plot::PlotPoint> {
+        // Beyond this angle a grating lobe of the array takes over
+        let fov = (0.5 / self.element_spacing).min(1.0).asin();
+        let cone: Vec<[f64; 2]> = std::iter::once([0.0, 0.0])
+            .chain((0..=FOV_ARC_POINTS).map(|i| {
+                let angle = -fov + 2.0 * fov * i as f64 / FOV_ARC_POINTS as f64;
+                [MAX_RANGE * angle.cos(), MAX_RANGE * angle.sin()]
+            }))
+            .collect();
+        egui_plot::Plot::new("my_plot_spatial")
+            .height(self.plot_heights.scene)
+            .data_aspect(1.0)
+            .x_axis_label("Along boresight (m)")
+            .y_axis_label("Across (m)")
+            .label_formatter(|_, point| {
+                format!(
+                    "R = {:.1} m, az = {:.1}°",
+                    point.x.hypot(point.y),
+                    point.y.atan2(point.x).to_degrees()
+                )
+            })
+            .show(ui, |plot_ui| {
+                plot_ui.polygon(
+                    egui_plot::Polygon::new("Field of view", cone)
+                        .fill_color(egui::Color32::from_white_alpha(12))
+                        .stroke(egui::Stroke::new(1.0, egui::Color32::GRAY)),
+                );
+                plot_ui.points(
+                    egui_plot::Points::new("Radar", vec![[0.0, 0.0]])
+                        .shape(egui_plot::MarkerShape::Up)
+                        .radius(8.0)
+                        .color(egui::Color32::WHITE),
+                );
+                self.plot_objects(plot_ui, |obj| {
+                    let [across, along] = obj.position();
+                    [along, across]
                 });
+                if plot_ui.response().clicked() {
+                    plot_ui.pointer_coordinate()
+                } else {
+                    None
+                }
+            })
+            .inner
+    }
+
+    /// A sphere for every enabled object at `position`, with a ring around the selected one.
+    fn plot_objects(
+        &self,
+        plot_ui: &mut egui_plot::PlotUi<'_>,
+        position: impl Fn(&RadarObject) -> [f64; 2],
+    ) {
+        for (i, obj) in self.objects.iter().enumerate() {
+            if !obj.enabled {
+                continue;
+            }
+            // Draw a sphere for each object as a circle on the plot
+            let sphere = egui_plot::Points::new(format!("sphere_{i}"), vec![position(obj)])
+                .radius(8.0)
+                .color(obj.color);
+            plot_ui.points(sphere);
+            if i == self.selected_object {
+                let ring = egui_plot::Points::new("selected_object", vec![position(obj)])
+                    .radius(11.0)
+                    .filled(false)
+                    .color(egui::Color32::WHITE);
+                plot_ui.points(ring);
+            }
+        }
+    }
+
+    /// Lowest and highest frequency on `my_plot2`, `None` if it shows nothing.
+    fn frequency_plot_span(&self) -> Option<(f64, f64)> {
+        let carrier = self
+            .simulation
+            .f
+            .iter()
+            .copied()
+            .filter(|_| !self.beat_only);
+        let objects = self
+            .simulation
+            .objects
+            .iter()
+            .filter(|obj| obj.enabled)
+            .flat_map(|obj| izip!(&obj.beat_freqs, &self.simulation.f))
+            .map(|(&beat, &f)| if self.beat_only { beat } else { beat + f });
+        carrier.chain(objects).fold(None, |span, y| match span {
+            Some((low, high)) => Some((y.min(low), y.max(high))),
+            None => Some((y, y)),
+        })
+    }
 
-            egui_plot::Plot::new("my_plot2")
-                .height(100.0)
-                .show(ui, |plot_ui| {
+    fn frequency_plot(&mut self, ui: &mut egui::Ui) {
+        ui.add(egui::Checkbox::new(
+            &mut self.beat_only,
+            "Show beat frequency only",
+        ));
+        let span = self.frequency_plot_span();
+        egui_plot::Plot::new("my_plot2")
+            .height(self.plot_heights.frequency)
+            .x_axis_label("Time (s)")
+            .y_axis_label(if self.beat_only {
+                "Beat Frequency (Hz)"
+            } else {
+                "Frequency (Hz)"
+            })
+            .show(ui, |plot_ui| {
+                // The sampling windows span the frequencies plotted, without widening the bounds
+                if let Some((low, high)) = span {
+                    for (i, (&start, chirp)) in self
+                        .simulation
+                        .start_times()
+                        .iter()
+                        .zip(self.durations())
+                        .enumerate()
+                    {
+                        let color = CHIRP_COLORS
+                            .get(i % CHIRP_COLORS.len())
+                            .expect("Color not found");
+                        let end = start + self.simulation.window_duration(chirp);
+                        let window = egui_plot::Polygon::new(
+                            format!("ADC window {i}"),
+                            vec![[start, low], [end, low], [end, high], [start, high]],
+                        )
+                        .fill_color(color.gamma_multiply(0.15))
+                        .stroke(egui::Stroke::new(1.0, *color))
+                        .name(format!("ADC window of chirp {}", i + 1));
+                        plot_ui.polygon(window);
+                    }
+                }
+                if !self.beat_only {
                     let line = egui_plot::Line::new(
                         "f",
-                        self.t
+                        self.simulation
+                            .t
                             .iter()
-                            .zip(self.f.iter())
+                            .zip(self.simulation.f.iter())
                             .map(|(&x, &y)| [x, y])
                             .collect::<egui_plot::PlotPoints<'_>>(),
                     )
                     .color(egui::Color32::LIGHT_BLUE)
                     .name("Carrier Frequency");
                     plot_ui.line(line);
-                    for (i, obj) in self.objects.iter().enumerate() {
-                        if !obj.3 {
-                            continue;
-                        }
-                        let line = egui_plot::Line::new(
+                }
+                for (i, obj) in self.simulation.objects.iter().enumerate() {
+                    if !obj.enabled {
+                        continue;
+                    }
+                    // The beat is tiny next to the GHz carrier, so it is only visible on its own
+                    let (id, name) = if self.beat_only {
+                        (format!("beat_{i}"), format!("Beat Frequency of Object {i}"))
+                    } else {
+                        (
                             format!("bf_{i}"),
-                            izip!(self.t.iter(), obj.4.iter(), self.f.iter())
-                                .map(|(&x, &y, &f)| [x, y + f])
-                                .collect::<egui_plot::PlotPoints<'_>>(),
+                            format!("Received Frequency of Object {i}"),
                         )
-                        .color(obj.2)
-                        .name(format!("Beat Frequency of Object {i}"));
-                        plot_ui.line(line);
+                    };
+                    let line = egui_plot::Line::new(
+                        id,
+                        izip!(
+                            self.simulation.t.iter(),
+                            obj.beat_freqs.iter(),
+                            self.simulation.f.iter()
+                        )
+                        .map(|(&x, &y, &f)| if self.beat_only { [x, y] } else { [x, y + f] })
+                        .collect::<egui_plot::PlotPoints<'_>>(),
+                    )
+                    .color(obj.color)
+                    .name(name);
+                    plot_ui.line(line);
+                }
+            });
+    }
+
+    fn signal_plot(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add(egui::Checkbox::new(
+                &mut self.compare_window,
+                "Compare before/after window",
+            ));
+            egui::ComboBox::from_id_salt("signal_plot_chirp")
+                .selected_text(format!("Chirp {}", self.views.signal_plot_chirp + 1))
+                .show_ui(ui, |ui| {
+                    for i in 0..self.chirps.len() {
+                        ui.selectable_value(
+                            &mut self.views.signal_plot_chirp,
+                            i,
+                            format!("Chirp {}", i + 1),
+                        );
                     }
                 });
-
-            egui_plot::Plot::new("my_plot3")
-                .height(100.0)
-                .show(ui, |plot_ui| {
-                    // Create a linspace from 0.0 to 1E-6 with 1024 points
-                    let start = 5E-6;
-                    let duration = 1E-6;
-
-                    // Find the index in self.t that is closest to 'start'
-                    let idx = idx_at_t(&self.t, start);
-
-                    // Collect the beat frequencies at the found index for all enabled objects
-                    let mut frequencies: Vec<f64> = Vec::new();
-                    for obj in self.objects.iter().take(3) {
-                        if obj.3 && obj.4.len() > idx {
-                            let f = obj.4.get(idx).expect("Frequency not at index");
-                            frequencies.push(*f);
-                        }
+            ui.add(
+                egui::Slider::new(&mut self.signal_oversampling, 1..=32).text("Trace oversampling"),
+            );
+            egui::ComboBox::from_label("Sampling")
+                .selected_text(self.analysis.sampling.name())
+                .show_ui(ui, |ui| {
+                    for sampling in Sampling::ALL {
+                        ui.selectable_value(&mut self.analysis.sampling, sampling, sampling.name())
+                            .on_hover_text(sampling.description());
                     }
-                    let t: Vec<f64> = (0..512)
-                        .map(|i| start + i as f64 * duration / 511.0)
-                        .collect();
-                    let high_res_signal = sample_signal(&t, &frequencies);
-                    // Plot the summed signal
-                    let line = egui_plot::Line::new(
-                        "Summed Beat Sine",
-                        t.iter()
-                            .zip(high_res_signal.iter())
-                            .map(|(&x, &y)| [x, y])
-                            .collect::<egui_plot::PlotPoints<'_>>(),
+                });
+        });
+        // Show exactly the samples that go into the FFT of the selected chirp
+        let Some((&start, chirp)) = self
+            .simulation
+            .start_times()
+            .get(self.views.signal_plot_chirp)
+            .zip(self.durations().nth(self.views.signal_plot_chirp))
+        else {
+            return;
+        };
+        let duration = self.simulation.window_duration(chirp);
+        egui_plot::Plot::new("my_plot3")
+            .height(self.plot_heights.signal)
+            .x_axis_label("Time (s)")
+            .y_axis_label("Amplitude")
+            .show(ui, |plot_ui| {
+                // Find the index in self.simulation.t that is closest to 'start'
+                let idx = idx_at_t(&self.simulation.t, start);
+
+                // Collect the beats at the found index for all enabled objects
+                let reflections = self.simulation.reflections_at(idx);
+
+                // Overlay sampling points
+                let n = ((duration * self.sampling_frequency).round() as usize).max(2);
+                let high_res_n = (n * self.signal_oversampling).min(MAX_TRACE_POINTS);
+                let t: Vec<f64> = (0..high_res_n)
+                    .map(|i| start + i as f64 * duration / (high_res_n - 1) as f64)
+                    .collect();
+                let high_res_signal = sample_signal(&t, &reflections);
+                // Plot the summed signal
+                let line = egui_plot::Line::new(
+                    "Summed Beat Sine",
+                    t.iter()
+                        .zip(high_res_signal.iter())
+                        .map(|(&x, &y)| [x, y])
+                        .collect::<egui_plot::PlotPoints<'_>>(),
+                )
+                .color(egui::Color32::YELLOW)
+                .name("Sum of a·sin(2π·beat_freq·t) for all reflections");
+                plot_ui.line(line);
+
+                let t = self.simulation.sample_times(start, chirp);
+                let low_res_signal = self.simulation.build_signal(start, chirp);
+                // Convert t and magnitude_sample to points for plotting
+                let overlay_points: Vec<[f64; 2]> = t
+                    .iter()
+                    .zip(low_res_signal.iter())
+                    .map(|(&tx, &my)| [tx, my])
+                    .collect();
+                // Keep dense sampling grids readable
+                let radius = (400.0 / n as f32).clamp(1.0, 4.0);
+                let points = egui_plot::Points::new("Overlay Samples", overlay_points)
+                    .color(egui::Color32::RED)
+                    .radius(radius);
+                plot_ui.points(points);
+
+                let nyquist = self.sampling_frequency / 2.0;
+                let highest_beat = reflections
+                    .iter()
+                    .fold(0.0_f64, |max, &(f, _)| max.max(f.abs()));
+                let adequacy = if highest_beat < nyquist {
+                    format!(
+                        "✔ highest beat {:.2} MHz is below Nyquist",
+                        highest_beat * 1e-6
                     )
-                    .color(egui::Color32::YELLOW)
-                    .name("Sum of sin(2π·beat_freq·t) for all objects");
-                    plot_ui.line(line);
+                } else {
+                    format!("⚠ highest beat {:.2} MHz aliases", highest_beat * 1e-6)
+                };
+                let bounds = plot_ui.plot_bounds();
+                let label = egui_plot::Text::new(
+                    "Sample Count",
+                    egui_plot::PlotPoint::new(bounds.min()[0], bounds.max()[1]),
+                    format!(
+                        "{n} samples in {:.1} µs at {:.1} MHz (Nyquist {:.1} MHz), {adequacy}",
+                        duration * 1e6,
+                        self.sampling_frequency * 1e-6,
+                        nyquist * 1e-6
+                    ),
+                )
+                .anchor(egui::Align2::LEFT_TOP)
+                .color(egui::Color32::WHITE);
+                plot_ui.text(label);
 
-                    // Overlay sampling points
-                    let n = (duration * self.sampling_frequency).round() as usize;
-                    let t: Vec<f64> = (0..n)
-                        .map(|i| start + i as f64 * duration / (n - 1) as f64)
-                        .collect();
-                    let low_res_signal = sample_signal(&t, &frequencies);
-                    // Convert t and magnitude_sample to points for plotting
-                    let overlay_points: Vec<[f64; 2]> = t
+                if self.compare_window {
+                    let amplitude = reflections.iter().map(|&(_, amplitude)| amplitude).sum();
+                    self.plot_window_comparison(plot_ui, &t, &low_res_signal, amplitude);
+                }
+            });
+    }
+
+    /// A vertical line at the expected beat of every reflection on every chirp, in the color of
+    /// its object, to compare the detections against.
+    fn plot_expected_beats(&self, plot_ui: &mut egui_plot::PlotUi<'_>) {
+        for comparison in &self.beat_comparisons {
+            let Some(obj) = self.objects.get(comparison.object) else {
+                continue;
+            };
+            plot_ui.vline(
+                egui_plot::VLine::new(
+                    format!("Object {} expected", comparison.object + 1),
+                    comparison.expected * 1e-6,
+                )
+                .color(obj.color)
+                .style(egui_plot::LineStyle::dashed_dense()),
+            );
+        }
+    }
+
+    /// Overlay the windowed `samples` and the window envelope on `my_plot3`, the envelope scaled
+    /// to `amplitude`, the largest the sum of the reflections can reach.
+    fn plot_window_comparison(
+        &self,
+        plot_ui: &mut egui_plot::PlotUi<'_>,
+        t: &[f64],
+        samples: &[f64],
+        amplitude: f64,
+    ) {
+        // Taper the displayed samples as if they were the FFT input
+        let windowed_signal = self.analysis.window.apply(samples);
+        let windowed_points: Vec<[f64; 2]> = t
+            .iter()
+            .zip(windowed_signal.iter())
+            .map(|(&tx, &my)| [tx, my])
+            .collect();
+        let line = egui_plot::Line::new("Windowed Samples", windowed_points.clone())
+            .color(egui::Color32::LIGHT_GREEN)
+            .name(format!("{} windowed samples", self.analysis.window.name()));
+        plot_ui.line(line);
+        let points = egui_plot::Points::new("Windowed Samples", windowed_points)
+            .color(egui::Color32::LIGHT_GREEN)
+            .radius(2.0);
+        plot_ui.points(points);
+
+        // The window itself, scaled to the largest possible signal amplitude
+        let envelope: Vec<[f64; 2]> = t
+            .iter()
+            .zip(self.analysis.window.coefficients(t.len()))
+            .map(|(&tx, w)| [tx, w * amplitude])
+            .collect();
+        let line = egui_plot::Line::new("Window", envelope)
+            .color(egui::Color32::GRAY)
+            .style(egui_plot::LineStyle::dashed_loose())
+            .name(format!("{} window", self.analysis.window.name()));
+        plot_ui.line(line);
+    }
+
+    fn autocorrelation_plot(&mut self, ui: &mut egui::Ui) {
+        ui.add(egui::Checkbox::new(
+            &mut self.views.show_autocorrelation,
+            "Show autocorrelation",
+        ))
+        .on_hover_text(
+            "A single dominant beat repeats every 1/f_beat, \
+             which is the spacing of the autocorrelation maxima.",
+        );
+        if !self.views.show_autocorrelation {
+            return;
+        }
+        egui_plot::Plot::new("autocorrelation_plot")
+            .height(self.plot_heights.signal)
+            .x_axis_label("Lag (s)")
+            .y_axis_label("Autocorrelation")
+            .show(ui, |plot_ui| {
+                let line = egui_plot::Line::new(
+                    "Autocorrelation",
+                    self.autocorrelation
                         .iter()
-                        .zip(low_res_signal.iter())
-                        .map(|(&tx, &my)| [tx, my])
-                        .collect();
-                    let points = egui_plot::Points::new("Overlay Samples", overlay_points)
-                        .color(egui::Color32::RED)
-                        .radius(4.0);
-                    plot_ui.points(points);
-                });
+                        .enumerate()
+                        .map(|(lag, &r)| [lag as f64 / self.sampling_frequency, r])
+                        .collect::<egui_plot::PlotPoints<'_>>(),
+                )
+                .color(egui::Color32::YELLOW)
+                .name(format!(
+                    "Autocorrelation of chirp {}",
+                    self.views.signal_plot_chirp + 1
+                ));
+                plot_ui.line(line);
+            });
+    }
+
+    fn iq_spectrum_plot(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add(egui::Checkbox::new(
+                &mut self.views.show_iq_spectrum,
+                "Show IQ spectrum",
+            ))
+            .on_hover_text(
+                "Two-sided spectrum of the complex beat signal. An imbalanced Q channel mirrors \
+                 every beat as an image spur at its negative frequency.",
+            );
+            ui.add(
+                egui::Slider::new(&mut self.analysis.iq_gain_imbalance_db, -3.0..=3.0)
+                    .text("Gain imbalance (dB)"),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.analysis.iq_phase_imbalance_deg, -20.0..=20.0)
+                    .text("Phase imbalance (°)"),
+            );
+        });
+        if !self.views.show_iq_spectrum {
+            return;
+        }
+        egui_plot::Plot::new("iq_fft_plot")
+            .height(self.plot_heights.fft)
+            .x_axis_label("Frequency (Hz)")
+            .y_axis_label("Magnitude")
+            .show(ui, |plot_ui| {
+                let line = egui_plot::Line::new(
+                    "IQ Spectrum",
+                    self.iq_fft
+                        .iter()
+                        .map(|&(freq, mag)| [freq, mag])
+                        .collect::<egui_plot::PlotPoints<'_>>(),
+                )
+                .color(egui::Color32::LIGHT_GREEN)
+                .name(format!(
+                    "IQ spectrum of chirp {}",
+                    self.views.signal_plot_chirp + 1
+                ));
+                plot_ui.line(line);
+            });
+    }
+
+    fn threshold_curve_plot(&mut self, ui: &mut egui::Ui) {
+        ui.add(egui::Checkbox::new(
+            &mut self.views.show_threshold_curve,
+            "Show detections vs SNR threshold",
+        ))
+        .on_hover_text(
+            "How many FFT peaks reach each SNR threshold. The knee where the count levels off \
+             separates the targets from the noise peaks.",
+        );
+        if !self.views.show_threshold_curve {
+            return;
+        }
+        egui_plot::Plot::new("threshold_curve_plot")
+            .height(self.plot_heights.signal)
+            .x_axis_label("SNR threshold (dB)")
+            .y_axis_label("Detections")
+            .show(ui, |plot_ui| {
+                plot_ui.line(
+                    egui_plot::Line::new("Detections", self.threshold_curve.clone())
+                        .color(egui::Color32::LIGHT_GREEN),
+                );
+                plot_ui.vline(
+                    egui_plot::VLine::new("Log threshold", self.log_threshold_db)
+                        .color(egui::Color32::GRAY)
+                        .style(egui_plot::LineStyle::dashed_dense()),
+                );
+            });
+    }
+
+    fn ambiguity_plot(&mut self, ui: &mut egui::Ui) {
+        ui.add(egui::Checkbox::new(
+            &mut self.views.show_ambiguity,
+            "Show chirp ambiguity function",
+        ))
+        .on_hover_text(
+            "Response of a linear chirp to an echo with a given delay and Doppler shift. \
+             The diagonal ridge is the range-Doppler coupling: a Doppler shift looks like a \
+             change in range.",
+        );
+        if !self.views.show_ambiguity {
+            self.ambiguity_texture = None;
+            return;
+        }
+        let width = self.ambiguity.first().map_or(0, Vec::len);
+        let height = self.ambiguity.len();
+        // Highest Doppler shift at the top
+        let rgba: Vec<u8> = self
+            .ambiguity
+            .iter()
+            .rev()
+            .flatten()
+            .flat_map(|&value| colormap(value).to_array())
+            .collect();
+        let image = egui::ColorImage::from_rgba_unmultiplied([width, height], &rgba);
+        if let Some(texture) = &mut self.ambiguity_texture {
+            texture.set(image, egui::TextureOptions::NEAREST);
+        } else {
+            self.ambiguity_texture = Some(ui.ctx().load_texture(
+                "ambiguity",
+                image,
+                egui::TextureOptions::NEAREST,
+            ));
+        }
+        let Some(texture) = &self.ambiguity_texture else {
+            return;
+        };
+        // The scaled chirp sweeps half the sampling rate, one Doppler bin is 1/n of that rate
+        let doppler_span = 2.0 * AMBIGUITY_DOPPLER_BINS as f64 / AMBIGUITY_SAMPLES as f64;
+        egui_plot::Plot::new("ambiguity_plot")
+            .height(self.plot_heights.fft)
+            .x_axis_label("Delay (chirp durations)")
+            .y_axis_label("Doppler (bandwidths)")
+            .show(ui, |plot_ui| {
+                plot_ui.image(egui_plot::PlotImage::new(
+                    "Ambiguity",
+                    texture,
+                    egui_plot::PlotPoint::new(0.0, 0.0),
+                    egui::vec2(2.0, doppler_span as f32),
+                ));
+            });
+    }
+
+    fn range_doppler_plot(&mut self, ui: &mut egui::Ui) {
+        ui.add(egui::Checkbox::new(
+            &mut self.views.show_range_doppler,
+            "Show range-Doppler map",
+        ))
+        .on_hover_text(format!(
+            "FFT of each of {RANGE_DOPPLER_CHIRPS} identical chirps, then across the chirps. \
+             Unlike the range lines of a single chirp, every target is a single blob at its \
+             range and velocity. Velocities wrap around beyond half a wavelength per chirp."
+        ));
+        if !self.views.show_range_doppler {
+            self.range_doppler_texture = None;
+            return;
+        }
+        let Some(chirp) = self.durations().nth(self.views.signal_plot_chirp) else {
+            return;
+        };
+        let width = self.range_doppler.first().map_or(0, Vec::len);
+        let height = self.range_doppler.len();
+        let config = self.config();
+        let center = config.chirp_center_frequency(self.views.signal_plot_chirp);
+        // Velocity per Hz of Doppler shift, negative if the velocity points away from the radar
+        let velocity_per_hz = self
+            .velocity_convention
+            .receding(-SPEED_OF_LIGHT / (2.0 * center));
+        let level = |mag: f64| {
+            let db = 20.0 * mag.max(f64::MIN_POSITIVE).log10();
+            1.0 - db / RANGE_DOPPLER_DB_FLOOR
+        };
+        // Highest velocity at the top
+        let rows: Vec<&Vec<f64>> = if velocity_per_hz > 0.0 {
+            self.range_doppler.iter().rev().collect()
+        } else {
+            self.range_doppler.iter().collect()
+        };
+        let rgba: Vec<u8> = rows
+            .into_iter()
+            .flatten()
+            .flat_map(|&mag| colormap(level(mag)).to_array())
+            .collect();
+        let image = egui::ColorImage::from_rgba_unmultiplied([width, height], &rgba);
+        if let Some(texture) = &mut self.range_doppler_texture {
+            texture.set(image, egui::TextureOptions::NEAREST);
+        } else {
+            self.range_doppler_texture = Some(ui.ctx().load_texture(
+                "range_doppler",
+                image,
+                egui::TextureOptions::NEAREST,
+            ));
+        }
+        let Some(texture) = &self.range_doppler_texture else {
+            return;
+        };
+        let range_step = range_for_beat(
+            self.sampling_frequency / (2.0 * width.max(1) as f64),
+            self.waveform.ramp_duration(chirp),
+            config.chirp_bandwidth(self.views.signal_plot_chirp),
+        );
+        let velocity_step = velocity_per_hz.abs() / (height.max(1) as f64 * chirp);
+        // Each pixel is centered on its bin, the Doppler bins run from -height/2 to height/2 - 1
+        let image_center = egui_plot::PlotPoint::new(
+            range_step * (width as f64 - 1.0) / 2.0,
+            -velocity_step / 2.0 * velocity_per_hz.signum(),
+        );
+        let objects: Vec<[f64; 2]> = self
+            .objects
+            .iter()
+            .filter(|obj| obj.enabled)
+            .map(|obj| [obj.range, obj.velocity])
+            .collect();
+        egui_plot::Plot::new("range_doppler_plot")
+            .height(self.plot_heights.fft)
+            .x_axis_label("Range (m)")
+            .y_axis_label(format!("Velocity ({})", self.velocity_convention.label()))
+            .show(ui, |plot_ui| {
+                plot_ui.image(egui_plot::PlotImage::new(
+                    "Range-Doppler",
+                    texture,
+                    image_center,
+                    egui::vec2(
+                        (range_step * width as f64) as f32,
+                        (velocity_step * height as f64) as f32,
+                    ),
+                ));
+                plot_ui.points(
+                    egui_plot::Points::new("Objects", objects)
+                        .shape(egui_plot::MarkerShape::Circle)
+                        .filled(false)
+                        .radius(6.0)
+                        .color(egui::Color32::WHITE),
+                );
+            });
+    }
+
+    fn top_view_plot(&mut self, ui: &mut egui::Ui) {
+        ui.add(egui::Checkbox::new(
+            &mut self.views.show_top_view,
+            "Show top view",
+        ))
+        .on_hover_text(
+            "Objects at their true position and the peaks of the chirp selected for the \
+                 signal plot at their range and the azimuth estimated across the RX array",
+        );
+        if !self.views.show_top_view {
+            return;
+        }
+        if self.rx_elements < 2 {
+            ui.label("The azimuth needs at least two RX elements.");
+        }
+        egui_plot::Plot::new("top_view_plot")
+            .height(self.plot_heights.scene)
+            .data_aspect(1.0)
+            .x_axis_label("Across (m)")
+            .y_axis_label("Along boresight (m)")
+            .show(ui, |plot_ui| {
+                for (i, obj) in self
+                    .objects
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, obj)| obj.enabled)
+                {
+                    plot_ui.points(
+                        egui_plot::Points::new(format!("Object {}", i + 1), vec![obj.position()])
+                            .radius(6.0)
+                            .color(obj.color),
+                    );
+                }
+                let estimates: Vec<[f64; 2]> = self
+                    .azimuth_estimates
+                    .iter()
+                    .map(|&(range, azimuth)| {
+                        let (sin, cos) = azimuth.to_radians().sin_cos();
+                        [range * sin, range * cos]
+                    })
+                    .collect();
+                plot_ui.points(
+                    egui_plot::Points::new("Detections", estimates)
+                        .shape(egui_plot::MarkerShape::Cross)
+                        .radius(6.0)
+                        .color(egui::Color32::WHITE),
+                );
+            });
+    }
+
+    fn spectrogram_plot(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add(egui::Checkbox::new(
+                &mut self.views.show_spectrogram,
+                "Show spectrogram",
+            ))
+            .on_hover_text(
+                "FFTs of a short window sliding across the whole chirp, not just the sampling \
+                 window. Shows how the beat changes over time, like the jump at the start of the \
+                 chirp while the echo of the previous one is still arriving.",
+            );
+            if self.views.show_spectrogram {
+                ui.add(
+                    egui::Slider::new(&mut self.views.spectrogram_window, SPECTROGRAM_WINDOW_RANGE)
+                        .logarithmic(true)
+                        .text("Window (samples)"),
+                )
+                .on_hover_text("Longer windows resolve the beat finer but blur it in time");
+                ui.add(
+                    egui::Slider::new(&mut self.views.spectrogram_hop, SPECTROGRAM_HOP_RANGE)
+                        .logarithmic(true)
+                        .text("Hop (samples)"),
+                )
+                .on_hover_text(format!(
+                    "Raised on long chirps to keep at most {SPECTROGRAM_MAX_FRAMES} windows"
+                ));
+            }
+        });
+        if !self.views.show_spectrogram {
+            self.spectrogram_texture = None;
+            return;
+        }
+        let Some(chirp) = self.durations().nth(self.views.signal_plot_chirp) else {
+            return;
+        };
+        let frames = self.spectrogram.len();
+        let bins = self.spectrogram.first().map_or(0, Vec::len);
+        let peak = self
+            .spectrogram
+            .iter()
+            .flatten()
+            .fold(f64::MIN_POSITIVE, |max, &mag| max.max(mag));
+        let level = |mag: f64| {
+            let db = 20.0 * (mag / peak).max(f64::MIN_POSITIVE).log10();
+            1.0 - db / SPECTROGRAM_DB_FLOOR
+        };
+        // Time to the right, highest frequency at the top
+        let rgba: Vec<u8> = (0..bins)
+            .rev()
+            .flat_map(|bin| {
+                self.spectrogram
+                    .iter()
+                    .map(move |frame| frame.get(bin).copied().unwrap_or(0.0))
+            })
+            .flat_map(|mag| colormap(level(mag)).to_array())
+            .collect();
+        let image = egui::ColorImage::from_rgba_unmultiplied([frames, bins], &rgba);
+        if let Some(texture) = &mut self.spectrogram_texture {
+            texture.set(image, egui::TextureOptions::NEAREST);
+        } else {
+            self.spectrogram_texture = Some(ui.ctx().load_texture(
+                "spectrogram",
+                image,
+                egui::TextureOptions::NEAREST,
+            ));
+        }
+        let Some(texture) = &self.spectrogram_texture else {
+            return;
+        };
+        let hop = self.effective_spectrogram_hop(self.spectrogram_samples(chirp));
+        let time_step = hop as f64 / self.sampling_frequency * 1e6;
+        let frequency_step = self.sampling_frequency / self.views.spectrogram_window as f64 * 1e-6;
+        // Each pixel is centered on its frame and bin
+        let image_center = egui_plot::PlotPoint::new(
+            time_step * (frames as f64 - 1.0) / 2.0,
+            frequency_step * (bins as f64 - 1.0) / 2.0,
+        );
+        egui_plot::Plot::new("spectrogram_plot")
+            .height(self.plot_heights.fft)
+            .x_axis_label(format!(
+                "Time since the start of chirp {} (µs)",
+                self.views.signal_plot_chirp + 1
+            ))
+            .y_axis_label("Beat frequency (MHz)")
+            .show(ui, |plot_ui| {
+                plot_ui.image(egui_plot::PlotImage::new(
+                    "Spectrogram",
+                    texture,
+                    image_center,
+                    egui::vec2(
+                        (time_step * frames as f64) as f32,
+                        (frequency_step * bins as f64) as f32,
+                    ),
+                ));
+            });
+    }
+
+    fn tx_spectrum_plot(&mut self, ui: &mut egui::Ui) {
+        ui.add(egui::Checkbox::new(
+            &mut self.views.show_tx_spectrum,
+            "Show TX chirp spectrum",
+        ))
+        .on_hover_text(
+            "The transmitted chirp spans the whole bandwidth, \
+             while the beat signal above is only a few MHz wide.",
+        );
+        if !self.views.show_tx_spectrum {
+            return;
+        }
+        egui_plot::Plot::new("tx_fft_plot")
+            .height(self.plot_heights.fft)
+            .x_axis_label("Frequency offset (Hz)")
+            .y_axis_label("Magnitude")
+            .show(ui, |plot_ui| {
+                let line = egui_plot::Line::new(
+                    "TX Chirp Spectrum",
+                    self.tx_fft
+                        .iter()
+                        .map(|&(freq, mag)| [freq, mag])
+                        .collect::<egui_plot::PlotPoints<'_>>(),
+                )
+                .color(egui::Color32::LIGHT_BLUE)
+                .name("TX chirp (baseband, first chirp)");
+                plot_ui.line(line);
+            });
+    }
+
+    /// `mag` as plotted on the FFT plot, in dB down to [`FFT_DB_FLOOR`] if `fft_in_db` is set.
+    fn fft_display_magnitude(&self, mag: f64) -> f64 {
+        if self.fft_in_db {
+            (20.0 * mag.log10()).max(FFT_DB_FLOOR)
+        } else {
+            mag
+        }
+    }
+
+    /// Inverse of [`Self::fft_display_magnitude`].
+    fn fft_linear_magnitude(&self, displayed: f64) -> f64 {
+        if self.fft_in_db {
+            10.0_f64.powf(displayed / 20.0)
+        } else {
+            displayed.max(0.0)
+        }
+    }
 
-            egui_plot::Plot::new("fft_plot")
-                .height(120.0)
-                .show(ui, |plot_ui| {
-                    let colors = [
-                        egui::Color32::LIGHT_GREEN,
-                        egui::Color32::LIGHT_BLUE,
-                        egui::Color32::YELLOW,
-                        egui::Color32::RED,
-                        egui::Color32::WHITE,
-                        egui::Color32::LIGHT_RED,
-                        egui::Color32::LIGHT_YELLOW,
-                        egui::Color32::LIGHT_GRAY,
-                        egui::Color32::GRAY,
-                        egui::Color32::BLUE,
-                    ];
-                    for (i, fft) in self.ffts.iter().enumerate() {
-                        let color = colors.get(i % colors.len()).expect("Color not found");
+    fn fft_plot(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add(egui::Checkbox::new(
+                &mut self.color_peaks_by_range,
+                "Color peaks by range",
+            ))
+            .on_hover_text("Color each peak by the range of a stationary target at its frequency");
+            ui.add(egui::Checkbox::new(&mut self.fft_in_db, "FFT in dB"))
+                .on_hover_text(
+                    "Plot 20·log10 of the magnitudes, so weak returns show next to strong ones",
+                );
+            ui.add(egui::Checkbox::new(
+                &mut self.views.show_theoretical_response,
+                "Theoretical response",
+            ))
+            .on_hover_text(
+                "The continuous response of the windowed FFT at each true beat frequency, \
+                 which the FFT bins sample (scalloping and leakage)",
+            );
+            ui.add(egui::Checkbox::new(
+                &mut self.show_expected_beats,
+                "Expected beats",
+            ))
+            .on_hover_text(
+                "Where the peak of every enabled object should be, from its range and velocity, \
+                 in the color of the object",
+            );
+            ui.add(
+                egui::Slider::new(
+                    &mut self.analysis.max_display_frequency,
+                    0.1e6..=self.sampling_frequency / 2.0,
+                )
+                .text("Max Frequency (Hz)")
+                .logarithmic(true),
+            )
+            .on_hover_text("Crop the spectra above this frequency for display and peak search");
+            if ui
+                .button("Pin Current FFT")
+                .on_hover_text(format!(
+                    "Keep the current spectra as a reference, up to {MAX_PINNED_FFTS}"
+                ))
+                .clicked()
+            {
+                if self.pinned_ffts.len() == MAX_PINNED_FFTS {
+                    self.pinned_ffts.remove(0);
+                }
+                self.pinned_ffts.push(self.simulation.ffts.clone());
+            }
+            if ui
+                .add_enabled(
+                    !self.pinned_ffts.is_empty(),
+                    egui::Button::new("Clear Pins"),
+                )
+                .clicked()
+            {
+                self.pinned_ffts.clear();
+            }
+        });
+        let threshold = self.fft_display_magnitude(self.analysis.peak_threshold);
+        let mut dragging = self.dragging_peak_threshold;
+        let dragged_threshold = egui_plot::Plot::new("fft_plot")
+            .height(self.plot_heights.fft)
+            .x_axis_label("Frequency (MHz)")
+            .y_axis_label(if self.fft_in_db {
+                "Magnitude (dB)"
+            } else {
+                "Magnitude"
+            })
+            .allow_drag(!dragging)
+            .show(ui, |plot_ui| {
+                plot_ui.hline(
+                    egui_plot::HLine::new("Peak threshold", threshold)
+                        .color(egui::Color32::LIGHT_RED)
+                        .style(egui_plot::LineStyle::dashed_loose()),
+                );
+                for (pin, ffts) in self.pinned_ffts.iter().enumerate() {
+                    // Older pins fade out
+                    let gray = 80 + (120 * (pin + 1) / self.pinned_ffts.len()) as u8;
+                    for (i, fft) in ffts.iter().enumerate() {
                         let line = egui_plot::Line::new(
-                            format!("FFT Chrip {i}"),
+                            format!("Pinned FFT {pin}"),
                             fft.iter()
-                                .map(|(freq, mag)| [*freq, *mag])
+                                .map(|&(freq, mag)| [freq * 1e-6, self.fft_display_magnitude(mag)])
                                 .collect::<egui_plot::PlotPoints<'_>>(),
                         )
-                        .color(*color)
-                        .name(format!("FFT Chrip {i}"));
+                        .color(egui::Color32::from_gray(gray))
+                        .style(egui_plot::LineStyle::dashed_dense())
+                        .name(format!("Pin {} Chirp {i}", pin + 1));
                         plot_ui.line(line);
                     }
-
-                    // For compatibility with the code below, set spectrum to the first fft (or empty if none)
-                    let spectrum: Vec<(f64, f64)> = if let Some(fft) = self.ffts.first() {
-                        fft.iter().map(|(freq, mag)| (*freq, *mag)).collect()
-                    } else {
-                        Vec::new()
-                    };
-                    // Plot the FFT magnitude
+                }
+                for (i, (color, response)) in self.theoretical_responses.iter().enumerate() {
+                    let response: Vec<[f64; 2]> = response
+                        .iter()
+                        .map(|&[freq, mag]| [freq * 1e-6, self.fft_display_magnitude(mag)])
+                        .collect();
+                    let line = egui_plot::Line::new(format!("Theoretical Response {i}"), response)
+                        .color(color.gamma_multiply(0.6))
+                        .style(egui_plot::LineStyle::dotted_dense())
+                        .name("Theoretical response");
+                    plot_ui.line(line);
+                }
+                if self.show_expected_beats {
+                    self.plot_expected_beats(plot_ui);
+                }
+                for (i, fft) in self.simulation.ffts.iter().enumerate() {
+                    let color = CHIRP_COLORS
+                        .get(i % CHIRP_COLORS.len())
+                        .expect("Color not found");
                     let line = egui_plot::Line::new(
-                        "FFT Magnitude",
-                        spectrum
-                            .iter()
-                            .map(|&(f, mag)| [f * 1e-6, mag]) // MHz
+                        format!("FFT Chrip {i}"),
+                        fft.iter()
+                            .map(|&(freq, mag)| [freq * 1e-6, self.fft_display_magnitude(mag)])
                             .collect::<egui_plot::PlotPoints<'_>>(),
                     )
-                    .color(egui::Color32::LIGHT_GREEN)
-                    .name("FFT |Magnitude| (MHz)");
+                    .color(*color)
+                    .name(format!("FFT Chrip {i}"));
                     plot_ui.line(line);
+                }
 
-                    for (i, peaks) in self.fft_peaks.iter().enumerate() {
-                        let peak_points: Vec<[f64; 2]> = peaks
-                            .iter()
-                            .map(|(freq, mag)| [*freq, *mag]) // MHz
-                            .collect();
-                        let color = colors.get(i % colors.len()).expect("Color not found");
-                        let points =
-                            egui_plot::Points::new(format!("FFT Peaks {i}"), peak_points.clone())
-                                .color(*color)
-                                .radius(3.0)
-                                .name(format!("FFT Peaks {i}"));
+                let config = self.config();
+                for (i, (peaks, chirp)) in self
+                    .simulation
+                    .fft_peaks
+                    .iter()
+                    .zip(self.durations())
+                    .enumerate()
+                {
+                    let chirp_color = *CHIRP_COLORS
+                        .get(i % CHIRP_COLORS.len())
+                        .expect("Color not found");
+                    for &(freq, mag) in peaks {
+                        let mag = self.fft_display_magnitude(mag);
+                        // The same target lands at the same range on every chirp, so coloring by
+                        // range groups the detections of one target across chirps
+                        let color = if self.color_peaks_by_range {
+                            colormap(
+                                range_for_beat(
+                                    freq,
+                                    self.waveform.ramp_duration(chirp),
+                                    config.chirp_bandwidth(i),
+                                ) / MAX_RANGE,
+                            )
+                        } else {
+                            chirp_color
+                        };
+                        let point = [freq * 1e-6, mag];
+                        let points = egui_plot::Points::new(format!("FFT Peaks {i}"), vec![point])
+                            .color(color)
+                            .radius(3.0)
+                            .name(format!("FFT Peaks {i}"));
                         plot_ui.points(points);
-                        let points = egui_plot::Points::new(format!("FFT Peaks {i}"), peak_points)
-                            .color(*color)
+                        let points = egui_plot::Points::new(format!("FFT Peaks {i}"), vec![point])
                             .radius(1.0)
                             .color(egui::Color32::BLACK)
                             .name(format!("FFT Peaks {i}"));
                         plot_ui.points(points);
                     }
+                }
+
+                // Grab the threshold line when a drag starts within a few pixels of it
+                let response = plot_ui.response();
+                if response.drag_started()
+                    && let Some(pointer) = response.interact_pointer_pos()
+                {
+                    let line_y = plot_ui
+                        .screen_from_plot(egui_plot::PlotPoint::new(0.0, threshold))
+                        .y;
+                    dragging = (pointer.y - line_y).abs() <= PEAK_THRESHOLD_GRAB_DISTANCE;
+                }
+                if !response.dragged() {
+                    dragging = false;
+                }
+                if dragging {
+                    plot_ui.pointer_coordinate().map(|point| point.y)
+                } else {
+                    None
+                }
+            })
+            .inner;
+
+        self.dragging_peak_threshold = dragging;
+        if let Some(threshold) = dragged_threshold {
+            self.analysis.peak_threshold = self.fft_linear_magnitude(threshold);
+        }
+    }
+}
+
+impl eframe::App for App {
+    /// Called by the framework to save state before shutdown.
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, eframe::APP_KEY, self);
+    }
+
+    /// Called each time the UI needs repainting, which may be many times per second.
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.nudge_selected_object(ctx);
+        self.animate_objects(ctx);
+        self.update_if_changed();
+        self.log_strong_detections();
+        egui::CentralPanel::default().show(ctx, |ui| {
+            // The central panel the region left after adding TopPanel's and SidePanel's
+            ui.heading("FMCW Radar demo 0");
+            self.summary_ui(ui);
 
-                    //plot_ui.set_x_axis_formatter(|x, _| format!("{:.1}", x));
-                    //plot_ui.set_x_axis_label("Frequency (MHz)");
-                    //plot_ui.set_y_axis_label("Magnitude");
+            egui::SidePanel::left("left_panel").show_inside(ui, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    self.objects_ui(ui);
+                    ui.separator();
+                    self.radar_parameters_ui(ui);
+                    self.receiver_chain_ui(ui);
+                    self.detection_ui(ui);
+                    self.antenna_ui(ui);
+                    self.inverse_ui(ui);
+                    self.scene_json_ui(ui);
+                    self.export_ui(ui);
+                    ui.separator();
+                    self.layout_ui(ui);
+                });
+            });
+            // Scroll instead of squeezing the plots when the window is too small for them
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    for view in SceneView::ALL {
+                        ui.radio_value(&mut self.scene_view, view, view.name());
+                    }
+                    ui.separator();
+                    let range_velocity = self.scene_view == SceneView::RangeVelocity;
+                    ui.add_enabled(
+                        range_velocity,
+                        egui::Checkbox::new(&mut self.plot_lines, "Plot Lines"),
+                    );
+                    ui.add_enabled(
+                        range_velocity,
+                        egui::Checkbox::new(
+                            &mut self.views.show_velocity_estimates,
+                            "Velocity estimates",
+                        ),
+                    )
+                    .on_hover_text(format!(
+                        "Cross at the range and velocity of every peak of the chirp selected for \
+                         the signal plot, with the velocity from the phase of the peak across \
+                         {RANGE_DOPPLER_CHIRPS} repetitions of that chirp"
+                    ));
+                });
+                self.scene_plot(ui);
+                ui.horizontal(|ui| {
+                    ui.add(egui::Checkbox::new(
+                        &mut self.views.interpolate_range_lines,
+                        "Interpolated range lines",
+                    ))
+                    .on_hover_text(
+                        "Build the range lines from the peak frequencies interpolated between \
+                         the FFT bins instead of the bin frequencies",
+                    );
+                    if let Some((raw, interpolated)) = self.range_line_residuals() {
+                        ui.label(format!(
+                            "Range-line residual: {raw:.2} m from bins, {interpolated:.2} m \
+                             interpolated"
+                        ));
+                    }
                 });
+                self.frequency_plot(ui);
+                self.signal_plot(ui);
+                self.autocorrelation_plot(ui);
+                self.fft_plot(ui);
+                self.beat_table_ui(ui);
+                self.threshold_curve_plot(ui);
+                if let Some((measured, theoretical)) = self.main_lobe_widths() {
+                    ui.label(format!(
+                        "Strongest peak -3 dB width: {:.1} kHz measured, {:.1} kHz theoretical ({} window)",
+                        measured * 1e-3,
+                        theoretical * 1e-3,
+                        self.analysis.window.name()
+                    ));
+                }
+                self.iq_spectrum_plot(ui);
+                self.ambiguity_plot(ui);
+                self.range_doppler_plot(ui);
+                self.spectrogram_plot(ui);
+                self.top_view_plot(ui);
+                self.tx_spectrum_plot(ui);
 
-            ui.add(egui::github_link_file!(
-                "https://github.com/GRASBOCK/fmcw-radar_demo-0/blob/main/",
-                "Source code."
-            ));
+                ui.add(egui::github_link_file!(
+                    "https://github.com/GRASBOCK/fmcw-radar_demo-0/blob/main/",
+                    "Source code."
+                ));
+            });
 
             ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
                 powered_by_egui_and_eframe(ui);
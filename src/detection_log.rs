@@ -0,0 +1,61 @@
+//! Appends detections to a CSV file, to use the simulator as an event recorder.
+
+use std::io::Write as _;
+
+/// Rows written between two flushes of the file.
+const FLUSH_INTERVAL: usize = 64;
+
+/// A CSV file that detections are appended to.
+pub struct DetectionLog {
+    path: String,
+    writer: std::io::BufWriter<std::fs::File>,
+    unflushed: usize,
+}
+
+impl DetectionLog {
+    /// Opens `path` for appending and writes the header if the file is empty.
+    pub fn open(path: &str) -> std::io::Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        let is_empty = file.metadata()?.len() == 0;
+        let mut writer = std::io::BufWriter::new(file);
+        if is_empty {
+            writeln!(writer, "timestamp_s,chirp,frequency_hz,magnitude,snr_db")?;
+        }
+        Ok(Self {
+            path: path.to_owned(),
+            writer,
+            unflushed: 0,
+        })
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Appends one row per `(chirp, frequency, magnitude, snr_db)` detection, stamped with the
+    /// current time in seconds since the Unix epoch.
+    pub fn append(&mut self, detections: &[(usize, f64, f64, f64)]) -> std::io::Result<()> {
+        if detections.is_empty() {
+            return Ok(());
+        }
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0.0, |elapsed| elapsed.as_secs_f64());
+        for &(chirp, freq, mag, snr_db) in detections {
+            writeln!(
+                self.writer,
+                "{timestamp},{},{freq},{mag},{snr_db}",
+                chirp + 1
+            )?;
+        }
+        self.unflushed += detections.len();
+        if self.unflushed >= FLUSH_INTERVAL {
+            self.writer.flush()?;
+            self.unflushed = 0;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,1081 @@
+//! Signal processing that does not depend on the GUI.
+
+use std::cell::RefCell;
+use std::sync::Arc;
+
+use realfft::{RealFftPlanner, RealToComplex};
+pub use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftDirection, FftPlanner};
+
+/// Speed of light in vacuum in m/s, exact by the definition of the meter.
+pub const SPEED_OF_LIGHT: f64 = 299_792_458.0;
+
+/// Which direction of motion a positive velocity stands for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum VelocityConvention {
+    /// Positive velocities move away from the radar.
+    #[default]
+    Receding,
+    /// Positive velocities move towards the radar, as is common in automotive radar.
+    Approaching,
+}
+
+impl VelocityConvention {
+    pub const ALL: [Self; 2] = [Self::Receding, Self::Approaching];
+
+    /// Short label for the positive direction, e.g. for axis and slider labels.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Receding => "+ = receding",
+            Self::Approaching => "+ = approaching",
+        }
+    }
+
+    /// Converts `velocity` between this convention and [`Self::Receding`], in either direction.
+    pub fn receding(self, velocity: f64) -> f64 {
+        match self {
+            Self::Receding => velocity,
+            Self::Approaching => -velocity,
+        }
+    }
+}
+
+/// How [`doppler_shift`] computes the shift of an echo, both for the way to the target and back.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum DopplerModel {
+    /// The exact shift of a reflection off a moving mirror, `f·((c-v)/(c+v) - 1)` for a
+    /// receding velocity `v`.
+    #[default]
+    Relativistic,
+    /// The first-order approximation `-2·v·f/c` that radar textbooks use.
+    TwoWayClassical,
+}
+
+impl DopplerModel {
+    pub const ALL: [Self; 2] = [Self::Relativistic, Self::TwoWayClassical];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Relativistic => "Relativistic",
+            Self::TwoWayClassical => "Two-way classical",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Relativistic => "f·((c-v)/(c+v) - 1), exact for a reflector moving at v",
+            Self::TwoWayClassical => "-2·v·f/c, the approximation for v much slower than light",
+        }
+    }
+}
+
+/// Doppler shift in Hz of a signal at `frequency` reflected by a target moving at `velocity`.
+///
+/// Receding targets shift the echo down (redshift, a negative shift), approaching ones shift it
+/// up (blueshift, a positive shift). `convention` decides which of them has a positive
+/// `velocity`, `model` which formula computes the shift.
+pub fn doppler_shift(
+    frequency: f64,
+    velocity: f64,
+    convention: VelocityConvention,
+    model: DopplerModel,
+) -> f64 {
+    let velocity = convention.receding(velocity);
+    match model {
+        DopplerModel::Relativistic => {
+            frequency * ((SPEED_OF_LIGHT - velocity) / (SPEED_OF_LIGHT + velocity) - 1.0)
+        }
+        DopplerModel::TwoWayClassical => -2.0 * velocity * frequency / SPEED_OF_LIGHT,
+    }
+}
+
+/// How [`fftspectrum`] scales the FFT magnitudes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Normalization {
+    /// The raw FFT magnitudes, as returned by `numpy.fft.fft` and MATLAB's `fft`.
+    None,
+    /// Divided by the FFT length `N`.
+    PerSample,
+    /// Divided by `√N`, which preserves the energy (`norm="ortho"` in numpy).
+    Unitary,
+    /// Scaled by `2/N`, so a sine of amplitude `A` peaks at `A` in the single-sided spectrum. The
+    /// DC bin is scaled by `1/N`, so it shows the mean of the signal.
+    #[default]
+    Amplitude,
+}
+
+impl Normalization {
+    pub const ALL: [Self; 4] = [Self::None, Self::PerSample, Self::Unitary, Self::Amplitude];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::PerSample => "1/N",
+            Self::Unitary => "1/√N",
+            Self::Amplitude => "Amplitude-preserving",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::None => "Raw FFT magnitudes: a unit sine peaks at N/2",
+            Self::PerSample => "Divided by N: a unit sine peaks at 1/2",
+            Self::Unitary => "Divided by √N, energy preserving: a unit sine peaks at √N/2",
+            Self::Amplitude => {
+                "Scaled by 2/N for the single-sided spectrum: a unit sine peaks at 1"
+            }
+        }
+    }
+
+    /// Factor the FFT magnitudes of length `n` are multiplied with.
+    pub fn scale(self, n: usize) -> f64 {
+        let n = n as f64;
+        match self {
+            Self::None => 1.0,
+            Self::PerSample => 1.0 / n,
+            Self::Unitary => 1.0 / n.sqrt(),
+            Self::Amplitude => 2.0 / n,
+        }
+    }
+}
+
+/// Taper applied to the sampled beat signal before the FFT.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Window {
+    #[default]
+    Rectangular,
+    Hann,
+    Hamming,
+    Blackman,
+}
+
+impl Window {
+    pub const ALL: [Self; 4] = [Self::Rectangular, Self::Hann, Self::Hamming, Self::Blackman];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Rectangular => "Rectangular",
+            Self::Hann => "Hann",
+            Self::Hamming => "Hamming",
+            Self::Blackman => "Blackman",
+        }
+    }
+
+    /// Width of the main lobe at -3 dB, in bins.
+    pub fn half_power_width_bins(self) -> f64 {
+        match self {
+            Self::Rectangular => 0.886,
+            Self::Hann => 1.44,
+            Self::Hamming => 1.30,
+            Self::Blackman => 1.68,
+        }
+    }
+
+    /// The `n` window coefficients.
+    pub fn coefficients(self, n: usize) -> Vec<f64> {
+        let last = n.saturating_sub(1).max(1) as f64;
+        (0..n)
+            .map(|i| {
+                let phase = 2.0 * std::f64::consts::PI * i as f64 / last;
+                match self {
+                    Self::Rectangular => 1.0,
+                    Self::Hann => 0.5 - 0.5 * phase.cos(),
+                    Self::Hamming => 0.54 - 0.46 * phase.cos(),
+                    Self::Blackman => 0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos(),
+                }
+            })
+            .collect()
+    }
+
+    /// Mean of the `n` coefficients, the factor by which the window scales the peak of a tone.
+    pub fn coherent_gain(self, n: usize) -> f64 {
+        if n == 0 {
+            return 1.0;
+        }
+        self.coefficients(n).iter().sum::<f64>() / n as f64
+    }
+
+    /// `signal` multiplied by the window.
+    pub fn apply(self, signal: &[f64]) -> Vec<f64> {
+        signal
+            .iter()
+            .zip(self.coefficients(signal.len()))
+            .map(|(&x, w)| x * w)
+            .collect()
+    }
+}
+
+thread_local! {
+    // The planners cache the plan of every length they have seen, so keeping them around lets
+    // the FFTs recomputed every frame skip the planning
+    static REAL_PLANNER: RefCell<RealFftPlanner<f64>> = RefCell::new(RealFftPlanner::new());
+    static COMPLEX_PLANNER: RefCell<FftPlanner<f64>> = RefCell::new(FftPlanner::new());
+}
+
+/// Cached real-to-complex forward FFT of length `n`.
+fn real_fft(n: usize) -> Arc<dyn RealToComplex<f64>> {
+    REAL_PLANNER.with_borrow_mut(|planner| planner.plan_fft_forward(n))
+}
+
+/// Cached complex FFT of length `n`.
+fn complex_fft(n: usize, direction: FftDirection) -> Arc<dyn Fft<f64>> {
+    COMPLEX_PLANNER.with_borrow_mut(|planner| planner.plan_fft(n, direction))
+}
+
+/// Single-sided `(frequency, magnitude)` spectrum of a real `signal` sampled at `sampling_rate`,
+/// tapered by `window`.
+///
+/// The FFT has `fft_size` points: a longer `signal` is truncated, a shorter one is padded with
+/// zeros, which interpolates the spectrum onto a finer grid. The magnitudes are normalized by the
+/// number of samples rather than the padded length and divided by the coherent gain of the
+/// window, so the peak of a tone has the same height under every window and any padding.
+pub fn fftspectrum(
+    signal: &[f64],
+    sampling_rate: f64,
+    fft_size: usize,
+    window: Window,
+    normalization: Normalization,
+) -> Vec<(f64, f64)> {
+    let samples = signal.len().min(fft_size);
+    let n = fft_size;
+    // Compute magnitude spectrum (normalize)
+    let scale = normalization.scale(samples) / window.coherent_gain(samples);
+    // The single-sided scaling doubles every bin for its negative-frequency twin, which DC does
+    // not have. Neither does the Nyquist bin, but the spectrum ends just below it.
+    let dc_scale = if normalization == Normalization::Amplitude {
+        scale / 2.0
+    } else {
+        scale
+    };
+    beat_bins(signal, fft_size, window)
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let freq = i as f64 * sampling_rate / n as f64;
+            let mag = c.norm() * if i == 0 { dc_scale } else { scale };
+            (freq, mag)
+        })
+        .collect()
+}
+
+/// Magnitude spectrogram of a real `signal` sampled at `sampling_rate`: the [`fftspectrum`] of
+/// `window_len` samples centered on every `hop`-th sample, tapered by `window`.
+///
+/// Each row is one frame, in time order, and holds the magnitudes of its `window_len/2` bins.
+/// Frames that reach past either end of the signal are padded with zeros there.
+pub fn spectrogram(
+    signal: &[f64],
+    sampling_rate: f64,
+    window_len: usize,
+    hop: usize,
+    window: Window,
+    normalization: Normalization,
+) -> Vec<Vec<f64>> {
+    let hop = hop.max(1);
+    let half = window_len / 2;
+    (0..signal.len())
+        .step_by(hop)
+        .map(|center| {
+            let frame: Vec<f64> = (0..window_len)
+                .map(|i| {
+                    (center + i)
+                        .checked_sub(half)
+                        .and_then(|idx| signal.get(idx))
+                        .copied()
+                        .unwrap_or(0.0)
+                })
+                .collect();
+            fftspectrum(&frame, sampling_rate, window_len, window, normalization)
+                .into_iter()
+                .map(|(_, mag)| mag)
+                .collect()
+        })
+        .collect()
+}
+
+/// Welch estimate of the single-sided `(frequency, magnitude)` spectrum of a real `signal`
+/// sampled at `sampling_rate`: the mean magnitude of the [`fftspectrum`] of every segment of
+/// `segment_len` samples, each overlapping the next by the fraction `overlap` of its samples.
+///
+/// Averaging the segments steadies the noise bins at the cost of the wider bins of the shorter
+/// FFT. Samples past the last whole segment are left out; a signal shorter than one segment is
+/// transformed whole, padded with zeros to `segment_len`.
+pub fn welch_spectrum(
+    signal: &[f64],
+    sampling_rate: f64,
+    segment_len: usize,
+    overlap: f64,
+    window: Window,
+    normalization: Normalization,
+) -> Vec<(f64, f64)> {
+    let segment_len = segment_len.max(1);
+    // An overlap of 1 would never advance
+    let hop = ((segment_len as f64 * (1.0 - overlap.clamp(0.0, 1.0))).round() as usize).max(1);
+    let segments: Vec<&[f64]> = if signal.len() < segment_len {
+        vec![signal]
+    } else {
+        signal.windows(segment_len).step_by(hop).collect()
+    };
+    let mut spectra = segments
+        .iter()
+        .map(|segment| fftspectrum(segment, sampling_rate, segment_len, window, normalization));
+    let Some(mut average) = spectra.next() else {
+        return vec![];
+    };
+    for spectrum in spectra {
+        for ((_, sum), (_, mag)) in average.iter_mut().zip(spectrum) {
+            *sum += mag;
+        }
+    }
+    let count = segments.len() as f64;
+    for (_, mag) in &mut average {
+        *mag /= count;
+    }
+    average
+}
+
+/// The `fft_size/2` complex non-negative frequency bins of a real `signal` tapered by `window`,
+/// truncated or zero-padded to `fft_size` points like in [`fftspectrum`], without any scaling.
+pub fn beat_bins(signal: &[f64], fft_size: usize, window: Window) -> Vec<Complex<f64>> {
+    let samples = signal.get(..fft_size).unwrap_or(signal);
+    // The input is real, so the real-to-complex FFT only computes the n/2+1 non-negative bins
+    let fft = real_fft(fft_size);
+    let mut input = window.apply(samples);
+    input.resize(fft_size, 0.0);
+    let mut buffer = fft.make_output_vec();
+    fft.process(&mut input, &mut buffer)
+        .expect("buffers are sized for the plan");
+    buffer.truncate(fft_size / 2);
+    buffer
+}
+
+/// `(azimuth, magnitude)` spectrum of the values `snapshot` that one beat bin takes on the
+/// elements of a uniform linear array spaced `spacing` wavelengths apart.
+///
+/// An echo from `θ` degrees off boresight reaches each element `2π·spacing·sin θ` radians later
+/// than the one before, so an FFT of `bins` points across the zero-padded elements resolves its
+/// angle. Bins whose `sin θ` would exceed 1 are dropped; the rest are in ascending azimuth. The
+/// magnitudes are divided by the number of elements.
+pub fn angle_spectrum(snapshot: &[Complex<f64>], spacing: f64, bins: usize) -> Vec<(f64, f64)> {
+    let elements = snapshot.len();
+    if elements == 0 || bins < elements || spacing <= 0.0 {
+        return vec![];
+    }
+    let mut buffer = snapshot.to_vec();
+    buffer.resize(bins, Complex::new(0.0, 0.0));
+    complex_fft(bins, FftDirection::Forward).process(&mut buffer);
+    // The phase progression of the bin k is k/bins cycles per element
+    let negative = bins - bins / 2;
+    buffer
+        .iter()
+        .enumerate()
+        .skip(negative)
+        .chain(buffer.iter().enumerate().take(negative))
+        .filter_map(|(k, c)| {
+            let bin = if k < negative {
+                k as f64
+            } else {
+                k as f64 - bins as f64
+            };
+            let sin = bin / bins as f64 / spacing;
+            (sin.abs() <= 1.0).then(|| (sin.asin().to_degrees(), c.norm() / elements as f64))
+        })
+        .collect()
+}
+
+/// Two-sided `(frequency, magnitude)` spectrum of a complex `signal` sampled at `sampling_rate`,
+/// from `-sampling_rate/2` up to `sampling_rate/2`.
+///
+/// The magnitudes are divided by the FFT length, so a unit complex exponential peaks at 1.
+pub fn two_sided_spectrum(signal: &[Complex<f64>], sampling_rate: f64) -> Vec<(f64, f64)> {
+    let n = signal.len();
+    let mut buffer = signal.to_vec();
+    complex_fft(n, FftDirection::Forward).process(&mut buffer);
+    // Negative frequencies are in the upper half of the bins
+    let negative = n - n / 2;
+    buffer
+        .iter()
+        .enumerate()
+        .skip(negative)
+        .chain(buffer.iter().enumerate().take(negative))
+        .map(|(i, c)| {
+            let bin = if i < negative {
+                i as f64
+            } else {
+                i as f64 - n as f64
+            };
+            (bin * sampling_rate / n as f64, c.norm() / n as f64)
+        })
+        .collect()
+}
+
+/// Like [`sample_signal`], with the carrier `phase` in radians at every timestamp added to every
+/// frequency.
+pub fn sample_noisy_signal(t: &[f64], reflections: &[(f64, f64)], phase: &[f64]) -> Vec<f64> {
+    t.iter()
+        .zip(phase)
+        .map(|(&t, &phase)| {
+            reflections
+                .iter()
+                .map(|&(f, amplitude)| {
+                    amplitude * (2.0 * std::f64::consts::PI * f * t + phase).sin()
+                })
+                .sum()
+        })
+        .collect()
+}
+
+/// Sum of a sine per `(frequency, amplitude)` reflection at every timestamp of `t`.
+pub fn sample_signal(t: &[f64], reflections: &[(f64, f64)]) -> Vec<f64> {
+    // For each timestamp in t, sum a·sin(2π f t) for all reflections and return a Vec
+    t.iter()
+        .map(|&t_val| {
+            let mut sum = 0.0;
+            for &(f, amplitude) in reflections {
+                sum += amplitude * (2.0 * std::f64::consts::PI * f * t_val).sin();
+            }
+            sum
+        })
+        .collect()
+}
+
+/// Beat signal of every element of a uniform linear array of `elements` antennas spaced `spacing`
+/// wavelengths apart, at every timestamp of `t`, for `(frequency, amplitude, azimuth)` reflections.
+///
+/// The first element samples [`sample_signal`] of the beats' magnitudes. The echo from `azimuth`
+/// degrees reaches every further element `2π·spacing·sin(azimuth)` radians later.
+pub fn sample_array_signal(
+    t: &[f64],
+    reflections: &[(f64, f64, f64)],
+    elements: usize,
+    spacing: f64,
+) -> Vec<Vec<f64>> {
+    (0..elements)
+        .map(|element| {
+            t.iter()
+                .map(|&t| {
+                    reflections
+                        .iter()
+                        .map(|&(f, amplitude, azimuth)| {
+                            let delay = element as f64 * spacing * azimuth.to_radians().sin();
+                            let cycles = f.abs() * t + delay;
+                            amplitude * (2.0 * std::f64::consts::PI * cycles).sin()
+                        })
+                        .sum()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Applies the gain and phase imbalance of an IQ receiver to ideal `iq` samples, in place.
+///
+/// The Q channel is amplified by `gain_db` and its local oscillator is off quadrature by
+/// `phase_deg`, which mirrors every tone at `f` as an image at `-f`.
+pub fn apply_iq_imbalance(iq: &mut [Complex<f64>], gain_db: f64, phase_deg: f64) {
+    let gain = 10f64.powf(gain_db / 20.0);
+    let (sin, cos) = phase_deg.to_radians().sin_cos();
+    for sample in iq {
+        // sin(φ + θ) = sin φ cos θ + cos φ sin θ
+        sample.im = gain * (sample.im * cos + sample.re * sin);
+    }
+}
+
+/// Random phase in radians of an oscillator with a 1/f² phase-noise spectrum, for `n` samples.
+///
+/// The phase is a random walk whose steps are normally distributed with a standard deviation of
+/// `level` radians, so it drifts further the longer the signal is.
+pub fn phase_noise(n: usize, level: f64, rng: &mut impl rand::Rng) -> Vec<f64> {
+    let mut phase = 0.0;
+    (0..n)
+        .map(|_| {
+            // Box-Muller transform, `1 - u` keeps the logarithm finite
+            let u: f64 = rng.random();
+            let v: f64 = rng.random();
+            let step = (-2.0 * (1.0 - u).ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos();
+            phase += level * step;
+            phase
+        })
+        .collect()
+}
+
+/// Standard deviation of the steps of [`phase_noise`] sampled at `sample_rate`, for an oscillator
+/// whose single-sideband phase noise is `dbc_per_hz` at `offset` Hz from the carrier.
+///
+/// A random walk with steps of `level` radians has the single-sideband spectrum
+/// `L(f) = level² · sample_rate / (2π f)²`, solved here for `level`.
+pub fn phase_noise_level(dbc_per_hz: f64, offset: f64, sample_rate: f64) -> f64 {
+    let ssb = 10f64.powf(dbc_per_hz / 10.0);
+    2.0 * std::f64::consts::PI * offset * (ssb / sample_rate).sqrt()
+}
+
+/// Adds white Gaussian noise to `signal`, `snr_db` below the mean power of the signal itself.
+///
+/// A silent signal stays silent, as there is no power to set the noise relative to.
+pub fn add_white_noise(signal: &mut [f64], snr_db: f64, rng: &mut impl rand::Rng) {
+    if signal.is_empty() {
+        return;
+    }
+    let power = signal.iter().map(|x| x * x).sum::<f64>() / signal.len() as f64;
+    let sigma = (power / 10f64.powf(snr_db / 10.0)).sqrt();
+    let Ok(normal) = rand_distr::Normal::new(0.0, sigma) else {
+        return;
+    };
+    for sample in signal {
+        *sample += rng.sample(normal);
+    }
+}
+
+/// Subtracts the mean of `signal`, or with `linear` its least-squares straight line, so a DC
+/// offset or a slow drift does not swamp the lowest bins of its spectrum.
+pub fn detrend(signal: &mut [f64], linear: bool) {
+    if signal.is_empty() {
+        return;
+    }
+    let n = signal.len() as f64;
+    let mean = signal.iter().sum::<f64>() / n;
+    let center = (n - 1.0) / 2.0;
+    let slope = if linear && signal.len() > 1 {
+        let (covariance, variance) =
+            signal
+                .iter()
+                .enumerate()
+                .fold((0.0, 0.0), |(covariance, variance), (i, &value)| {
+                    let x = i as f64 - center;
+                    (covariance + x * (value - mean), variance + x * x)
+                });
+        covariance / variance
+    } else {
+        0.0
+    };
+    for (i, sample) in signal.iter_mut().enumerate() {
+        *sample -= mean + slope * (i as f64 - center);
+    }
+}
+
+/// Quantizes `signal` like an ADC with `bits` of resolution over `-full_scale..=full_scale`.
+///
+/// Every sample is rounded to the closest of the `2^bits` levels, which sit in the middle of
+/// their steps, so a 1-bit ADC only keeps the sign. Samples beyond full scale saturate at the
+/// outermost level. Zero bits leave the signal as it is.
+pub fn quantize(signal: &mut [f64], bits: u32, full_scale: f64) {
+    if bits == 0 {
+        return;
+    }
+    let levels = 2f64.powf(f64::from(bits));
+    let step = 2.0 * full_scale / levels;
+    let outermost = full_scale - step / 2.0;
+    for sample in signal {
+        let level = (*sample / step).floor() + 0.5;
+        *sample = (level * step).clamp(-outermost, outermost);
+    }
+}
+
+/// `signal` low-pass filtered by a Hann-windowed sinc with `taps` coefficients (rounded up to an
+/// odd count) that cuts off at `cutoff` cycles per sample.
+///
+/// The filter is centered on every sample, so it does not delay the signal. Samples beyond the
+/// ends count as zero.
+pub fn lowpass(signal: &[f64], cutoff: f64, taps: usize) -> Vec<f64> {
+    use std::f64::consts::PI;
+
+    let half = taps / 2;
+    let kernel: Vec<f64> = (0..=2 * half)
+        .map(|i| {
+            let m = i as f64 - half as f64;
+            let sinc = if i == half {
+                2.0 * cutoff
+            } else {
+                (2.0 * PI * cutoff * m).sin() / (PI * m)
+            };
+            sinc * (0.5 + 0.5 * (PI * m / (half as f64 + 1.0)).cos())
+        })
+        .collect();
+    // Normalize to unit gain at DC
+    let gain: f64 = kernel.iter().sum();
+    (0..signal.len())
+        .map(|i| {
+            kernel
+                .iter()
+                .enumerate()
+                .filter_map(|(j, &h)| {
+                    let idx = (i + j).checked_sub(half)?;
+                    signal.get(idx).map(|&x| x * h)
+                })
+                .sum::<f64>()
+                / gain
+        })
+        .collect()
+}
+
+/// Magnitude of the frequency response of a window with the given `coefficients`, as
+/// `(offset, magnitude)` pairs for offsets in Hz from `-max_offset` to `max_offset`.
+///
+/// The response is sampled `oversampling` times finer than the FFT bins by zero-padding the
+/// window. A sine at `f` with amplitude `A` shows up in the unnormalized FFT of the windowed
+/// signal as `A/2` times this response centered at `f`.
+pub fn window_response(
+    coefficients: &[f64],
+    sampling_rate: f64,
+    oversampling: usize,
+    max_offset: f64,
+) -> Vec<(f64, f64)> {
+    // The window is real, so its magnitude response is symmetric around zero
+    let positive: Vec<(f64, f64)> = fftspectrum(
+        coefficients,
+        sampling_rate,
+        coefficients.len() * oversampling.max(1),
+        Window::Rectangular,
+        Normalization::None,
+    )
+    .into_iter()
+    .take_while(|&(offset, _)| offset <= max_offset)
+    .collect();
+    positive
+        .iter()
+        .skip(1)
+        .rev()
+        .map(|&(offset, mag)| (-offset, mag))
+        .chain(positive.iter().copied())
+        .collect()
+}
+
+/// Magnitudes of a `(frequency, magnitude)` spectrum interpolated linearly onto the
+/// frequencies of `axis`, so spectra with different bin spacings can be compared bin by bin.
+///
+/// The spectrum must be sorted by frequency. Frequencies outside of it map to zero.
+pub fn resample_spectrum(spectrum: &[(f64, f64)], axis: &[f64]) -> Vec<f64> {
+    axis.iter()
+        .map(|&freq| {
+            let upper = spectrum.partition_point(|&(f, _)| f < freq);
+            match (
+                upper.checked_sub(1).and_then(|i| spectrum.get(i)),
+                spectrum.get(upper),
+            ) {
+                (_, Some(&(f1, m1))) if f1 == freq => m1,
+                (Some(&(f0, m0)), Some(&(f1, m1))) => m0 + (m1 - m0) * (freq - f0) / (f1 - f0),
+                _ => 0.0,
+            }
+        })
+        .collect()
+}
+
+/// Autocorrelation of `signal` for lags of `0..signal.len()` samples, normalized to 1 at lag 0.
+///
+/// Computed as the inverse FFT of the power spectrum, zero-padded so the correlation does not
+/// wrap around.
+pub fn autocorrelate(signal: &[f64]) -> Vec<f64> {
+    let n = signal.len();
+    let padded = (2 * n).next_power_of_two();
+    let mut buffer: Vec<Complex<f64>> = signal
+        .iter()
+        .map(|&x| Complex { re: x, im: 0.0 })
+        .chain(std::iter::repeat(Complex { re: 0.0, im: 0.0 }))
+        .take(padded)
+        .collect();
+    complex_fft(padded, FftDirection::Forward).process(&mut buffer);
+    for c in &mut buffer {
+        *c = Complex {
+            re: c.norm_sqr(),
+            im: 0.0,
+        };
+    }
+    complex_fft(padded, FftDirection::Inverse).process(&mut buffer);
+
+    let zero_lag = buffer.first().map_or(0.0, |c| c.re);
+    buffer
+        .iter()
+        .take(n)
+        .map(|c| if zero_lag > 0.0 { c.re / zero_lag } else { 0.0 })
+        .collect()
+}
+
+/// Index of the time in `v` closest to `t`, or 0 if `v` is empty. Of two equally close times the
+/// earlier one wins.
+///
+/// `v` must be sorted in ascending order, like the time steps of the simulation, because it is
+/// searched by bisection.
+pub fn idx_at_t(v: &[f64], t: f64) -> usize {
+    let after = v.partition_point(|&ti| ti < t);
+    let Some(before) = after.checked_sub(1) else {
+        return 0;
+    };
+    match (v.get(before), v.get(after)) {
+        (Some(&earlier), Some(&later)) if later - t < t - earlier => after,
+        _ => before,
+    }
+}
+
+/// Beat frequency in Hz per meter of range on a chirp of `chirp` seconds sweeping `bandwidth` Hz,
+/// i.e. `2·slope/c`.
+pub fn beat_per_meter(chirp: f64, bandwidth: f64) -> f64 {
+    2.0 * bandwidth / (chirp * SPEED_OF_LIGHT)
+}
+
+/// Magnitude of the ambiguity function of a complex `waveform` sampled at `sampling_rate`.
+///
+/// Each of the `doppler_bins` rows is one Doppler shift, in ascending order and centered at
+/// zero, spaced `sampling_rate / waveform.len()` apart. The columns are the delays of
+/// `-(n-1)..=(n-1)` samples. The magnitudes are normalized to 1 at zero delay and Doppler.
+pub fn ambiguity_function(
+    waveform: &[Complex<f64>],
+    sampling_rate: f64,
+    doppler_bins: usize,
+) -> Vec<Vec<f64>> {
+    let n = waveform.len();
+    let energy: f64 = waveform.iter().map(Complex::norm_sqr).sum();
+    if n == 0 || energy == 0.0 {
+        return vec![];
+    }
+    // Zero-padded so the correlation over all delays does not wrap around
+    let padded = (2 * n).next_power_of_two();
+    let forward = complex_fft(padded, FftDirection::Forward);
+    let inverse = complex_fft(padded, FftDirection::Inverse);
+    let pad = |samples: &mut Vec<Complex<f64>>| samples.resize(padded, Complex::new(0.0, 0.0));
+
+    let mut reference = waveform.to_vec();
+    pad(&mut reference);
+    forward.process(&mut reference);
+
+    (0..doppler_bins)
+        .map(|bin| {
+            let doppler = (bin as f64 - (doppler_bins / 2) as f64) * sampling_rate / n as f64;
+            let mut shifted: Vec<Complex<f64>> = waveform
+                .iter()
+                .enumerate()
+                .map(|(i, &sample)| {
+                    let t = i as f64 / sampling_rate;
+                    sample * Complex::from_polar(1.0, 2.0 * std::f64::consts::PI * doppler * t)
+                })
+                .collect();
+            pad(&mut shifted);
+            forward.process(&mut shifted);
+            for (a, b) in shifted.iter_mut().zip(&reference) {
+                *a *= b.conj();
+            }
+            inverse.process(&mut shifted);
+            // Negative delays wrap around to the end of the correlation
+            (1..n)
+                .rev()
+                .map(|lag| padded - lag)
+                .chain(0..n)
+                .map(|idx| {
+                    shifted
+                        .get(idx)
+                        .map_or(0.0, |c| c.norm() / (padded as f64 * energy))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Magnitude range-Doppler map of `frames`, the real beat signals of successive identical chirps.
+///
+/// Every frame is tapered by `window` and transformed on its own (fast time), which resolves the
+/// beat and so the range. Every range bin is then tapered and transformed across the frames (slow
+/// time), which resolves how its phase advances from chirp to chirp, i.e. the Doppler shift.
+///
+/// Each of the `frames.len()` rows is one Doppler bin, in ascending order and centered at zero,
+/// spaced one over `frames.len()` chirp durations apart. The columns are the `n/2` beat bins of
+/// frames of `n` samples; longer frames are truncated to the shortest. The magnitudes are
+/// normalized to 1 at the strongest cell.
+pub fn range_doppler(frames: &[Vec<f64>], window: Window) -> Vec<Vec<f64>> {
+    let n = frames.iter().map(Vec::len).min().unwrap_or(0);
+    let chirps = frames.len();
+    if n < 2 {
+        return vec![];
+    }
+    let fast = real_fft(n);
+    let fast_time: Vec<Vec<Complex<f64>>> = frames
+        .iter()
+        .map(|frame| {
+            let mut input = window.apply(frame.get(..n).unwrap_or(frame));
+            let mut output = fast.make_output_vec();
+            fast.process(&mut input, &mut output)
+                .expect("buffers are sized for the plan");
+            output.truncate(n / 2);
+            output
+        })
+        .collect();
+
+    let slow = complex_fft(chirps, FftDirection::Forward);
+    let taper = window.coefficients(chirps);
+    let slow_time: Vec<Vec<f64>> = (0..n / 2)
+        .map(|bin| {
+            let mut buffer: Vec<Complex<f64>> = fast_time
+                .iter()
+                .zip(&taper)
+                .map(|(spectrum, &w)| spectrum.get(bin).map_or(Complex::new(0.0, 0.0), |&c| c * w))
+                .collect();
+            slow.process(&mut buffer);
+            buffer.iter().map(|c| c.norm()).collect()
+        })
+        .collect();
+
+    let peak = slow_time
+        .iter()
+        .flatten()
+        .fold(0.0_f64, |max, &mag| max.max(mag));
+    // Negative Doppler shifts are in the upper half of the bins
+    let negative = chirps - chirps / 2;
+    (negative..chirps)
+        .chain(0..negative)
+        .map(|doppler_bin| {
+            slow_time
+                .iter()
+                .map(|spectrum| {
+                    let mag = spectrum.get(doppler_bin).copied().unwrap_or(0.0);
+                    if peak > 0.0 { mag / peak } else { 0.0 }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Doppler shift at each of the beat `bins` across `frames`, the real beat signals of successive
+/// identical chirps, in cycles per chirp from -0.5 to 0.5.
+///
+/// Every frame is transformed into `fft_size` points like in [`beat_bins`]. The value of a bin
+/// advances in phase by the Doppler shift times the chirp duration from one frame to the next, so
+/// an FFT across the frames, tapered by `window`, peaks at that shift. The peak is refined between
+/// the Doppler bins like in [`refine_peak`]. Empty without frames.
+pub fn doppler_per_chirp(
+    frames: &[Vec<f64>],
+    bins: &[usize],
+    fft_size: usize,
+    window: Window,
+) -> Vec<f64> {
+    let chirps = frames.len();
+    if chirps == 0 {
+        return vec![];
+    }
+    let fast_time: Vec<Vec<Complex<f64>>> = frames
+        .iter()
+        .map(|frame| beat_bins(frame, fft_size, window))
+        .collect();
+    let slow = complex_fft(chirps, FftDirection::Forward);
+    let taper = window.coefficients(chirps);
+    // Negative Doppler shifts are in the upper half of the bins
+    let negative = chirps - chirps / 2;
+    bins.iter()
+        .map(|&bin| {
+            let mut buffer: Vec<Complex<f64>> = fast_time
+                .iter()
+                .zip(&taper)
+                .map(|(spectrum, &w)| spectrum.get(bin).map_or(Complex::new(0.0, 0.0), |&c| c * w))
+                .collect();
+            slow.process(&mut buffer);
+            let mags: Vec<f64> = (negative..chirps)
+                .chain(0..negative)
+                .map(|doppler_bin| buffer.get(doppler_bin).map_or(0.0, |c| c.norm()))
+                .collect();
+            let peak_idx = mags
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.total_cmp(b.1))
+                .map_or(0, |(idx, _)| idx);
+            let (peak, _) = refine_peak(&mags, peak_idx);
+            (peak - (chirps / 2) as f64) / chirps as f64
+        })
+        .collect()
+}
+
+/// Range in meters of a stationary target whose beat frequency is `beat` Hz on a chirp of
+/// `chirp` seconds sweeping `bandwidth` Hz.
+pub fn range_for_beat(beat: f64, chirp: f64, bandwidth: f64) -> f64 {
+    beat.abs() * SPEED_OF_LIGHT * chirp / (2.0 * bandwidth)
+}
+
+/// Doppler shift in Hz that gives a target at `range` meters a beat frequency of `beat` Hz on a
+/// chirp of `chirp` seconds sweeping `bandwidth` Hz. A `falling` chirp ramps down instead of up.
+pub fn doppler_for_beat(beat: f64, range: f64, chirp: f64, falling: bool, bandwidth: f64) -> f64 {
+    // The range shifts the echo down by the beat of a stationary target on a rising chirp and up
+    // on a falling one, the Doppler shift makes up the rest
+    let range_beat = 2.0 * range * bandwidth / (SPEED_OF_LIGHT * chirp);
+    if falling {
+        beat.abs() - range_beat
+    } else {
+        range_beat - beat.abs()
+    }
+}
+
+/// Velocity in m/s that shifts an echo by `doppler` Hz while the radar transmits at `frequency`,
+/// the inverse of [`doppler_shift`].
+pub fn velocity_for_doppler(
+    doppler: f64,
+    frequency: f64,
+    convention: VelocityConvention,
+    model: DopplerModel,
+) -> f64 {
+    let receding = match model {
+        DopplerModel::Relativistic => {
+            let ratio = 1.0 + doppler / frequency;
+            SPEED_OF_LIGHT * (1.0 - ratio) / (1.0 + ratio)
+        }
+        DopplerModel::TwoWayClassical => -doppler * SPEED_OF_LIGHT / (2.0 * frequency),
+    };
+    convention.receding(receding)
+}
+
+/// Amplitude factor of the echo of a target `range` meters away through an atmosphere that
+/// attenuates by `loss` dB per km, on the way to the target and back.
+///
+/// Absorption by oxygen and water vapour and scattering by rain take a fixed number of dB per km,
+/// so the echo weakens exponentially with the path length on top of the spreading loss.
+pub fn atmospheric_attenuation(loss: f64, range: f64) -> f64 {
+    let path_km = 2.0 * range.max(0.0) * 1e-3;
+    10.0_f64.powf(-loss * path_km / 20.0)
+}
+
+/// Total group delay in seconds of a receive chain whose stages delay the signal by
+/// `stage_delays` seconds each.
+///
+/// Every stage between the antenna and the mixer delays the echo, which makes objects appear
+/// `c·delay/2` further away than they are.
+pub fn chain_group_delay(stage_delays: &[f64]) -> f64 {
+    stage_delays.iter().sum()
+}
+
+/// Width in Hz of the peak at `peak_idx` of a `(frequency, magnitude)` spectrum, measured where
+/// the magnitude has dropped by 3 dB, i.e. to `1/√2` of the peak.
+///
+/// The crossings are interpolated linearly between bins. If the magnitude never drops that far,
+/// the spectrum edge is taken instead.
+pub fn measure_3db_width(spectrum: &[(f64, f64)], peak_idx: usize) -> f64 {
+    let (Some(&(_, peak)), Some(lower), Some(upper)) = (
+        spectrum.get(peak_idx),
+        spectrum.get(..=peak_idx),
+        spectrum.get(peak_idx..),
+    ) else {
+        return 0.0;
+    };
+    let threshold = peak / std::f64::consts::SQRT_2;
+    half_power_edge(upper.iter(), threshold) - half_power_edge(lower.iter().rev(), threshold)
+}
+
+/// Frequency at which the magnitude first drops below `threshold`, walking away from the peak
+/// that `bins` starts at.
+fn half_power_edge<'a>(bins: impl Iterator<Item = &'a (f64, f64)>, threshold: f64) -> f64 {
+    let mut inside: Option<(f64, f64)> = None;
+    for &(freq, mag) in bins {
+        if let Some((inside_freq, inside_mag)) = inside
+            && mag < threshold
+        {
+            let fraction = (inside_mag - threshold) / (inside_mag - mag);
+            return inside_freq + fraction * (freq - inside_freq);
+        }
+        inside = Some((freq, mag));
+    }
+    inside.map_or(0.0, |(freq, _)| freq)
+}
+
+/// Noise floor of a `(frequency, magnitude)` spectrum, estimated as its median magnitude.
+///
+/// The few bins of the peaks barely move the median, unlike the mean.
+pub fn noise_floor(spectrum: &[(f64, f64)]) -> f64 {
+    let mut mags: Vec<f64> = spectrum.iter().map(|&(_, mag)| mag).collect();
+    mags.sort_by(f64::total_cmp);
+    mags.get(mags.len() / 2).copied().unwrap_or(0.0)
+}
+
+/// Signal-to-noise ratio in dB of a peak of `magnitude` above `noise_floor`.
+pub fn snr_db(magnitude: f64, noise_floor: f64) -> f64 {
+    20.0 * (magnitude / noise_floor).log10()
+}
+
+/// Fractional bin and magnitude of the peak at `peak_idx` of `mags`, refined by fitting a
+/// parabola through the log-magnitudes of the peak bin and its two neighbours.
+///
+/// The main lobe of a tapered window is close to a Gaussian, which is a parabola in the log
+/// domain, so this is more accurate than a fit through the magnitudes themselves. Peaks at the
+/// edges, next to empty bins or without a maximum at `peak_idx` keep their bin and magnitude.
+pub fn refine_peak(mags: &[f64], peak_idx: usize) -> (f64, f64) {
+    let bin = peak_idx as f64;
+    let peak = mags.get(peak_idx).copied().unwrap_or(0.0);
+    let (Some(&left), Some(&right)) = (
+        peak_idx.checked_sub(1).and_then(|i| mags.get(i)),
+        mags.get(peak_idx + 1),
+    ) else {
+        return (bin, peak);
+    };
+    if left <= 0.0 || peak <= 0.0 || right <= 0.0 {
+        return (bin, peak);
+    }
+    let (left, center, right) = (left.ln(), peak.ln(), right.ln());
+    let curvature = left - 2.0 * center + right;
+    if curvature >= 0.0 {
+        return (bin, peak);
+    }
+    let offset = (0.5 * (left - right) / curvature).clamp(-0.5, 0.5);
+    let value = center - 0.25 * (left - right) * offset;
+    (bin + offset, value.exp())
+}
+
+/// Find multiple peaks in a signal above the baseline (average).
+///
+/// Returns the indices of the peaks in ascending order. A peak is a local maximum above the mean
+/// of the whole signal: a sample that is at least its left neighbor and greater than its right
+/// one, so a flat top counts once, at its last sample. The first and last samples lack a
+/// neighbor and are never peaks. Neither a flat nor a monotonically rising signal has a peak.
+pub fn multiple_peak_finding(signal: &[f64]) -> Vec<usize> {
+    if signal.is_empty() {
+        return vec![];
+    }
+    let baseline = signal.iter().sum::<f64>() / signal.len() as f64;
+    signal
+        .windows(3)
+        .enumerate()
+        .filter_map(|(left, window)| match *window {
+            [prev, value, next] if value > baseline && value >= prev && value > next => {
+                Some(left + 1)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Cell-averaging CFAR: the indices of the bins of `mags` that exceed `alpha` times their local
+/// noise estimate, in ascending order.
+///
+/// The noise of a bin is the mean of the `train` bins on either side of it, beyond `guard` bins
+/// that are skipped so the main lobe of a peak does not raise its own estimate. Near the edges
+/// only the training cells that exist are averaged; a bin without any is never detected.
+pub fn cfar_detect(mags: &[f64], guard: usize, train: usize, alpha: f64) -> Vec<usize> {
+    let n = mags.len();
+    (0..n)
+        .filter(|&i| {
+            let leading = mags
+                .get(i.saturating_sub(guard + train)..i.saturating_sub(guard))
+                .unwrap_or_default();
+            let trailing = mags
+                .get((i + guard + 1).min(n)..(i + guard + 1 + train).min(n))
+                .unwrap_or_default();
+            let count = leading.len() + trailing.len();
+            if count == 0 {
+                return false;
+            }
+            let noise = leading.iter().chain(trailing).sum::<f64>() / count as f64;
+            mags.get(i).is_some_and(|&mag| mag > alpha * noise)
+        })
+        .collect()
+}
+
+/// Merges every run of adjacent `detections` into its strongest bin of `mags`, so a peak whose
+/// main lobe spans several bins is reported once.
+pub fn merge_adjacent_detections(mags: &[f64], detections: &[usize]) -> Vec<usize> {
+    let magnitude = |i: usize| mags.get(i).copied().unwrap_or(f64::NEG_INFINITY);
+    let mut merged: Vec<usize> = vec![];
+    let mut previous: Option<usize> = None;
+    for &idx in detections {
+        match merged.last_mut() {
+            Some(strongest) if previous.is_some_and(|previous| previous + 1 == idx) => {
+                if magnitude(idx) > magnitude(*strongest) {
+                    *strongest = idx;
+                }
+            }
+            _ => merged.push(idx),
+        }
+        previous = Some(idx);
+    }
+    merged
+}
@@ -0,0 +1,87 @@
+/// `samples` as a mono 16-bit PCM WAV file at `sample_rate` Hz, scaled so that the largest
+/// sample reaches full scale without clipping.
+pub fn wav_file(samples: &[f64], sample_rate: u32) -> hound::Result<Vec<u8>> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let peak = samples
+        .iter()
+        .fold(0.0_f64, |peak, sample| peak.max(sample.abs()));
+    let scale = if peak > 0.0 {
+        f64::from(i16::MAX) / peak
+    } else {
+        0.0
+    };
+    let mut file = std::io::Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut file, spec)?;
+    for &sample in samples {
+        writer.write_sample((sample * scale).round() as i16)?;
+    }
+    writer.finalize()?;
+    Ok(file.into_inner())
+}
+
+/// Hands `contents` to the user as a file called `file_name`.
+///
+/// Natively this opens a save dialog; cancelling it is not an error.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_file(file_name: &str, contents: &[u8]) -> std::io::Result<()> {
+    if let Some(path) = rfd::FileDialog::new().set_file_name(file_name).save_file() {
+        std::fs::write(path, contents)?;
+    }
+    Ok(())
+}
+
+/// Hands `contents` to the user as a file called `file_name`.
+///
+/// On the web the browser downloads the file.
+#[cfg(target_arch = "wasm32")]
+pub fn save_file(file_name: &str, contents: &[u8]) -> std::io::Result<()> {
+    use eframe::wasm_bindgen::JsCast as _;
+
+    let js_error = |e: eframe::wasm_bindgen::JsValue| std::io::Error::other(format!("{e:?}"));
+
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(contents));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type("application/octet-stream");
+    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options)
+        .map_err(js_error)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(js_error)?;
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| std::io::Error::other("No document"))?;
+    let anchor = document
+        .create_element("a")
+        .map_err(js_error)?
+        .dyn_into::<web_sys::HtmlAnchorElement>()
+        .map_err(|_element| std::io::Error::other("Not an anchor element"))?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    web_sys::Url::revoke_object_url(&url).map_err(js_error)
+}
+
+/// Contents of a file the user picks, or `None` if they cancel the open dialog.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn open_file() -> std::io::Result<Option<Vec<u8>>> {
+    rfd::FileDialog::new()
+        .add_filter("JSON", &["json"])
+        .pick_file()
+        .map(std::fs::read)
+        .transpose()
+}
+
+/// Contents of a file the user picks.
+///
+/// The browser only reads files asynchronously, which the immediate-mode UI cannot wait for.
+#[cfg(target_arch = "wasm32")]
+pub fn open_file() -> std::io::Result<Option<Vec<u8>>> {
+    Err(std::io::Error::other(
+        "Opening files is not supported on the web, paste the scene into Scene JSON instead",
+    ))
+}
@@ -1,4 +1,17 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
 mod app;
+mod detection_log;
+pub mod dsp;
+mod export;
+mod presets;
+mod raster;
+mod simulation;
+mod svg;
 pub use app::App;
+pub use presets::Preset;
+pub use simulation::{
+    AnalysisSettings, Chirp, PathLoss, RadarConfig, RadarObject, Sampling, Scene, SignalSource,
+    SimulationResult, SwerlingModel, Waveform, beat_frequencies, beat_frequencies_along,
+    chirp_index, compute_beats, multipath_range, saw, simulate, sweep,
+};
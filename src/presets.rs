@@ -0,0 +1,115 @@
+//! Named scenes with parameters that work well together, as a starting point for new users.
+
+use egui::Color32;
+
+use crate::simulation::{Chirp, RadarConfig, RadarObject, Scene};
+
+/// A curated scene offered in the presets dropdown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Preset {
+    Automotive77GHz,
+    ShortRange24GHz,
+    TwoCloseTargets,
+    FastApproachingTarget,
+}
+
+impl Preset {
+    pub const ALL: [Self; 4] = [
+        Self::Automotive77GHz,
+        Self::ShortRange24GHz,
+        Self::TwoCloseTargets,
+        Self::FastApproachingTarget,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Automotive77GHz => "77 GHz automotive",
+            Self::ShortRange24GHz => "24 GHz short range",
+            Self::TwoCloseTargets => "Two close targets",
+            Self::FastApproachingTarget => "Fast approaching target",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Automotive77GHz => "A car, a truck and a pedestrian in front of a 1 GHz sweep",
+            Self::ShortRange24GHz => {
+                "The 250 MHz ISM band at 24 GHz with long chirps, for a few meters of range"
+            }
+            Self::TwoCloseTargets => "Two targets 0.4 m apart, a few range resolutions",
+            Self::FastApproachingTarget => {
+                "A target closing in at 50 m/s far out, next to a stationary one"
+            }
+        }
+    }
+
+    /// The radar parameters and objects of this preset. Approaching objects have negative
+    /// velocities, as the presets use the default velocity convention.
+    pub fn scene(self) -> Scene {
+        match self {
+            Self::Automotive77GHz => Scene {
+                config: RadarConfig {
+                    carrier_frequency: 77e9,
+                    bandwidth: 1e9,
+                    sampling_frequency: 30e6,
+                    sampling_duration: 40e-6,
+                    chirps: [50e-6, 40e-6, 60e-6].map(Chirp::new).to_vec(),
+                    ..RadarConfig::default()
+                },
+                objects: vec![
+                    RadarObject {
+                        extended: true,
+                        ..RadarObject::new(45.0, -15.0, Color32::GREEN, true)
+                    },
+                    RadarObject {
+                        rcs: 10.0,
+                        extended: true,
+                        length: 12.0,
+                        ..RadarObject::new(70.0, 5.0, Color32::BLUE, true)
+                    },
+                    RadarObject {
+                        rcs: 0.5,
+                        azimuth: -20.0,
+                        ..RadarObject::new(12.0, -1.5, Color32::RED, true)
+                    },
+                ],
+            },
+            Self::ShortRange24GHz => Scene {
+                config: RadarConfig {
+                    carrier_frequency: 24e9,
+                    bandwidth: 0.25e9,
+                    sampling_frequency: 10e6,
+                    sampling_duration: 100e-6,
+                    chirps: [200e-6, 150e-6, 250e-6].map(Chirp::new).to_vec(),
+                    ..RadarConfig::default()
+                },
+                objects: vec![
+                    RadarObject::new(3.0, 0.0, Color32::GREEN, true),
+                    RadarObject {
+                        rcs: 0.5,
+                        ..RadarObject::new(8.0, 1.0, Color32::BLUE, true)
+                    },
+                    RadarObject::new(15.0, -2.0, Color32::RED, true),
+                ],
+            },
+            Self::TwoCloseTargets => Scene {
+                config: RadarConfig::default(),
+                objects: vec![
+                    RadarObject::new(25.0, 0.0, Color32::GREEN, true),
+                    RadarObject::new(25.4, 0.0, Color32::BLUE, true),
+                ],
+            },
+            Self::FastApproachingTarget => Scene {
+                config: RadarConfig {
+                    bandwidth: 1e9,
+                    chirps: [40e-6, 50e-6, 60e-6].map(Chirp::new).to_vec(),
+                    ..RadarConfig::default()
+                },
+                objects: vec![
+                    RadarObject::new(90.0, -50.0, Color32::RED, true),
+                    RadarObject::new(30.0, 0.0, Color32::GREEN, true),
+                ],
+            },
+        }
+    }
+}
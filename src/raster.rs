@@ -0,0 +1,253 @@
+//! A minimal rasterizer with a built-in bitmap font, for exporting plots as PNG images.
+
+/// Width of a glyph of [`FONT`] in units, without the spacing.
+const GLYPH_WIDTH: usize = 5;
+/// Height of a glyph of [`FONT`] in units.
+const GLYPH_HEIGHT: usize = 7;
+/// Distance from one glyph to the next, in units.
+const GLYPH_ADVANCE: f64 = 6.0;
+
+/// Where a line of text sits relative to the point it is drawn at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Anchor {
+    Middle,
+    End,
+}
+
+/// An RGB image drawn in units of `scale` pixels, so it comes out sharper than its layout.
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    scale: f64,
+    pixels: Vec<egui::Color32>,
+}
+
+impl Canvas {
+    /// A canvas of `width` × `height` units filled with `background`.
+    pub fn new(width: f64, height: f64, scale: usize, background: egui::Color32) -> Self {
+        let width = (width * scale as f64).round() as usize;
+        let height = (height * scale as f64).round() as usize;
+        Self {
+            width,
+            height,
+            scale: scale as f64,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// Fill the pixels of the rectangle from `min` to `max` in units, clipped to the canvas.
+    fn fill(&mut self, min: [f64; 2], max: [f64; 2], color: egui::Color32) {
+        let to_pixel =
+            |value: f64, end: usize| ((value * self.scale).round().max(0.0) as usize).min(end);
+        let ([left, top], [right, bottom]) = (min, max);
+        let (x0, x1) = (to_pixel(left, self.width), to_pixel(right, self.width));
+        let (y0, y1) = (to_pixel(top, self.height), to_pixel(bottom, self.height));
+        for y in y0..y1 {
+            let row = y * self.width;
+            for pixel in self.pixels.iter_mut().take(row + x1).skip(row + x0) {
+                *pixel = color;
+            }
+        }
+    }
+
+    /// A straight line one unit wide from `from` to `to`.
+    pub fn line(&mut self, from: [f64; 2], to: [f64; 2], color: egui::Color32) {
+        let [x0, y0] = from;
+        let [x1, y1] = to;
+        // Stepping by half a pixel leaves no gaps
+        let steps = ((x1 - x0).hypot(y1 - y0) * self.scale * 2.0)
+            .ceil()
+            .max(1.0) as usize;
+        for i in 0..=steps {
+            let fraction = i as f64 / steps as f64;
+            let x = x0 + fraction * (x1 - x0);
+            let y = y0 + fraction * (y1 - y0);
+            self.fill([x - 0.5, y - 0.5], [x + 0.5, y + 0.5], color);
+        }
+    }
+
+    /// Lines through consecutive `points`.
+    pub fn polyline(&mut self, points: &[[f64; 2]], color: egui::Color32) {
+        for pair in points.windows(2) {
+            if let [from, to] = pair {
+                self.line(*from, *to, color);
+            }
+        }
+    }
+
+    /// A disc of `radius` units around `center`, filled with `fill` inside an `outline` one unit
+    /// wide.
+    pub fn circle(
+        &mut self,
+        center: [f64; 2],
+        radius: f64,
+        fill: egui::Color32,
+        outline: egui::Color32,
+    ) {
+        let [cx, cy] = center;
+        // Walk the pixels of the bounding box, each measured from its center
+        let step = 1.0 / self.scale;
+        let pixels = (2.0 * radius * self.scale).ceil() as usize + 1;
+        for row in 0..pixels {
+            for col in 0..pixels {
+                let x = cx - radius + col as f64 * step;
+                let y = cy - radius + row as f64 * step;
+                let distance = (x + step / 2.0 - cx).hypot(y + step / 2.0 - cy);
+                if distance <= radius {
+                    let color = if distance > radius - 1.0 {
+                        outline
+                    } else {
+                        fill
+                    };
+                    self.fill([x, y], [x + step, y + step], color);
+                }
+            }
+        }
+    }
+
+    /// `text` with its middle or end at `x` and centered vertically on `y`.
+    pub fn text(&mut self, text: &str, [x, y]: [f64; 2], anchor: Anchor, color: egui::Color32) {
+        let width = text_width(text);
+        let left = match anchor {
+            Anchor::Middle => x - width / 2.0,
+            Anchor::End => x - width,
+        };
+        let top = y - GLYPH_HEIGHT as f64 / 2.0;
+        for_each_dot(text, |u, v| {
+            self.fill([left + u, top + v], [left + u + 1.0, top + v + 1.0], color);
+        });
+    }
+
+    /// `text` turned to read upwards, centered on `center`.
+    pub fn vertical_text(&mut self, text: &str, center: [f64; 2], color: egui::Color32) {
+        let [x, y] = center;
+        let left = x - GLYPH_HEIGHT as f64 / 2.0;
+        let bottom = y + text_width(text) / 2.0;
+        // Along the text is up the canvas, down the glyph is right
+        for_each_dot(text, |u, v| {
+            self.fill(
+                [left + v, bottom - u - 1.0],
+                [left + v + 1.0, bottom - u],
+                color,
+            );
+        });
+    }
+
+    /// The canvas as a PNG file.
+    pub fn png(&self) -> Result<Vec<u8>, png::EncodingError> {
+        let mut file = Vec::new();
+        let mut encoder = png::Encoder::new(&mut file, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        let data: Vec<u8> = self
+            .pixels
+            .iter()
+            .flat_map(|color| [color.r(), color.g(), color.b()])
+            .collect();
+        writer.write_image_data(&data)?;
+        writer.finish()?;
+        Ok(file)
+    }
+}
+
+/// Width of `text` in units, without the spacing after the last glyph.
+fn text_width(text: &str) -> f64 {
+    let glyphs = text.chars().count() as f64;
+    (glyphs * GLYPH_ADVANCE - (GLYPH_ADVANCE - GLYPH_WIDTH as f64)).max(0.0)
+}
+
+/// Call `dot` with the position `(along, down)` in units of every dot of the glyphs of `text`.
+/// Characters without a glyph are left blank.
+fn for_each_dot(text: &str, mut dot: impl FnMut(f64, f64)) {
+    for (i, c) in text.chars().enumerate() {
+        let Some((_, rows)) = FONT.iter().find(|&&(glyph, _)| glyph == c) else {
+            continue;
+        };
+        for (v, row) in rows.iter().enumerate() {
+            for u in 0..GLYPH_WIDTH {
+                if row & (1 << (GLYPH_WIDTH - 1 - u)) != 0 {
+                    dot(i as f64 * GLYPH_ADVANCE + u as f64, v as f64);
+                }
+            }
+        }
+    }
+}
+
+/// Glyphs of 5 × 7 dots, one row per byte from the top, the leftmost dot in the highest bit.
+#[rustfmt::skip]
+const FONT: [(char, [u8; GLYPH_HEIGHT]); 73] = [
+    ('0', [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110]),
+    ('1', [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('2', [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111]),
+    ('3', [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110]),
+    ('4', [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010]),
+    ('5', [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110]),
+    ('6', [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110]),
+    ('7', [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000]),
+    ('8', [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110]),
+    ('9', [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100]),
+    ('A', [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001]),
+    ('B', [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110]),
+    ('C', [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110]),
+    ('D', [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100]),
+    ('E', [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111]),
+    ('F', [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000]),
+    ('G', [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111]),
+    ('H', [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001]),
+    ('I', [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('J', [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100]),
+    ('K', [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001]),
+    ('L', [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111]),
+    ('M', [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001]),
+    ('N', [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001]),
+    ('O', [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('P', [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000]),
+    ('Q', [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101]),
+    ('R', [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001]),
+    ('S', [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110]),
+    ('T', [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100]),
+    ('U', [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('V', [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100]),
+    ('W', [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010]),
+    ('X', [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001]),
+    ('Y', [0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100]),
+    ('Z', [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111]),
+    ('a', [0b00000, 0b00000, 0b01110, 0b00001, 0b01111, 0b10001, 0b01111]),
+    ('b', [0b10000, 0b10000, 0b10110, 0b11001, 0b10001, 0b10001, 0b11110]),
+    ('c', [0b00000, 0b00000, 0b01110, 0b10000, 0b10000, 0b10001, 0b01110]),
+    ('d', [0b00001, 0b00001, 0b01101, 0b10011, 0b10001, 0b10001, 0b01111]),
+    ('e', [0b00000, 0b00000, 0b01110, 0b10001, 0b11111, 0b10000, 0b01110]),
+    ('f', [0b00110, 0b01001, 0b01000, 0b11100, 0b01000, 0b01000, 0b01000]),
+    ('g', [0b00000, 0b01111, 0b10001, 0b10001, 0b01111, 0b00001, 0b01110]),
+    ('h', [0b10000, 0b10000, 0b10110, 0b11001, 0b10001, 0b10001, 0b10001]),
+    ('i', [0b00100, 0b00000, 0b01100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('j', [0b00010, 0b00000, 0b00110, 0b00010, 0b00010, 0b10010, 0b01100]),
+    ('k', [0b10000, 0b10000, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010]),
+    ('l', [0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('m', [0b00000, 0b00000, 0b11010, 0b10101, 0b10101, 0b10001, 0b10001]),
+    ('n', [0b00000, 0b00000, 0b10110, 0b11001, 0b10001, 0b10001, 0b10001]),
+    ('o', [0b00000, 0b00000, 0b01110, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('p', [0b00000, 0b00000, 0b11110, 0b10001, 0b11110, 0b10000, 0b10000]),
+    ('q', [0b00000, 0b00000, 0b01111, 0b10001, 0b01111, 0b00001, 0b00001]),
+    ('r', [0b00000, 0b00000, 0b10110, 0b11001, 0b10000, 0b10000, 0b10000]),
+    ('s', [0b00000, 0b00000, 0b01110, 0b10000, 0b01110, 0b00001, 0b11110]),
+    ('t', [0b01000, 0b01000, 0b11100, 0b01000, 0b01000, 0b01001, 0b00110]),
+    ('u', [0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b10011, 0b01101]),
+    ('v', [0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100]),
+    ('w', [0b00000, 0b00000, 0b10001, 0b10001, 0b10101, 0b10101, 0b01010]),
+    ('x', [0b00000, 0b00000, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001]),
+    ('y', [0b00000, 0b00000, 0b10001, 0b10001, 0b01111, 0b00001, 0b01110]),
+    ('z', [0b00000, 0b00000, 0b11111, 0b00010, 0b00100, 0b01000, 0b11111]),
+    ('.', [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100]),
+    (',', [0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b00100, 0b01000]),
+    ('-', [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000]),
+    ('+', [0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000]),
+    ('(', [0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00100, 0b00010]),
+    (')', [0b01000, 0b00100, 0b00010, 0b00010, 0b00010, 0b00100, 0b01000]),
+    ('/', [0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000]),
+    (':', [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000]),
+    ('%', [0b11000, 0b11001, 0b00010, 0b00100, 0b01000, 0b10011, 0b00011]),
+    ('=', [0b00000, 0b00000, 0b11111, 0b00000, 0b11111, 0b00000, 0b00000]),
+    ('_', [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b11111]),
+];
@@ -0,0 +1,1399 @@
+use std::ops::RangeInclusive;
+
+use itertools::izip;
+use rand::SeedableRng as _;
+
+use crate::dsp::{
+    DopplerModel, Normalization, SPEED_OF_LIGHT, VelocityConvention, Window, add_white_noise,
+    atmospheric_attenuation, cfar_detect, chain_group_delay, detrend, doppler_shift, fftspectrum,
+    idx_at_t, lowpass, merge_adjacent_detections, phase_noise, phase_noise_level, quantize,
+    refine_peak, sample_noisy_signal, sample_signal, welch_spectrum,
+};
+
+/// Shape of the frequency ramp within each chirp.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Waveform {
+    /// Rises over the whole chirp and jumps back to the start frequency.
+    #[default]
+    Sawtooth,
+    /// Rises over the first half of the chirp and falls back over the second half, so up- and
+    /// down-ramp beats separate range from Doppler.
+    Triangle,
+}
+
+impl Waveform {
+    pub const ALL: [Self; 2] = [Self::Sawtooth, Self::Triangle];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Sawtooth => "Sawtooth",
+            Self::Triangle => "Triangle",
+        }
+    }
+
+    /// Fraction of the bandwidth swept at the fraction `progress` (0 to 1) of a chirp.
+    pub fn ramp(self, progress: f64) -> f64 {
+        match self {
+            Self::Sawtooth => progress,
+            Self::Triangle => 1.0 - (2.0 * progress - 1.0).abs(),
+        }
+    }
+
+    /// Duration in seconds of a single rising or falling ramp of a chirp of `chirp` seconds, over
+    /// which the whole bandwidth is swept.
+    pub fn ramp_duration(self, chirp: f64) -> f64 {
+        match self {
+            Self::Sawtooth => chirp,
+            Self::Triangle => chirp / 2.0,
+        }
+    }
+}
+
+/// How the radar cross section of a target fluctuates from one look to the next, after Swerling.
+///
+/// Many scatterers of similar size add up to an exponentially distributed cross section, one
+/// dominant scatterer among smaller ones to a chi-square distribution with four degrees of
+/// freedom. Either changes once per scan for slowly turning targets or from chirp to chirp for
+/// fast ones.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum SwerlingModel {
+    /// A constant cross section.
+    #[default]
+    Zero,
+    /// Many similar scatterers, drawn once per scan.
+    One,
+    /// Many similar scatterers, drawn for every chirp.
+    Two,
+    /// One dominant scatterer, drawn once per scan.
+    Three,
+    /// One dominant scatterer, drawn for every chirp.
+    Four,
+}
+
+impl SwerlingModel {
+    pub const ALL: [Self; 5] = [Self::Zero, Self::One, Self::Two, Self::Three, Self::Four];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Zero => "Swerling 0 (steady)",
+            Self::One => "Swerling I (per scan)",
+            Self::Two => "Swerling II (per chirp)",
+            Self::Three => "Swerling III (per scan)",
+            Self::Four => "Swerling IV (per chirp)",
+        }
+    }
+
+    /// Whether every chirp draws its own cross section instead of sharing one per scan.
+    pub fn per_chirp(self) -> bool {
+        matches!(self, Self::Two | Self::Four)
+    }
+
+    /// A cross section in square meters drawn around the mean `rcs`.
+    ///
+    /// The chi-square distribution with `2k` degrees of freedom and mean `rcs` is the gamma
+    /// distribution of shape `k` and scale `rcs/k`, with `k = 1` for many similar scatterers and
+    /// `k = 2` for a dominant one.
+    pub fn sample_rcs(self, rcs: f64, rng: &mut impl rand::Rng) -> f64 {
+        let shape = match self {
+            Self::Zero => return rcs,
+            Self::One | Self::Two => 1.0,
+            Self::Three | Self::Four => 2.0,
+        };
+        rand_distr::Gamma::new(shape, rcs / shape).map_or(rcs, |gamma| rng.sample(gamma))
+    }
+}
+
+/// One chirp of a [`RadarConfig`]. Scenes saved while the chirps were only their durations load
+/// as rising chirps in the global band.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(from = "SavedChirp")]
+pub struct Chirp {
+    /// Duration in seconds.
+    pub duration: f64,
+    /// Ramp down from the top of the band instead of up.
+    pub falling: bool,
+    /// Swept bandwidth in Hz, `None` for the `bandwidth` of the config.
+    pub bandwidth: Option<f64>,
+    /// Frequency in Hz at the bottom of the band, `None` for the `carrier_frequency` of the config.
+    pub start_freq: Option<f64>,
+}
+
+impl Chirp {
+    /// A rising chirp of `duration` seconds in the global band.
+    pub fn new(duration: f64) -> Self {
+        Self {
+            duration,
+            falling: false,
+            bandwidth: None,
+            start_freq: None,
+        }
+    }
+}
+
+/// A [`Chirp`] as saved, in full or only as its duration.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum SavedChirp {
+    Duration(f64),
+    Chirp {
+        duration: f64,
+        #[serde(default)]
+        falling: bool,
+        #[serde(default)]
+        bandwidth: Option<f64>,
+        #[serde(default)]
+        start_freq: Option<f64>,
+    },
+}
+
+impl From<SavedChirp> for Chirp {
+    fn from(saved: SavedChirp) -> Self {
+        match saved {
+            SavedChirp::Duration(duration) => Self::new(duration),
+            SavedChirp::Chirp {
+                duration,
+                falling,
+                bandwidth,
+                start_freq,
+            } => Self {
+                duration,
+                falling,
+                bandwidth,
+                start_freq,
+            },
+        }
+    }
+}
+
+// Bounds of the parameters a broken configuration is clamped into, also those of their sliders
+pub const SWEEP_NONLINEARITY_RANGE: RangeInclusive<f64> = 0.0..=0.02;
+pub const SAMPLING_DURATION_RANGE: RangeInclusive<f64> = 1e-6..=1e-3;
+pub const RX_ELEMENTS_RANGE: RangeInclusive<usize> = 1..=64;
+pub const ELEMENT_SPACING_RANGE: RangeInclusive<f64> = 0.1..=2.0;
+
+/// Duration in seconds of a chirp added in the UI, and of one whose duration is broken.
+pub const NEW_CHIRP_DURATION: f64 = 40e-6;
+
+/// `value` if it is positive and finite, otherwise `fallback`.
+fn positive_or(value: f64, fallback: f64) -> f64 {
+    if value > 0.0 && value.is_finite() {
+        value
+    } else {
+        fallback
+    }
+}
+
+/// The radar parameters the simulation runs with.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct RadarConfig {
+    pub carrier_frequency: f64,
+    pub bandwidth: f64,
+    pub sampling_frequency: f64,
+    pub sampling_duration: f64,
+    /// Number of receive antennas in the uniform linear array.
+    pub rx_elements: usize,
+    /// Spacing of the receive antennas, in wavelengths.
+    pub element_spacing: f64,
+    /// The chirps of the sawtooth, in the order they are transmitted.
+    pub chirps: Vec<Chirp>,
+    /// Direction of motion of objects with a positive velocity.
+    pub velocity_convention: VelocityConvention,
+    /// Formula of the Doppler shift of the echoes.
+    pub doppler_model: DopplerModel,
+    /// Largest deviation of the sweep from a linear ramp, as a fraction of the bandwidth.
+    pub sweep_nonlinearity: f64,
+    /// Shape of the frequency ramp within each chirp.
+    pub waveform: Waveform,
+}
+
+impl Default for RadarConfig {
+    fn default() -> Self {
+        Self {
+            carrier_frequency: 77E9,
+            bandwidth: 1.6E9,
+            sampling_frequency: 50E6,
+            sampling_duration: 40E-6,
+            rx_elements: 4,
+            element_spacing: 0.5,
+            chirps: [40e-6, 20e-6, 60e-6].map(Chirp::new).to_vec(),
+            velocity_convention: VelocityConvention::default(),
+            doppler_model: DopplerModel::default(),
+            sweep_nonlinearity: 0.0,
+            waveform: Waveform::default(),
+        }
+    }
+}
+
+impl RadarConfig {
+    /// This configuration with its broken parameters replaced, so a broken scene cannot fill the
+    /// results with NaNs or grow the work without bound.
+    ///
+    /// The parameters the simulation divides by fall back to their defaults unless they are
+    /// positive and finite. The array and the sweep nonlinearity are clamped into their bounds.
+    pub fn sanitized(self) -> Self {
+        let defaults = Self::default();
+        let own_band = |value: Option<f64>| value.filter(|&value| positive_or(value, 0.0) > 0.0);
+        Self {
+            carrier_frequency: positive_or(self.carrier_frequency, defaults.carrier_frequency),
+            bandwidth: positive_or(self.bandwidth, defaults.bandwidth),
+            sampling_frequency: positive_or(self.sampling_frequency, defaults.sampling_frequency),
+            sampling_duration: positive_or(self.sampling_duration, defaults.sampling_duration),
+            rx_elements: self
+                .rx_elements
+                .clamp(*RX_ELEMENTS_RANGE.start(), *RX_ELEMENTS_RANGE.end()),
+            element_spacing: positive_or(self.element_spacing, defaults.element_spacing)
+                .clamp(*ELEMENT_SPACING_RANGE.start(), *ELEMENT_SPACING_RANGE.end()),
+            chirps: self
+                .chirps
+                .into_iter()
+                .map(|chirp| Chirp {
+                    duration: positive_or(chirp.duration, NEW_CHIRP_DURATION),
+                    falling: chirp.falling,
+                    bandwidth: own_band(chirp.bandwidth),
+                    start_freq: own_band(chirp.start_freq),
+                })
+                .collect(),
+            velocity_convention: self.velocity_convention,
+            doppler_model: self.doppler_model,
+            sweep_nonlinearity: if self.sweep_nonlinearity.is_finite() {
+                self.sweep_nonlinearity.clamp(
+                    *SWEEP_NONLINEARITY_RANGE.start(),
+                    *SWEEP_NONLINEARITY_RANGE.end(),
+                )
+            } else {
+                defaults.sweep_nonlinearity
+            },
+            waveform: self.waveform,
+        }
+    }
+
+    /// Whether the chirp at index `chirp` ramps down.
+    pub fn is_falling(&self, chirp: usize) -> bool {
+        self.chirps.get(chirp).is_some_and(|chirp| chirp.falling)
+    }
+
+    /// Bandwidth in Hz the chirp at index `chirp` sweeps, the global one unless it has its own.
+    pub fn chirp_bandwidth(&self, chirp: usize) -> f64 {
+        self.band(chirp).1
+    }
+
+    /// Frequency in Hz at the middle of the band of the chirp at index `chirp`.
+    pub fn chirp_center_frequency(&self, chirp: usize) -> f64 {
+        let (start, bandwidth) = self.band(chirp);
+        start + bandwidth / 2.0
+    }
+
+    /// Start frequency and bandwidth of the chirp at index `chirp`.
+    fn band(&self, chirp: usize) -> (f64, f64) {
+        let chirp = self.chirps.get(chirp);
+        (
+            chirp
+                .and_then(|chirp| chirp.start_freq)
+                .unwrap_or(self.carrier_frequency),
+            chirp
+                .and_then(|chirp| chirp.bandwidth)
+                .unwrap_or(self.bandwidth),
+        )
+    }
+
+    /// Transmitted frequency at the fraction `progress` (0 to 1) of a chirp in the global band.
+    ///
+    /// A real VCO bows away from the linear ramp. This is modeled by the quadratic
+    /// `4·progress·(1 - progress)`, which is zero at both ends of the chirp and reaches
+    /// `sweep_nonlinearity` times the bandwidth halfway through. The slope of the ramp then
+    /// changes during the chirp, so the beat of a target drifts within the sampling window by an
+    /// amount proportional to its range.
+    pub fn sweep_frequency(&self, progress: f64) -> f64 {
+        self.band_frequency(self.carrier_frequency, self.bandwidth, progress)
+    }
+
+    /// Like [`Self::sweep_frequency`], in the band of the chirp at index `chirp`.
+    pub fn chirp_frequency(&self, chirp: usize, progress: f64) -> f64 {
+        let (start, bandwidth) = self.band(chirp);
+        self.band_frequency(start, bandwidth, progress)
+    }
+
+    /// Frequency at the fraction `progress` of a chirp sweeping `bandwidth` Hz up from `start`.
+    fn band_frequency(&self, start: f64, bandwidth: f64, progress: f64) -> f64 {
+        let bow = 4.0 * self.sweep_nonlinearity * progress * (1.0 - progress);
+        start + bandwidth * (progress + bow)
+    }
+}
+
+/// Fraction of the bandwidth swept at every time of `t_`, for the chirps of `config` repeated one
+/// after another with the ramp shape of its waveform.
+pub fn saw(t_: &[f64], config: &RadarConfig) -> Vec<f64> {
+    t_.iter().map(|&t| chirp_ramp(t, config).1).collect()
+}
+
+/// Index of the chirp of `config` that is transmitted at time `t`.
+pub fn chirp_index(t: f64, config: &RadarConfig) -> usize {
+    chirp_ramp(t, config).0
+}
+
+/// Transmitted frequency at every time of `t_`, with every chirp sweeping its own band.
+pub fn sweep(t_: &[f64], config: &RadarConfig) -> Vec<f64> {
+    t_.iter()
+        .map(|&t| {
+            let (chirp, ramp) = chirp_ramp(t, config);
+            config.chirp_frequency(chirp, ramp)
+        })
+        .collect()
+}
+
+/// Index of the chirp at time `t` and the fraction of its bandwidth swept by then.
+fn chirp_ramp(t: f64, config: &RadarConfig) -> (usize, f64) {
+    // period of the sawtooth
+    let period = config.chirps.iter().map(|chirp| chirp.duration);
+    // Find which Tc interval we're in, wrapping around if needed
+    let total_duration: f64 = period.clone().sum();
+    let t_wrapped = t % total_duration;
+    let mut total_period = 0.0;
+    let mut current_period = config
+        .chirps
+        .first()
+        .map_or(total_duration, |chirp| chirp.duration);
+    let mut current_chirp = 0;
+    for (i, p) in period.enumerate() {
+        if t_wrapped < total_period + p {
+            current_period = p;
+            current_chirp = i;
+            break;
+        }
+        total_period += p;
+    }
+    // normalized time within current period
+    let t_mod = t_wrapped - total_period;
+    // ramp value from 0.0 to 1.0, or back down for falling chirps
+    let ramp = config.waveform.ramp(t_mod / current_period);
+    if config.is_falling(current_chirp) {
+        (current_chirp, 1.0 - ramp)
+    } else {
+        (current_chirp, ramp)
+    }
+}
+
+/// Beat frequency at every time of `t`, where the radar transmits `f`, of a target at `range`
+/// meters moving at `velocity` m/s.
+pub fn beat_frequencies(
+    t: &[f64],
+    f: &[f64],
+    range: f64,
+    velocity: f64,
+    config: &RadarConfig,
+) -> Vec<f64> {
+    beat_frequencies_along(
+        t,
+        f,
+        &vec![range; t.len()],
+        &vec![velocity; t.len()],
+        config,
+    )
+}
+
+/// Like [`beat_frequencies`], for a target at `ranges[i]` meters moving at `velocities[i]` m/s at
+/// time `t[i]`.
+pub fn beat_frequencies_along(
+    t: &[f64],
+    f: &[f64],
+    ranges: &[f64],
+    velocities: &[f64],
+    config: &RadarConfig,
+) -> Vec<f64> {
+    // Time shift due to range
+    let time_at_range = &t
+        .iter()
+        .zip(ranges)
+        .map(|(ti, range)| ti - 2.0 * range / SPEED_OF_LIGHT)
+        .collect::<Vec<f64>>();
+    let range_frequencies = sweep(time_at_range, config);
+
+    // Calculate beat frequency at each time sample. The delayed echo lags behind a rising ramp and
+    // ahead of a falling one, so the range shift changes sign with the slope.
+    izip!(&range_frequencies, f, velocities)
+        .map(|(&range_frequency, &fi, &velocity)| {
+            let range_shift = range_frequency - fi;
+            let doppler = doppler_shift(
+                fi,
+                velocity,
+                config.velocity_convention,
+                config.doppler_model,
+            );
+            doppler + range_shift
+        })
+        .collect()
+}
+
+/// A target in the scene, either a point or two scatterers `length` apart.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct RadarObject {
+    pub range: f64,
+    pub velocity: f64,
+    pub color: egui::Color32,
+    pub enabled: bool,
+    /// Reflect at the front and the rear of the object instead of at its center.
+    pub extended: bool,
+    /// Distance between the front and the rear scatterer, in meters.
+    pub length: f64,
+    /// Radar cross section, in square meters.
+    pub rcs: f64,
+    /// Angle off boresight in degrees, positive towards the last element of the array.
+    pub azimuth: f64,
+    /// Amplitude in meters of a sinusoidal vibration of the range, like of a propeller, which
+    /// adds micro-Doppler sidebands around the beat. 0 for a rigid target.
+    pub micro_amp: f64,
+    /// Frequency of the vibration in Hz.
+    pub micro_freq: f64,
+    /// How the radar cross section fluctuates around `rcs`.
+    pub swerling: SwerlingModel,
+    /// Beat frequency of this object at every simulation time step.
+    #[serde(skip)]
+    pub beat_freqs: Vec<f64>,
+    /// Beat frequencies of the front and rear scatterer, only computed for extended objects.
+    #[serde(skip)]
+    pub scatterer_beat_freqs: Vec<Vec<f64>>,
+}
+
+impl Default for RadarObject {
+    fn default() -> Self {
+        Self::new(0.0, 0.0, egui::Color32::WHITE, true)
+    }
+}
+
+impl RadarObject {
+    pub fn new(range: f64, velocity: f64, color: egui::Color32, enabled: bool) -> Self {
+        Self {
+            range,
+            velocity,
+            color,
+            enabled,
+            extended: false,
+            length: 4.5,
+            rcs: 1.0,
+            azimuth: 0.0,
+            micro_amp: 0.0,
+            micro_freq: 1e3,
+            swerling: SwerlingModel::default(),
+            beat_freqs: vec![],
+            scatterer_beat_freqs: vec![],
+        }
+    }
+
+    /// Fill in `beat_freqs` and, for an extended object, `scatterer_beat_freqs`, for the radar
+    /// transmitting `f` at the times `t`. The receiver delays the echo as if the object was
+    /// `range_bias` meters further away.
+    pub fn compute_beats(&mut self, t: &[f64], f: &[f64], range_bias: f64, config: &RadarConfig) {
+        let beat_freqs_at = |range: f64| self.reflection_beats(t, f, range + range_bias, config);
+        self.beat_freqs = beat_freqs_at(self.range);
+        self.scatterer_beat_freqs = if self.extended {
+            self.scatterer_ranges().map(beat_freqs_at).to_vec()
+        } else {
+            vec![]
+        };
+    }
+
+    /// Beat frequency at every time of `t`, where the radar transmits `f`, of the reflection of
+    /// this object at `range`, as it vibrates around that range.
+    pub fn reflection_beats(
+        &self,
+        t: &[f64],
+        f: &[f64],
+        range: f64,
+        config: &RadarConfig,
+    ) -> Vec<f64> {
+        let omega = 2.0 * std::f64::consts::PI * self.micro_freq;
+        let ranges: Vec<f64> = t
+            .iter()
+            .map(|&ti| range + self.micro_amp * (omega * ti).sin())
+            .collect();
+        // The vibration recedes while its range grows
+        let velocities: Vec<f64> = t
+            .iter()
+            .map(|&ti| {
+                let receding = self.micro_amp * omega * (omega * ti).cos();
+                self.velocity + config.velocity_convention.receding(receding)
+            })
+            .collect();
+        beat_frequencies_along(t, f, &ranges, &velocities, config)
+    }
+
+    /// Position in meters across and along the boresight of the radar at the origin.
+    pub fn position(&self) -> [f64; 2] {
+        let (sin, cos) = self.azimuth.to_radians().sin_cos();
+        [self.range * sin, self.range * cos]
+    }
+
+    /// Ranges of the front and the rear scatterer of an extended object.
+    pub fn scatterer_ranges(&self) -> [f64; 2] {
+        [
+            self.range - self.length / 2.0,
+            self.range + self.length / 2.0,
+        ]
+    }
+
+    /// Ranges of every reflection of this object, in the order of
+    /// [`Self::reflection_beat_freqs`].
+    pub fn reflection_ranges(&self) -> Vec<f64> {
+        if self.extended {
+            self.scatterer_ranges().to_vec()
+        } else {
+            vec![self.range]
+        }
+    }
+
+    /// Beat frequencies of every reflection of this object at time step `idx`.
+    pub fn reflection_beat_freqs(&self, idx: usize) -> impl Iterator<Item = f64> + '_ {
+        let beat_freqs = if self.extended {
+            self.scatterer_beat_freqs.as_slice()
+        } else {
+            std::slice::from_ref(&self.beat_freqs)
+        };
+        beat_freqs.iter().filter_map(move |bf| bf.get(idx).copied())
+    }
+
+    /// Ghost of this object from multipath over a flat ground, for a radar `height` meters above
+    /// it: a copy at the [`multipath_range`] that moves as fast as that range changes and whose
+    /// echo the ground scales by `reflectivity` in amplitude. Its beats are not computed yet.
+    pub fn ground_bounce(&self, height: f64, reflectivity: f64) -> Self {
+        let bounced = self.range.hypot(2.0 * height);
+        // The direct path changes with the velocity, the bounced one by range / bounced of it
+        let velocity = if bounced > 0.0 {
+            self.velocity * (1.0 + self.range / bounced) / 2.0
+        } else {
+            self.velocity
+        };
+        Self {
+            range: multipath_range(self.range, height),
+            velocity,
+            rcs: self.rcs * reflectivity.powi(2),
+            beat_freqs: vec![],
+            scatterer_beat_freqs: vec![],
+            ..self.clone()
+        }
+    }
+}
+
+/// Effective range of the echo of a target at `range` meters that travels one way directly and
+/// the other way bounced off a flat ground, `height` meters below the radar and the target.
+///
+/// The bounced path is as long as the direct path to the mirror image of the target, `2 height`
+/// below it, so the echo arrives as if from halfway between the two.
+pub fn multipath_range(range: f64, height: f64) -> f64 {
+    (range + range.hypot(2.0 * height)) / 2.0
+}
+
+/// Range and velocity difference below which two objects count as coincident.
+const COINCIDENCE_TOLERANCE: f64 = 1e-3;
+
+/// Pairs `(first, duplicate)` of indices of enabled objects at the same range and velocity.
+///
+/// Their echoes are identical, so they add up to a single peak of double height, which is
+/// usually an input mistake rather than intended.
+pub fn coincident_objects(objects: &[RadarObject]) -> Vec<(usize, usize)> {
+    let enabled = || objects.iter().enumerate().filter(|(_, obj)| obj.enabled);
+    enabled()
+        .flat_map(|(i, a)| {
+            enabled()
+                .filter(move |&(j, b)| {
+                    j > i
+                        && (a.range - b.range).abs() < COINCIDENCE_TOLERANCE
+                        && (a.velocity - b.velocity).abs() < COINCIDENCE_TOLERANCE
+                })
+                .map(move |(j, _)| (i, j))
+        })
+        .collect()
+}
+
+/// [`RadarObject::compute_beats`] of every object, on all cores with the `parallel` feature.
+pub fn compute_beats(
+    objects: &mut [RadarObject],
+    t: &[f64],
+    f: &[f64],
+    range_bias: f64,
+    config: &RadarConfig,
+) {
+    #[cfg(feature = "parallel")]
+    use rayon::iter::{IntoParallelRefMutIterator as _, ParallelIterator as _};
+    #[cfg(feature = "parallel")]
+    let objects = objects.par_iter_mut();
+    #[cfg(not(feature = "parallel"))]
+    let objects = objects.iter_mut();
+    objects.for_each(|obj| obj.compute_beats(t, f, range_bias, config));
+}
+
+/// A radar configuration together with the objects it observes, without anything computed.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Scene {
+    pub config: RadarConfig,
+    pub objects: Vec<RadarObject>,
+}
+
+/// Everything computed for one configuration and scene.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct SimulationResult {
+    pub config: RadarConfig,
+    pub settings: AnalysisSettings,
+    /// The targets, whose beats over `t` are in `beat_freqs` and `scatterer_beat_freqs`.
+    pub targets: Vec<RadarObject>,
+    /// Beat frequency of every target at every time step of `t`.
+    pub beat_freqs: Vec<Vec<f64>>,
+    /// Beat frequencies of the front and rear scatterer of every target, empty unless it is
+    /// extended.
+    pub scatterer_beat_freqs: Vec<Vec<Vec<f64>>>,
+    /// Simulation time steps.
+    pub t: Vec<f64>,
+    /// Transmitted frequency at every time step.
+    pub f: Vec<f64>,
+    /// Single-sided `(frequency, magnitude)` spectrum per chirp.
+    pub spectra: Vec<Vec<(f64, f64)>>,
+    /// Detected `(frequency, magnitude)` peaks per chirp.
+    pub detections: Vec<Vec<(f64, f64)>>,
+}
+
+/// What the signal that goes into the FFT is made of.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum SignalSource {
+    /// Constant-frequency beats, sampled ideally, without any impairment. Only the beats of
+    /// vibrating objects wobble.
+    Ideal,
+    /// The beats with every impairment that is switched on.
+    #[default]
+    Impaired,
+}
+
+impl SignalSource {
+    pub const ALL: [Self; 2] = [Self::Ideal, Self::Impaired];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ideal => "Ideal beat",
+            Self::Impaired => "With impairments",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Ideal => "Sum of the beat sines, ignoring all impairment settings",
+            Self::Impaired => {
+                "Phase noise, sweep nonlinearity, receiver noise, ADC quantization and the \
+                 sampling mode as currently set"
+            }
+        }
+    }
+}
+
+/// How the received power of an echo falls off with the range of its reflection.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum PathLoss {
+    /// Every echo is received with the same power, whatever its range.
+    #[default]
+    None,
+    /// Power falls off as 1/R², like the spreading on the way to the target alone.
+    OneWay,
+    /// Power falls off as 1/R⁴, the radar range equation for the way there and back.
+    TwoWay,
+}
+
+impl PathLoss {
+    pub const ALL: [Self; 3] = [Self::None, Self::OneWay, Self::TwoWay];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::OneWay => "1/R² (one-way)",
+            Self::TwoWay => "1/R⁴ (two-way)",
+        }
+    }
+
+    /// Exponent of the range in the denominator of the received power.
+    fn exponent(self) -> i32 {
+        match self {
+            Self::None => 0,
+            Self::OneWay => 2,
+            Self::TwoWay => 4,
+        }
+    }
+}
+
+/// Smallest range used for the path loss, so objects at the radar do not echo infinitely loud.
+const MIN_PATH_LOSS_RANGE: f64 = 0.1;
+
+/// Amplitude reflection coefficient of the ground that multipath ghosts bounce off.
+const GROUND_REFLECTIVITY: f64 = 0.5;
+
+/// How the ADC samples are taken from the continuous beat signal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Sampling {
+    /// Evaluate the beat signal exactly at the sample times.
+    #[default]
+    Ideal,
+    /// Pick every `DECIMATION`-th sample of a finer sampling, without any filter.
+    Decimated,
+    /// Low-pass filter the finer sampling to the Nyquist frequency before decimating it.
+    AntiAliased,
+}
+
+impl Sampling {
+    pub const ALL: [Self; 3] = [Self::Ideal, Self::Decimated, Self::AntiAliased];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ideal => "Ideal",
+            Self::Decimated => "Decimated",
+            Self::AntiAliased => "Anti-aliased",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Ideal => {
+                "Samples of the beat signal at the sample times, beats above Nyquist alias"
+            }
+            Self::Decimated => {
+                "Every sample of a finer sampling, without a filter the beats alias just the same"
+            }
+            Self::AntiAliased => {
+                "A low-pass filter before decimating suppresses the beats above Nyquist"
+            }
+        }
+    }
+}
+
+/// Factor by which the non-ideal sampling modes oversample before decimating.
+const DECIMATION: usize = 8;
+
+/// Coefficients of the anti-aliasing filter.
+const ANTI_ALIAS_TAPS: usize = 8 * DECIMATION + 1;
+
+/// Default cap of the FFT length, well above the default 2000 samples.
+const DEFAULT_MAX_FFT_SIZE: usize = 1 << 16;
+
+/// Offset from the carrier at which the phase noise is specified, in Hz, as on oscillator data
+/// sheets.
+pub const PHASE_NOISE_OFFSET: f64 = 1e6;
+
+/// Fewest samples of a sampling window, so that its sample spacing is defined.
+const MIN_SAMPLES: usize = 2;
+
+/// Mixed into the seed of the receiver noise, so it does not repeat the draws of the phase
+/// noise from the same seed.
+const RECEIVER_NOISE_STREAM: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Mixed into the seed of the fluctuating cross sections, for the same reason.
+const SWERLING_STREAM: u64 = 0xBF58_476D_1CE4_E5B9;
+
+/// `(frequency, magnitude)` peaks of the spectrum of every chirp.
+type ChirpPeaks = Vec<Vec<(f64, f64)>>;
+
+/// How the echoes of a [`RadarConfig`] are synthesized, impaired, sampled, transformed and
+/// searched for peaks: everything the simulation depends on beyond the radar and the objects.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct AnalysisSettings {
+    /// Synthesize coincident objects only once instead of adding up their echoes.
+    pub merge_coincident: bool,
+    /// Delay of the receiver hardware (cables, filters) in seconds.
+    pub rx_delay: f64,
+    /// End the sampling window with its chirp instead of sampling into the next one.
+    pub clamp_sampling_window: bool,
+    /// Where within each chirp the ADC window starts, as a fraction of the chirp duration.
+    pub sample_start_fraction: f64,
+    pub window: Window,
+    pub normalization: Normalization,
+    /// Largest number of samples transformed per chirp, to keep frames fast.
+    pub max_fft_size: usize,
+    /// Length of the FFT per chirp, zero padding or truncating the samples, 0 for the sample count.
+    pub fft_size: usize,
+    /// Average the spectra of overlapping segments of the samples (Welch's method) instead of
+    /// transforming all of them at once.
+    pub welch: bool,
+    /// Samples per segment of the averaged spectrum.
+    pub welch_segment: usize,
+    /// Fraction of its samples every segment shares with the next.
+    pub welch_overlap: f64,
+    /// Highest frequency of the spectra kept for display and peak search, in Hz.
+    pub max_display_frequency: f64,
+    pub signal_source: SignalSource,
+    /// Subtract the mean of the samples before the FFT.
+    pub remove_dc: bool,
+    /// Subtract their linear trend instead of only their mean.
+    pub remove_trend: bool,
+    pub sampling: Sampling,
+    /// Jitter the carrier phase.
+    pub phase_noise: bool,
+    /// Single-sideband phase noise of the carrier at [`PHASE_NOISE_OFFSET`], in dBc/Hz.
+    pub phase_noise_dbc_hz: f64,
+    /// Seed of the random numbers, so noisy simulations can be reproduced.
+    pub seed: u64,
+    /// How the echoes weaken with range.
+    pub path_loss: PathLoss,
+    /// Amplitude of the echo of a 1 m² target at 1 m, which scales all echoes.
+    pub echo_gain: f64,
+    /// Attenuation of the atmosphere in dB per km, on top of the path loss.
+    pub atmospheric_loss: f64,
+    /// Add a ghost of every object from its echo bouncing off a flat ground.
+    pub multipath: bool,
+    /// Height of the radar and the targets above the ground, in meters.
+    pub radar_height: f64,
+    /// Leave the receiver noise out of the impaired signal.
+    pub no_noise: bool,
+    /// Ratio of the signal power to the power of the white receiver noise, in dB.
+    pub snr_db: f64,
+    /// Resolution of the ADC in bits, 0 for an ideal ADC that does not quantize.
+    pub adc_bits: u32,
+    /// Largest amplitude the ADC converts without saturating.
+    pub adc_fullscale: f64,
+    /// Gain of the Q channel relative to the I channel, in dB.
+    pub iq_gain_imbalance_db: f64,
+    /// Deviation of the Q channel from quadrature, in degrees.
+    pub iq_phase_imbalance_deg: f64,
+    /// Linear magnitude a CFAR detection must reach to be reported.
+    pub peak_threshold: f64,
+    /// Bins skipped on either side of the bin under test by the CA-CFAR detector.
+    pub cfar_guard: usize,
+    /// Bins averaged on either side of the guard bins for the CFAR noise estimate.
+    pub cfar_train: usize,
+    /// Factor over the CFAR noise estimate a bin must exceed to be detected.
+    pub cfar_alpha: f64,
+}
+
+impl Default for AnalysisSettings {
+    fn default() -> Self {
+        Self {
+            merge_coincident: false,
+            rx_delay: 0.0,
+            clamp_sampling_window: false,
+            sample_start_fraction: 0.98,
+            window: Window::default(),
+            normalization: Normalization::default(),
+            max_fft_size: DEFAULT_MAX_FFT_SIZE,
+            fft_size: 0,
+            welch: false,
+            welch_segment: 256,
+            welch_overlap: 0.5,
+            max_display_frequency: RadarConfig::default().sampling_frequency / 2.0,
+            signal_source: SignalSource::default(),
+            remove_dc: false,
+            remove_trend: false,
+            sampling: Sampling::default(),
+            phase_noise: false,
+            phase_noise_dbc_hz: -90.0,
+            seed: 0,
+            path_loss: PathLoss::default(),
+            echo_gain: 1.0,
+            atmospheric_loss: 0.0,
+            multipath: false,
+            radar_height: 5.0,
+            no_noise: true,
+            snr_db: 20.0,
+            adc_bits: 0,
+            adc_fullscale: 4.0,
+            iq_gain_imbalance_db: 0.0,
+            iq_phase_imbalance_deg: 0.0,
+            peak_threshold: 0.0,
+            cfar_guard: 2,
+            cfar_train: 8,
+            cfar_alpha: 4.0,
+        }
+    }
+}
+
+impl AnalysisSettings {
+    /// Group delay in seconds of every stage of the receive chain.
+    pub fn chain_stage_delays(&self) -> Vec<f64> {
+        vec![self.rx_delay]
+    }
+
+    /// Range in meters that the group delay of the receive chain adds to every object.
+    pub fn range_bias(&self) -> f64 {
+        chain_group_delay(&self.chain_stage_delays()) * SPEED_OF_LIGHT / 2.0
+    }
+}
+
+/// Simulates `objects` under `config` and `settings` without any UI: the sweep, the beats of
+/// every object, the spectrum of every chirp and its detections, exactly as
+/// [`crate::App::update`] computes them.
+pub fn simulate(
+    config: &RadarConfig,
+    settings: &AnalysisSettings,
+    objects: &[RadarObject],
+) -> SimulationResult {
+    Simulation::new(config, settings, objects).result()
+}
+
+/// One run of the simulation pipeline, with everything the plots need beyond the
+/// [`SimulationResult`].
+#[derive(Clone, Debug, Default)]
+pub struct Simulation {
+    /// The radar parameters, [sanitized](RadarConfig::sanitized).
+    pub config: RadarConfig,
+    pub settings: AnalysisSettings,
+    /// The objects, with their beats over `t`.
+    pub objects: Vec<RadarObject>,
+    /// Pairs of enabled objects at the same range and velocity, the later one second.
+    pub coincident_objects: Vec<(usize, usize)>,
+    /// Ground-bounce ghost of every synthesized object after the index of the object, empty
+    /// without multipath.
+    pub ghosts: Vec<(usize, RadarObject)>,
+    /// Simulation time steps.
+    pub t: Vec<f64>,
+    /// Transmitted frequency at every time step.
+    pub f: Vec<f64>,
+    /// Length of the FFT actually run per chirp.
+    pub fft_sizes: Vec<usize>,
+    /// Single-sided `(frequency, magnitude)` spectrum per chirp, up to the highest displayed
+    /// frequency.
+    pub ffts: Vec<Vec<(f64, f64)>>,
+    /// Detected `(frequency, magnitude)` peaks per chirp, at the frequencies of their bins.
+    pub fft_peaks: ChirpPeaks,
+    /// `fft_peaks` with their frequencies and magnitudes interpolated between the bins.
+    pub interpolated_peaks: ChirpPeaks,
+}
+
+impl Simulation {
+    /// Runs the pipeline for `objects` under `config` and `settings`.
+    pub fn new(config: &RadarConfig, settings: &AnalysisSettings, objects: &[RadarObject]) -> Self {
+        let mut simulation = Self {
+            config: config.clone().sanitized(),
+            settings: settings.clone(),
+            objects: objects.to_vec(),
+            ..Self::default()
+        };
+        simulation.run();
+        simulation
+    }
+
+    /// The serializable part of this run.
+    pub fn result(&self) -> SimulationResult {
+        SimulationResult {
+            config: self.config.clone(),
+            settings: self.settings.clone(),
+            targets: self.objects.clone(),
+            beat_freqs: self
+                .objects
+                .iter()
+                .map(|obj| obj.beat_freqs.clone())
+                .collect(),
+            scatterer_beat_freqs: self
+                .objects
+                .iter()
+                .map(|obj| obj.scatterer_beat_freqs.clone())
+                .collect(),
+            t: self.t.clone(),
+            f: self.f.clone(),
+            spectra: self.ffts.clone(),
+            detections: self.fft_peaks.clone(),
+        }
+    }
+
+    /// The sweep, the beats of every object and its ghost, the spectrum of every chirp and its
+    /// peaks.
+    fn run(&mut self) {
+        let samples = 1000;
+        let duration: f64 = self.durations().sum::<f64>() * 3.0;
+        self.t = (0..samples)
+            .map(|i| i as f64 * duration / samples as f64)
+            .collect();
+        // Calculate frequencies across the time vector
+        self.f = sweep(&self.t, &self.config);
+
+        let range_bias = self.settings.range_bias();
+        compute_beats(
+            &mut self.objects,
+            &self.t,
+            &self.f,
+            range_bias,
+            &self.config,
+        );
+        self.coincident_objects = coincident_objects(&self.objects);
+        self.ghosts = if self.settings.multipath {
+            self.direct_objects()
+                .map(|(i, obj)| {
+                    let ghost = obj.ground_bounce(self.settings.radar_height, GROUND_REFLECTIVITY);
+                    (i, ghost)
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+        for (_, ghost) in &mut self.ghosts {
+            ghost.compute_beats(&self.t, &self.f, range_bias, &self.config);
+        }
+
+        // Create FFT spectra for multiple different start times
+        let start_times = self.start_times();
+        self.fft_sizes = self
+            .durations()
+            .map(|chirp| self.fft_length(chirp))
+            .collect();
+        self.ffts = self.compute_spectra(&start_times);
+        (self.fft_peaks, self.interpolated_peaks) = self.find_peaks();
+    }
+
+    /// Duration in seconds of every chirp, in chirp order.
+    fn durations(&self) -> impl Iterator<Item = f64> + '_ {
+        self.config.chirps.iter().map(|chirp| chirp.duration)
+    }
+
+    /// The `(frequency, magnitude)` peaks of every spectrum, once at the frequencies of their bins
+    /// and once interpolated between the bins.
+    fn find_peaks(&self) -> (ChirpPeaks, ChirpPeaks) {
+        // Find peaks in each FFT with CA-CFAR and return their actual frequencies (in Hz)
+        let peak_indices: Vec<Vec<usize>> = self
+            .ffts
+            .iter()
+            .map(|fft| {
+                let mags: Vec<f64> = fft.iter().map(|&(_, mag)| mag).collect();
+                let detections: Vec<usize> = cfar_detect(
+                    &mags,
+                    self.settings.cfar_guard,
+                    self.settings.cfar_train,
+                    self.settings.cfar_alpha,
+                )
+                .into_iter()
+                .filter(|&idx| {
+                    mags.get(idx)
+                        .is_some_and(|&mag| mag >= self.settings.peak_threshold)
+                })
+                .collect();
+                merge_adjacent_detections(&mags, &detections)
+            })
+            .collect();
+        let peaks = self
+            .ffts
+            .iter()
+            .zip(&peak_indices)
+            .map(|(fft, indices)| {
+                indices
+                    .iter()
+                    .filter_map(|&idx| fft.get(idx).copied())
+                    .collect::<Vec<(f64, f64)>>()
+            })
+            .collect();
+        let interpolated = self
+            .ffts
+            .iter()
+            .zip(&peak_indices)
+            .zip(&self.fft_sizes)
+            .map(|((fft, indices), &size)| {
+                let mags: Vec<f64> = fft.iter().map(|&(_, mag)| mag).collect();
+                let bin_width = self.config.sampling_frequency / size as f64;
+                indices
+                    .iter()
+                    .map(|&idx| {
+                        let (bin, mag) = refine_peak(&mags, idx);
+                        (bin * bin_width, mag)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        (peaks, interpolated)
+    }
+
+    /// Windowed single-sided spectrum of every chirp, cropped to the display range.
+    fn compute_spectra(&self, start_times: &[f64]) -> Vec<Vec<(f64, f64)>> {
+        start_times
+            .iter()
+            .zip(self.durations())
+            .zip(&self.fft_sizes)
+            .map(|((&start, chirp), &size)| {
+                let signal = self.build_signal(start, chirp);
+                let spectrum = if self.settings.welch {
+                    // Every segment is one FFT of `size` points
+                    welch_spectrum(
+                        &signal,
+                        self.config.sampling_frequency,
+                        size,
+                        self.settings.welch_overlap,
+                        self.settings.window,
+                        self.settings.normalization,
+                    )
+                } else {
+                    // Only the start of overly long windows is transformed, so the frame never
+                    // stalls
+                    fftspectrum(
+                        &signal,
+                        self.config.sampling_frequency,
+                        size,
+                        self.settings.window,
+                        self.settings.normalization,
+                    )
+                };
+                // The FFT runs over all samples, only the bins of interest are kept
+                spectrum
+                    .into_iter()
+                    .take_while(|&(freq, _)| freq <= self.settings.max_display_frequency)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Start time of the sampling window of every chirp.
+    pub fn start_times(&self) -> Vec<f64> {
+        // For each chirp, compute the start time as the sum of previous chirp durations plus the
+        // sample start fraction of the current chirp duration
+        let mut start_times = Vec::new();
+        // sum of chirps (except the last one)
+        for (i, chirp) in self.durations().enumerate() {
+            let sum = {
+                if i > 0 {
+                    self.durations().take(i).sum()
+                } else {
+                    0.0
+                }
+            };
+            let sum = sum + chirp * self.settings.sample_start_fraction;
+            start_times.push(sum);
+        }
+        start_times
+    }
+
+    /// Sample times of the sampling window starting at `start` on a chirp of `chirp` seconds.
+    pub fn sample_times(&self, start: f64, chirp: f64) -> Vec<f64> {
+        let sampling_duration = self.window_duration(chirp);
+        let n = self.sample_count(chirp);
+        (0..n)
+            .map(|i| start + i as f64 * sampling_duration / (n - 1) as f64)
+            .collect()
+    }
+
+    /// Samples of the beat signal in the sampling window starting at `start` on a chirp of
+    /// `chirp` seconds, before windowing.
+    ///
+    /// This is exactly what goes into the FFT; the signal source decides which impairments are
+    /// part of it, the DC removal applies to either.
+    pub fn build_signal(&self, start: f64, chirp: f64) -> Vec<f64> {
+        let t = self.sample_times(start, chirp);
+        // Collect the beats at the found index for all enabled objects
+        let reflections = self.reflections_at(idx_at_t(&self.t, start));
+        let mut signal = match self.settings.signal_source {
+            SignalSource::Ideal if self.vibrating() => {
+                self.chirped_beat_signal(&t, &vec![0.0; t.len()])
+            }
+            SignalSource::Ideal => sample_signal(&t, &reflections),
+            SignalSource::Impaired => {
+                let step = self.window_duration(chirp) / (t.len() as f64 - 1.0);
+                let mut signal = self.impaired_signal(&t, step, &reflections);
+                if !self.settings.no_noise {
+                    // Every chirp draws its own noise, the same on every frame
+                    let seed = self.settings.seed ^ start.to_bits() ^ RECEIVER_NOISE_STREAM;
+                    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+                    add_white_noise(&mut signal, self.settings.snr_db, &mut rng);
+                }
+                quantize(
+                    &mut signal,
+                    self.settings.adc_bits,
+                    self.settings.adc_fullscale,
+                );
+                signal
+            }
+        };
+        if self.settings.remove_dc {
+            detrend(&mut signal, self.settings.remove_trend);
+        }
+        signal
+    }
+
+    /// Beat signal at the sample times `t`, `step` seconds apart, with the enabled impairments:
+    /// carrier phase noise, the nonlinear sweep and the sampling mode.
+    fn impaired_signal(&self, t: &[f64], step: f64, reflections: &[(f64, f64)]) -> Vec<f64> {
+        let phase = if self.settings.phase_noise {
+            // The jittering carrier shifts the phase of every reflection alike. This ignores that
+            // the echo correlates with the delayed transmit phase, the worst case for far targets.
+            // Every chirp draws its own phase noise, the same on every frame.
+            let chirp = t
+                .first()
+                .map_or(0, |&start| chirp_index(start, &self.config));
+            let seed = self.settings.seed ^ ((chirp as u64) << 32);
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let level = phase_noise_level(
+                self.settings.phase_noise_dbc_hz,
+                PHASE_NOISE_OFFSET,
+                1.0 / step,
+            );
+            phase_noise(t.len(), level, &mut rng)
+        } else {
+            vec![0.0; t.len()]
+        };
+        let synthesize = |t: &[f64], phase: &[f64]| {
+            // Constant beats only hold on a linear ramp that does not turn within the window
+            if self.constant_beats() {
+                sample_noisy_signal(t, reflections, phase)
+            } else {
+                self.chirped_beat_signal(t, phase)
+            }
+        };
+        match self.settings.sampling {
+            Sampling::Ideal => synthesize(t, &phase),
+            Sampling::Decimated | Sampling::AntiAliased => {
+                // Every DECIMATION-th fine sample falls on a sample time
+                let fine_t: Vec<f64> = t
+                    .iter()
+                    .flat_map(|&t| {
+                        (0..DECIMATION).map(move |i| t + i as f64 * step / DECIMATION as f64)
+                    })
+                    .collect();
+                let fine_phase: Vec<f64> = phase
+                    .iter()
+                    .flat_map(|&phase| std::iter::repeat_n(phase, DECIMATION))
+                    .collect();
+                let mut fine = synthesize(&fine_t, &fine_phase);
+                if self.settings.sampling == Sampling::AntiAliased {
+                    fine = lowpass(&fine, 0.5 / DECIMATION as f64, ANTI_ALIAS_TAPS);
+                }
+                fine.into_iter().step_by(DECIMATION).collect()
+            }
+        }
+    }
+
+    /// Beat signal at the times `t` with the carrier `phase` noise, following the beat of every
+    /// reflection as it drifts with the nonlinear sweep, flips at the turn of a triangle or
+    /// wobbles with the vibration of its object.
+    pub fn chirped_beat_signal(&self, t: &[f64], phase: &[f64]) -> Vec<f64> {
+        let f = sweep(t, &self.config);
+        let range_bias = self.settings.range_bias();
+        let mut signal = vec![0.0; t.len()];
+        for (i, obj) in self.synthesized_objects() {
+            let rcs = self.look_rcs(i, obj, t.first().copied().unwrap_or(0.0));
+            for range in obj.reflection_ranges() {
+                let amplitude = self.echo_amplitude(range, rcs);
+                let beats = obj.reflection_beats(t, &f, range + range_bias, &self.config);
+                // Integrate the drifting beat to its phase, starting where a constant beat would be
+                let mut beat_phase = 0.0;
+                let mut previous: Option<(f64, f64)> = None;
+                for (sample, &t, &beat, &noise) in izip!(signal.iter_mut(), t, &beats, phase) {
+                    beat_phase += match previous {
+                        Some((prev_t, prev_beat)) => {
+                            std::f64::consts::PI * (beat + prev_beat) * (t - prev_t)
+                        }
+                        None => 2.0 * std::f64::consts::PI * beat * t,
+                    };
+                    previous = Some((t, beat));
+                    *sample += amplitude * (beat_phase + noise).sin();
+                }
+            }
+        }
+        signal
+    }
+
+    /// The enabled objects and their ghosts whose echoes are synthesized, with the index of the
+    /// object.
+    pub fn synthesized_objects(&self) -> impl Iterator<Item = (usize, &RadarObject)> {
+        let ghosts = self.ghosts.iter().map(|(i, ghost)| (*i, ghost));
+        self.direct_objects().chain(ghosts)
+    }
+
+    /// The enabled objects whose direct echoes are synthesized, with their index.
+    fn direct_objects(&self) -> impl Iterator<Item = (usize, &RadarObject)> {
+        // Merged objects only contribute through the first of the coincident objects
+        let merged = |i: usize| {
+            self.settings.merge_coincident
+                && self.coincident_objects.iter().any(|&(_, dup)| dup == i)
+        };
+        self.objects
+            .iter()
+            .enumerate()
+            .filter(move |&(i, obj)| obj.enabled && !merged(i))
+    }
+
+    /// `(beat frequency, amplitude)` of all reflections of the enabled objects at time step `idx`.
+    pub fn reflections_at(&self, idx: usize) -> Vec<(f64, f64)> {
+        let t = self.t.get(idx).copied().unwrap_or(0.0);
+        self.synthesized_objects()
+            .flat_map(|(i, obj)| {
+                let rcs = self.look_rcs(i, obj, t);
+                obj.reflection_beat_freqs(idx)
+                    .zip(obj.reflection_ranges())
+                    .map(move |(beat, range)| (beat, self.echo_amplitude(range, rcs)))
+            })
+            .collect()
+    }
+
+    /// Cross section of the object at index `i` in the look at time `t`, drawn from its Swerling
+    /// model. Every chirp of a scan shares one draw unless the model draws one per chirp; another
+    /// seed is another scan.
+    pub fn look_rcs(&self, i: usize, obj: &RadarObject, t: f64) -> f64 {
+        if obj.swerling == SwerlingModel::Zero {
+            return obj.rcs;
+        }
+        let chirp = if obj.swerling.per_chirp() {
+            chirp_index(t, &self.config)
+        } else {
+            0
+        };
+        let look = ((i as u64) << 32) | chirp as u64;
+        let mut rng =
+            rand::rngs::StdRng::seed_from_u64(self.settings.seed ^ SWERLING_STREAM ^ look);
+        obj.swerling.sample_rcs(obj.rcs, &mut rng)
+    }
+
+    /// Amplitude of the echo of a reflection at `range` meters off a target with radar cross
+    /// section `rcs`. The amplitude goes with the square root of the received power, which the
+    /// atmosphere attenuates further.
+    pub fn echo_amplitude(&self, range: f64, rcs: f64) -> f64 {
+        let loss = range
+            .max(MIN_PATH_LOSS_RANGE)
+            .powi(self.settings.path_loss.exponent());
+        self.settings.echo_gain
+            * (rcs / loss).sqrt()
+            * atmospheric_attenuation(self.settings.atmospheric_loss, range)
+    }
+
+    /// Whether every beat stays constant within a sampling window: on a linear sawtooth, with no
+    /// object vibrating.
+    fn constant_beats(&self) -> bool {
+        self.config.sweep_nonlinearity == 0.0
+            && self.config.waveform == Waveform::Sawtooth
+            && !self.vibrating()
+    }
+
+    /// Whether any synthesized object vibrates, so its beat wobbles with micro-Doppler.
+    fn vibrating(&self) -> bool {
+        self.synthesized_objects()
+            .any(|(_, obj)| obj.micro_amp > 0.0)
+    }
+
+    /// Time from the start of the sampling window to the end of a chirp of `chirp` seconds.
+    pub fn time_left_to_sample(&self, chirp: f64) -> f64 {
+        (1.0 - self.settings.sample_start_fraction) * chirp
+    }
+
+    /// Duration of the sampling window on a chirp of `chirp` seconds.
+    ///
+    /// Clamping ends the window with the chirp, but keeps at least the shortest sampling duration
+    /// of the slider so there are samples to transform.
+    pub fn window_duration(&self, chirp: f64) -> f64 {
+        if self.settings.clamp_sampling_window {
+            let shortest = *SAMPLING_DURATION_RANGE.start();
+            self.config
+                .sampling_duration
+                .min(self.time_left_to_sample(chirp).max(shortest))
+        } else {
+            self.config.sampling_duration
+        }
+    }
+
+    /// Number of samples in the sampling window on a chirp of `chirp` seconds, at least
+    /// [`MIN_SAMPLES`].
+    pub fn sample_count(&self, chirp: f64) -> usize {
+        ((self.window_duration(chirp) * self.config.sampling_frequency).round() as usize)
+            .max(MIN_SAMPLES)
+    }
+
+    /// Length of the FFT on a chirp of `chirp` seconds, the segment length when averaging, capped
+    /// at [`AnalysisSettings::max_fft_size`].
+    pub fn fft_length(&self, chirp: f64) -> usize {
+        let length = if self.settings.welch {
+            self.settings.welch_segment
+        } else if self.settings.fft_size == 0 {
+            self.sample_count(chirp)
+        } else {
+            self.settings.fft_size
+        };
+        length.min(self.settings.max_fft_size)
+    }
+}
@@ -0,0 +1,230 @@
+//! A minimal SVG writer, for exporting plots as vector graphics, which also rasterizes them as
+//! PNG images.
+
+use crate::raster::{Anchor, Canvas};
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 400.0;
+/// Space around the plot area for the tick and axis labels.
+const MARGIN: f64 = 60.0;
+/// Number of intervals between the ticks of each axis.
+const TICKS: usize = 5;
+/// Pixels per unit of the layout in the PNG image.
+const PNG_SCALE: usize = 2;
+// The named colors of the SVG, for the PNG image
+const BACKGROUND: egui::Color32 = egui::Color32::from_rgb(0x1b, 0x1b, 0x1b);
+const FRAME: egui::Color32 = egui::Color32::from_rgb(128, 128, 128);
+const TEXT: egui::Color32 = egui::Color32::from_rgb(211, 211, 211);
+
+/// Lines and points in data coordinates, drawn on linear axes that fit all of them.
+///
+/// The background is dark like the app, so the plot colors keep their contrast.
+pub struct SvgPlot {
+    x_label: String,
+    y_label: String,
+    lines: Vec<(egui::Color32, Vec<[f64; 2]>)>,
+    points: Vec<(egui::Color32, [f64; 2])>,
+}
+
+impl SvgPlot {
+    pub fn new(x_label: &str, y_label: &str) -> Self {
+        Self {
+            x_label: x_label.to_owned(),
+            y_label: y_label.to_owned(),
+            lines: vec![],
+            points: vec![],
+        }
+    }
+
+    pub fn line(&mut self, color: egui::Color32, points: Vec<[f64; 2]>) {
+        self.lines.push((color, points));
+    }
+
+    pub fn point(&mut self, color: egui::Color32, point: [f64; 2]) {
+        self.points.push((color, point));
+    }
+
+    /// Axes that fit all lines and points.
+    fn axes(&self) -> Axes {
+        let coordinates = || {
+            self.lines
+                .iter()
+                .flat_map(|(_, line)| line.iter())
+                .chain(self.points.iter().map(|(_, point)| point))
+        };
+        Axes {
+            x: bounds(coordinates().map(|&[x, _]| x)),
+            y: bounds(coordinates().map(|&[_, y]| y)),
+        }
+    }
+
+    /// The SVG document of the plot.
+    pub fn render(&self) -> String {
+        let axes = self.axes();
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{HEIGHT}\" \
+             font-family=\"sans-serif\" font-size=\"12\" fill=\"lightgray\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"#1b1b1b\"/>\n\
+             <rect x=\"{MARGIN}\" y=\"{MARGIN}\" width=\"{}\" height=\"{}\" fill=\"none\" \
+             stroke=\"gray\"/>\n",
+            WIDTH - 2.0 * MARGIN,
+            HEIGHT - 2.0 * MARGIN
+        );
+        for i in 0..=TICKS {
+            let [x, y] = axes.tick(i);
+            let [svg_x, svg_y] = axes.position([x, y]);
+            svg.push_str(&format!(
+                "<text x=\"{svg_x:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>\n\
+                 <text x=\"{:.1}\" y=\"{svg_y:.1}\" text-anchor=\"end\" \
+                 dominant-baseline=\"middle\">{}</text>\n",
+                HEIGHT - MARGIN + 16.0,
+                tick_label(x),
+                MARGIN - 6.0,
+                tick_label(y)
+            ));
+        }
+        svg.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>\n\
+             <text transform=\"translate(16 {:.1}) rotate(-90)\" text-anchor=\"middle\">{}</text>\n",
+            WIDTH / 2.0,
+            HEIGHT - 16.0,
+            escape(&self.x_label),
+            HEIGHT / 2.0,
+            escape(&self.y_label)
+        ));
+
+        for (color, line) in &self.lines {
+            let points: Vec<String> = line
+                .iter()
+                .map(|&point| {
+                    let [x, y] = axes.position(point);
+                    format!("{x:.2},{y:.2}")
+                })
+                .collect();
+            svg.push_str(&format!(
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\"/>\n",
+                points.join(" "),
+                hex(*color)
+            ));
+        }
+        for &(color, point) in &self.points {
+            let [x, y] = axes.position(point);
+            svg.push_str(&format!(
+                "<circle cx=\"{x:.2}\" cy=\"{y:.2}\" r=\"3\" fill=\"{}\" stroke=\"black\"/>\n",
+                hex(color)
+            ));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// The plot as a PNG image, rasterized from the same layout as [`Self::render`] with a
+    /// bitmap font.
+    pub fn render_png(&self) -> Result<Vec<u8>, png::EncodingError> {
+        let axes = self.axes();
+        let mut canvas = Canvas::new(WIDTH, HEIGHT, PNG_SCALE, BACKGROUND);
+        let (left, right) = (MARGIN, WIDTH - MARGIN);
+        let (top, bottom) = (MARGIN, HEIGHT - MARGIN);
+        canvas.polyline(
+            &[
+                [left, top],
+                [right, top],
+                [right, bottom],
+                [left, bottom],
+                [left, top],
+            ],
+            FRAME,
+        );
+        for i in 0..=TICKS {
+            let [x, y] = axes.tick(i);
+            let [image_x, image_y] = axes.position([x, y]);
+            canvas.text(
+                &tick_label(x),
+                [image_x, bottom + 12.0],
+                Anchor::Middle,
+                TEXT,
+            );
+            canvas.text(&tick_label(y), [left - 6.0, image_y], Anchor::End, TEXT);
+        }
+        canvas.text(
+            &self.x_label,
+            [WIDTH / 2.0, HEIGHT - 20.0],
+            Anchor::Middle,
+            TEXT,
+        );
+        canvas.vertical_text(&self.y_label, [12.0, HEIGHT / 2.0], TEXT);
+
+        for (color, line) in &self.lines {
+            let points: Vec<[f64; 2]> = line.iter().map(|&point| axes.position(point)).collect();
+            canvas.polyline(&points, *color);
+        }
+        for &(color, point) in &self.points {
+            canvas.circle(axes.position(point), 3.0, color, egui::Color32::BLACK);
+        }
+        canvas.png()
+    }
+}
+
+/// Data ranges of the axes of a plot.
+struct Axes {
+    x: (f64, f64),
+    y: (f64, f64),
+}
+
+impl Axes {
+    /// Position in the image of the data `point`, with y growing downwards.
+    fn position(&self, [x, y]: [f64; 2]) -> [f64; 2] {
+        let (x_min, x_max) = self.x;
+        let (y_min, y_max) = self.y;
+        [
+            MARGIN + (x - x_min) / (x_max - x_min) * (WIDTH - 2.0 * MARGIN),
+            HEIGHT - MARGIN - (y - y_min) / (y_max - y_min) * (HEIGHT - 2.0 * MARGIN),
+        ]
+    }
+
+    /// Values of the `i`-th tick of both axes, from the minimum at 0 to the maximum at [`TICKS`].
+    fn tick(&self, i: usize) -> [f64; 2] {
+        let fraction = i as f64 / TICKS as f64;
+        let (x_min, x_max) = self.x;
+        let (y_min, y_max) = self.y;
+        [
+            x_min + fraction * (x_max - x_min),
+            y_min + fraction * (y_max - y_min),
+        ]
+    }
+}
+
+/// Smallest and largest of the finite `values`, widened if they do not span a range.
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (min, max) = values
+        .filter(|value| value.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+            (min.min(value), max.max(value))
+        });
+    if min < max {
+        (min, max)
+    } else if min.is_finite() {
+        (min - 1.0, min + 1.0)
+    } else {
+        (0.0, 1.0)
+    }
+}
+
+fn tick_label(value: f64) -> String {
+    if value != 0.0 && (value.abs() >= 1e4 || value.abs() < 1e-2) {
+        format!("{value:.1e}")
+    } else {
+        format!("{value:.2}")
+    }
+}
+
+fn hex(color: egui::Color32) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,44 @@
+//! The ambiguity function peaks at zero delay and Doppler and has the right shape.
+
+use fmcw_radar_demo0::dsp::{Complex, ambiguity_function};
+
+const N: usize = 32;
+const DOPPLER_BINS: usize = 16;
+
+#[test]
+fn ambiguity_peaks_at_origin() {
+    let tone: Vec<Complex<f64>> = vec![Complex::new(1.0, 0.0); N];
+    let ambiguity = ambiguity_function(&tone, 1.0, DOPPLER_BINS);
+    assert_eq!(ambiguity.len(), DOPPLER_BINS, "one row per Doppler bin");
+    for row in &ambiguity {
+        assert_eq!(row.len(), 2 * N - 1, "one column per delay");
+    }
+    let origin = ambiguity
+        .get(DOPPLER_BINS / 2)
+        .and_then(|row| row.get(N - 1))
+        .copied();
+    assert!(
+        origin.is_some_and(|value| (value - 1.0).abs() < 1e-9),
+        "value at the origin is {origin:?}"
+    );
+    let max = ambiguity
+        .iter()
+        .flatten()
+        .fold(0.0_f64, |max, &v| max.max(v));
+    assert!(max <= 1.0 + 1e-9, "ambiguity exceeds 1: {max}");
+}
+
+#[test]
+fn constant_tone_has_no_response_at_doppler_bins() {
+    // At zero delay, a constant tone shifted by a whole Doppler bin sums to zero
+    let tone: Vec<Complex<f64>> = vec![Complex::new(1.0, 0.0); N];
+    let ambiguity = ambiguity_function(&tone, 1.0, DOPPLER_BINS);
+    let shifted = ambiguity
+        .get(DOPPLER_BINS / 2 + 1)
+        .and_then(|row| row.get(N - 1))
+        .copied();
+    assert!(
+        shifted.is_some_and(|value| value < 1e-9),
+        "value one Doppler bin off is {shifted:?}"
+    );
+}
@@ -0,0 +1,64 @@
+//! The atmosphere weakens echoes exponentially with range, on top of the path loss.
+
+use fmcw_radar_demo0::dsp::atmospheric_attenuation;
+use fmcw_radar_demo0::{AnalysisSettings, RadarConfig, RadarObject, simulate};
+
+#[test]
+fn attenuation_is_the_loss_over_the_way_there_and_back() {
+    assert_eq!(atmospheric_attenuation(0.0, 80.0), 1.0, "without loss");
+    assert_eq!(atmospheric_attenuation(20.0, 0.0), 1.0, "at the radar");
+    // 500 m there and 500 m back at 20 dB/km
+    let expected = 10.0_f64.powf(-1.0);
+    let attenuation = atmospheric_attenuation(20.0, 500.0);
+    assert!(
+        (attenuation - expected).abs() < 1e-12,
+        "attenuation {attenuation}, expected {expected}"
+    );
+}
+
+#[test]
+fn attenuation_falls_with_range() {
+    let attenuations: Vec<f64> = (0..=20)
+        .map(|i| atmospheric_attenuation(5.0, f64::from(i) * 50.0))
+        .collect();
+    assert!(
+        attenuations.windows(2).all(|pair| match pair {
+            [near, far] => far < near,
+            _ => false,
+        }),
+        "attenuations {attenuations:?}"
+    );
+}
+
+/// Largest magnitude in the spectrum of the first chirp with a single object at `range` through
+/// an atmosphere of `loss` dB/km.
+fn peak_at(range: f64, loss: f64) -> f64 {
+    let settings = AnalysisSettings {
+        atmospheric_loss: loss,
+        ..AnalysisSettings::default()
+    };
+    let object = RadarObject::new(range, 0.0, egui::Color32::WHITE, true);
+    simulate(&RadarConfig::default(), &settings, &[object])
+        .spectra
+        .first()
+        .map_or(0.0, |spectrum| {
+            spectrum.iter().map(|&(_, mag)| mag).fold(0.0, f64::max)
+        })
+}
+
+#[test]
+fn peak_magnitude_falls_monotonically_with_range() {
+    // 100 dB/km takes 4 dB per 20 m of range, well above the scalloping loss of the window
+    let peaks: Vec<f64> = [10.0, 30.0, 50.0, 70.0, 90.0]
+        .into_iter()
+        .map(|range| peak_at(range, 100.0))
+        .collect();
+    assert!(peaks.first().is_some_and(|&peak| peak > 0.0), "no echo");
+    assert!(
+        peaks.windows(2).all(|pair| match pair {
+            [near, far] => far < near,
+            _ => false,
+        }),
+        "peaks {peaks:?} from near to far"
+    );
+}
@@ -0,0 +1,71 @@
+//! The angle FFT across the receive array finds the azimuth of an echo.
+
+use fmcw_radar_demo0::RadarObject;
+use fmcw_radar_demo0::dsp::{Complex, angle_spectrum};
+
+const ELEMENTS: usize = 8;
+const SPACING: f64 = 0.5;
+const BINS: usize = 256;
+
+/// Values of one beat bin on every element for an echo from `azimuth` degrees.
+fn snapshot(azimuth: f64) -> Vec<Complex<f64>> {
+    let delay = SPACING * azimuth.to_radians().sin();
+    (0..ELEMENTS)
+        .map(|n| Complex::from_polar(1.0, 2.0 * std::f64::consts::PI * n as f64 * delay))
+        .collect()
+}
+
+fn estimate(azimuth: f64) -> Option<f64> {
+    angle_spectrum(&snapshot(azimuth), SPACING, BINS)
+        .into_iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(azimuth, _)| azimuth)
+}
+
+#[test]
+fn peak_is_at_the_azimuth_of_the_echo() {
+    for azimuth in [-40.0, -10.0, 0.0, 15.0, 30.0] {
+        let estimated = estimate(azimuth);
+        // One bin is 1/(BINS·SPACING) in sine, well under a degree near boresight
+        assert!(
+            estimated.is_some_and(|estimated| (estimated - azimuth).abs() < 1.0),
+            "{azimuth}° estimated at {estimated:?}°"
+        );
+    }
+}
+
+#[test]
+fn spectrum_is_ascending_and_peaks_at_one() {
+    let spectrum = angle_spectrum(&snapshot(20.0), SPACING, BINS);
+    assert!(
+        spectrum.windows(2).all(|pair| match pair {
+            [a, b] => a.0 < b.0,
+            _ => true,
+        }),
+        "azimuths in ascending order"
+    );
+    let peak = spectrum.iter().fold(0.0_f64, |max, &(_, mag)| max.max(mag));
+    assert!(peak <= 1.0 + 1e-12, "peak of {peak}");
+    assert!(peak > 0.9, "peak of {peak}");
+}
+
+#[test]
+fn single_element_sees_every_azimuth_alike() {
+    let spectrum = angle_spectrum(&[Complex::new(1.0, 0.0)], SPACING, BINS);
+    assert!(
+        spectrum.iter().all(|&(_, mag)| (mag - 1.0).abs() < 1e-12),
+        "a single element sees every azimuth alike"
+    );
+}
+
+#[test]
+fn position_follows_range_and_azimuth() {
+    let mut obj = RadarObject::new(10.0, 0.0, egui::Color32::WHITE, true);
+    obj.azimuth = 30.0;
+    let [x, y] = obj.position();
+    assert!((x - 5.0).abs() < 1e-12, "across {x} m");
+    assert!(
+        (y - 10.0 * 30f64.to_radians().cos()).abs() < 1e-12,
+        "along {y} m"
+    );
+}
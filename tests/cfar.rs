@@ -0,0 +1,85 @@
+//! CA-CFAR detection on synthetic spectra of tones over flat noise.
+
+use fmcw_radar_demo0::dsp::{cfar_detect, merge_adjacent_detections};
+use rand::{Rng as _, SeedableRng as _};
+
+const BINS: usize = 256;
+const GUARD: usize = 2;
+const TRAIN: usize = 8;
+const ALPHA: f64 = 4.0;
+
+/// Magnitudes uniformly between 0.5 and 1.5, the same on every run.
+fn noise() -> Vec<f64> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    (0..BINS).map(|_| 0.5 + rng.random::<f64>()).collect()
+}
+
+/// Adds a tone of `height` at `bin`, with half of it leaking into both neighbors.
+fn add_tone(mags: &mut [f64], bin: usize, height: f64) {
+    for (i, mag) in mags.iter_mut().enumerate() {
+        if i == bin {
+            *mag += height;
+        } else if i.abs_diff(bin) == 1 {
+            *mag += height / 2.0;
+        }
+    }
+}
+
+#[test]
+fn two_tones_over_noise_are_detected_once_each() {
+    let mut mags = noise();
+    add_tone(&mut mags, 60, 20.0);
+    add_tone(&mut mags, 180, 10.0);
+    let detections = cfar_detect(&mags, GUARD, TRAIN, ALPHA);
+    assert!(
+        detections
+            .iter()
+            .all(|&i| i.abs_diff(60) <= 1 || i.abs_diff(180) <= 1),
+        "false alarms in {detections:?}"
+    );
+    let peaks = merge_adjacent_detections(&mags, &detections);
+    assert_eq!(peaks, vec![60, 180], "one detection per tone");
+}
+
+#[test]
+fn noise_alone_has_no_detections() {
+    let detections = cfar_detect(&noise(), GUARD, TRAIN, ALPHA);
+    assert!(detections.is_empty(), "false alarms at {detections:?}");
+}
+
+#[test]
+fn tone_at_the_edge_is_detected_from_one_side() {
+    let mut mags = noise();
+    add_tone(&mut mags, 0, 20.0);
+    let peaks = merge_adjacent_detections(&mags, &cfar_detect(&mags, GUARD, TRAIN, ALPHA));
+    assert_eq!(peaks, vec![0], "tone at the first bin");
+}
+
+#[test]
+fn guard_cells_keep_a_wide_lobe_out_of_its_noise_estimate() {
+    let mut mags = noise();
+    // A lobe three bins wide on either side, which one guard bin does not cover
+    for (offset, height) in [(0, 20.0), (1, 16.0), (2, 12.0), (3, 8.0)] {
+        if let Some(mag) = mags.get_mut(100 + offset) {
+            *mag += height;
+        }
+        if let Some(mag) = mags.get_mut(100 - offset) {
+            *mag += height;
+        }
+    }
+    let narrow = cfar_detect(&mags, 1, TRAIN, ALPHA);
+    let wide = cfar_detect(&mags, 4, TRAIN, ALPHA);
+    assert!(wide.contains(&100), "peak missed with wide guard: {wide:?}");
+    assert!(
+        wide.len() > narrow.len(),
+        "wide guard detects {wide:?}, narrow {narrow:?}"
+    );
+}
+
+#[test]
+fn empty_spectrum_has_no_detections() {
+    assert!(
+        cfar_detect(&[], GUARD, TRAIN, ALPHA).is_empty(),
+        "empty spectrum"
+    );
+}
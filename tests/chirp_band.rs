@@ -0,0 +1,126 @@
+//! Chirps that sweep their own band instead of the global carrier frequency and bandwidth.
+
+use fmcw_radar_demo0::dsp::SPEED_OF_LIGHT;
+use fmcw_radar_demo0::{
+    AnalysisSettings, App, Chirp, RadarConfig, RadarObject, beat_frequencies, saw, simulate, sweep,
+};
+
+/// A chirp in the global band followed by one sweeping 400 MHz from 24 GHz.
+fn config() -> RadarConfig {
+    RadarConfig {
+        chirps: vec![
+            Chirp::new(40e-6),
+            Chirp {
+                bandwidth: Some(0.4e9),
+                start_freq: Some(24e9),
+                ..Chirp::new(40e-6)
+            },
+        ],
+        ..RadarConfig::default()
+    }
+}
+
+#[test]
+fn chirps_without_their_own_band_sweep_the_global_one() {
+    let config = RadarConfig::default();
+    let t: Vec<f64> = (0..500).map(|i| f64::from(i) * 0.3e-6).collect();
+    let expected: Vec<f64> = saw(&t, &config)
+        .iter()
+        .map(|&s| config.sweep_frequency(s))
+        .collect();
+    assert_eq!(sweep(&t, &config), expected, "sweep of the default chirps");
+}
+
+#[test]
+fn each_chirp_sweeps_its_own_band() {
+    let config = config();
+    let f = sweep(&[10e-6, 50e-6, 70e-6], &config);
+    let expected = [
+        config.carrier_frequency + config.bandwidth / 4.0,
+        24e9 + 0.4e9 / 4.0,
+        24e9 + 0.4e9 * 3.0 / 4.0,
+    ];
+    for (value, expected) in f.iter().zip(expected) {
+        assert!(
+            (value - expected).abs() < 1.0,
+            "frequency {value} Hz, expected {expected} Hz"
+        );
+    }
+    assert_eq!(
+        (config.chirp_bandwidth(1), config.chirp_center_frequency(1)),
+        (0.4e9, 24.2e9),
+        "band of the second chirp"
+    );
+}
+
+#[test]
+fn beat_follows_the_slope_of_its_chirp() {
+    let config = config();
+    let t: Vec<f64> = (0..800).map(|i| f64::from(i) * 0.1e-6).collect();
+    let f = sweep(&t, &config);
+    let range = 30.0;
+    let beats = beat_frequencies(&t, &f, range, 0.0, &config);
+    let delay = 2.0 * range / SPEED_OF_LIGHT;
+    // Halfway through each chirp, away from the reset of the ramp
+    for (time, bandwidth) in [(20e-6, config.bandwidth), (60e-6, 0.4e9)] {
+        let beat = t
+            .iter()
+            .position(|&ti| ti >= time)
+            .and_then(|idx| beats.get(idx))
+            .copied()
+            .unwrap_or_default();
+        let expected = -bandwidth / 40e-6 * delay;
+        assert!(
+            (beat - expected).abs() < 1e-6 * expected.abs(),
+            "beat {beat} Hz at {time} s, expected {expected} Hz"
+        );
+    }
+}
+
+#[test]
+fn detections_land_at_the_range_of_each_chirp() {
+    let config = config();
+    let range = 30.0;
+    let result = simulate(
+        &config,
+        &AnalysisSettings::default(),
+        &[RadarObject::new(range, 0.0, egui::Color32::GREEN, true)],
+    );
+    for (detections, bandwidth) in result.detections.iter().zip([config.bandwidth, 0.4e9]) {
+        let expected = 2.0 * range * bandwidth / (SPEED_OF_LIGHT * 40e-6);
+        let strongest = detections
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|&(freq, _)| freq);
+        assert!(
+            strongest.is_some_and(|freq| (freq - expected).abs() < 0.05 * expected),
+            "strongest peak {strongest:?}, expected {expected} Hz"
+        );
+    }
+}
+
+#[test]
+fn scenes_without_chirp_bands_use_the_global_band() {
+    let mut config = serde_json::to_value(config()).unwrap_or_default();
+    let chirps = config
+        .get_mut("chirps")
+        .and_then(|chirps| chirps.as_array_mut());
+    for chirp in chirps.into_iter().flatten() {
+        if let Some(chirp) = chirp.as_object_mut() {
+            chirp.remove("bandwidth");
+            chirp.remove("start_freq");
+        }
+    }
+    let json = serde_json::json!({ "config": config, "objects": [] }).to_string();
+    let mut app = App::default();
+    assert!(app.import_scene_json(&json).is_ok(), "import of {json}");
+    let config = app.scene().config;
+    assert!(
+        config
+            .chirps
+            .iter()
+            .all(|chirp| chirp.bandwidth.is_none() && chirp.start_freq.is_none()),
+        "bands per chirp {:?}",
+        config.chirps
+    );
+}
@@ -0,0 +1,39 @@
+//! The app works with any number of chirps, not just the three of the default configuration.
+
+use fmcw_radar_demo0::{App, Chirp};
+
+fn app_with_chirps(count: usize) -> App {
+    let mut app = App::default();
+    let mut scene = app.scene();
+    scene.config.chirps = vec![Chirp::new(40e-6); count];
+    app.set_scene(scene);
+    app
+}
+
+#[test]
+fn simulates_one_spectrum_per_chirp() {
+    for count in [1, 2, 5, 10] {
+        let mut app = app_with_chirps(count);
+        app.update();
+        let result = app.simulation_result();
+        assert_eq!(result.spectra.len(), count, "spectra of {count} chirps");
+        assert_eq!(
+            result.detections.len(),
+            count,
+            "detections of {count} chirps"
+        );
+    }
+}
+
+#[test]
+fn persisted_state_restores_the_chirp_count() {
+    let app = app_with_chirps(5);
+    let json = serde_json::to_string(&app).unwrap_or_default();
+    let restored: App = serde_json::from_str(&json).unwrap_or_default();
+    assert_eq!(
+        restored.scene().config.chirps.len(),
+        5,
+        "chirps after the round trip"
+    );
+    assert_eq!(restored.scene(), app.scene(), "scene after the round trip");
+}
@@ -0,0 +1,112 @@
+//! Parameters at the ends of the sliders, or beyond them in a broken scene, never turn the
+//! simulation into NaNs or infinities.
+
+use fmcw_radar_demo0::{App, Chirp, RadarConfig, SimulationResult};
+
+fn simulate(config: RadarConfig) -> (App, SimulationResult) {
+    let mut app = App::default();
+    let mut scene = app.scene();
+    scene.config = config;
+    app.set_scene(scene);
+    app.update();
+    let result = app.simulation_result();
+    (app, result)
+}
+
+fn assert_finite(result: &SimulationResult, case: &str) {
+    assert!(result.t.iter().all(|t| t.is_finite()), "times of {case}");
+    assert!(result.f.iter().all(|f| f.is_finite()), "sweep of {case}");
+    assert!(
+        result
+            .spectra
+            .iter()
+            .flatten()
+            .chain(result.detections.iter().flatten())
+            .all(|&(freq, mag)| freq.is_finite() && mag.is_finite()),
+        "spectra and detections of {case}"
+    );
+}
+
+#[test]
+fn slider_extremes_stay_finite() {
+    // The ends of the bandwidth, sampling rate, sampling duration and chirp duration sliders
+    for bandwidth in [0.05e9, 4e9] {
+        for sampling_frequency in [10e6, 100e6] {
+            for sampling_duration in [1e-6, 1e-3] {
+                for chirp in [1e-6, 100e-3] {
+                    let (_, result) = simulate(RadarConfig {
+                        bandwidth,
+                        sampling_frequency,
+                        sampling_duration,
+                        chirps: vec![Chirp::new(chirp); 3],
+                        ..RadarConfig::default()
+                    });
+                    assert_finite(
+                        &result,
+                        &format!(
+                            "B = {bandwidth} Hz, fs = {sampling_frequency} Hz, \
+                             window {sampling_duration} s, chirps of {chirp} s"
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn zero_and_nan_parameters_fall_back_to_the_defaults() {
+    let (app, result) = simulate(RadarConfig {
+        carrier_frequency: 0.0,
+        bandwidth: 0.0,
+        sampling_frequency: f64::NAN,
+        sampling_duration: -1.0,
+        chirps: [0.0, f64::INFINITY, 40e-6].map(Chirp::new).to_vec(),
+        ..RadarConfig::default()
+    });
+    assert_finite(&result, "zero and NaN parameters");
+    let config = app.scene().config;
+    let defaults = RadarConfig::default();
+    assert_eq!(config.bandwidth, defaults.bandwidth, "bandwidth after zero");
+    assert_eq!(
+        config.sampling_frequency, defaults.sampling_frequency,
+        "sampling rate after NaN"
+    );
+    assert!(
+        config
+            .chirps
+            .iter()
+            .all(|chirp| chirp.duration > 0.0 && chirp.duration.is_finite()),
+        "chirps {:?}",
+        config.chirps
+    );
+}
+
+#[test]
+fn array_and_nonlinearity_stay_within_their_sliders() {
+    for rx_elements in [0, 1_000_000] {
+        let (app, result) = simulate(RadarConfig {
+            rx_elements,
+            element_spacing: f64::NAN,
+            sweep_nonlinearity: f64::INFINITY,
+            ..RadarConfig::default()
+        });
+        assert_finite(&result, &format!("{rx_elements} elements"));
+        let config = app.scene().config;
+        assert!(
+            (1..=64).contains(&config.rx_elements),
+            "{} elements after {rx_elements}",
+            config.rx_elements
+        );
+        assert!(
+            config.element_spacing > 0.0 && config.element_spacing.is_finite(),
+            "spacing {} after NaN",
+            config.element_spacing
+        );
+        assert_eq!(
+            config.sweep_nonlinearity,
+            RadarConfig::default().sweep_nonlinearity,
+            "nonlinearity after infinity"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+//! Removing the DC offset, or a linear drift, empties the lowest bins but keeps the tones.
+
+use fmcw_radar_demo0::dsp::{Normalization, Window, detrend, fftspectrum};
+
+const N: usize = 1024;
+const SAMPLING_RATE: f64 = 1024.0;
+
+/// A unit tone exactly on bin 64 on top of `offset + drift·i`.
+fn tone_on(offset: f64, drift: f64) -> Vec<f64> {
+    (0..N)
+        .map(|i| {
+            let i = i as f64;
+            offset + drift * i + (2.0 * std::f64::consts::PI * 64.0 * i / SAMPLING_RATE).sin()
+        })
+        .collect()
+}
+
+fn spectrum(signal: &[f64]) -> Vec<f64> {
+    fftspectrum(
+        signal,
+        SAMPLING_RATE,
+        N,
+        Window::Rectangular,
+        Normalization::Amplitude,
+    )
+    .into_iter()
+    .map(|(_, mag)| mag)
+    .collect()
+}
+
+#[test]
+fn dc_bin_drops_to_zero() {
+    let mut signal = tone_on(3.0, 0.0);
+    let before = spectrum(&signal);
+    detrend(&mut signal, false);
+    let after = spectrum(&signal);
+    let dc = |spectrum: &[f64]| spectrum.first().copied().unwrap_or(f64::NAN);
+    assert!(
+        (dc(&before) - 3.0).abs() < 1e-9,
+        "DC bin {} before",
+        dc(&before)
+    );
+    assert!(dc(&after) < 1e-9, "DC bin {} after the removal", dc(&after));
+    let tone = after.get(64).copied().unwrap_or(f64::NAN);
+    assert!(
+        (tone - 1.0).abs() < 1e-9,
+        "tone bin {tone} after the removal"
+    );
+}
+
+#[test]
+fn linear_trend_is_removed_exactly() {
+    let clean = tone_on(0.0, 0.0);
+    let mut signal = tone_on(2.0, 0.01);
+    detrend(&mut signal, true);
+    let mut expected = clean;
+    // The tone itself has a slight trend over a whole number of cycles, which goes as well
+    detrend(&mut expected, true);
+    let error = signal
+        .iter()
+        .zip(&expected)
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0, f64::max);
+    assert!(
+        error < 1e-9,
+        "samples off by up to {error} after detrending"
+    );
+}
+
+#[test]
+fn empty_and_single_samples_stay_finite() {
+    let mut empty: Vec<f64> = vec![];
+    detrend(&mut empty, true);
+    let mut single = vec![5.0];
+    detrend(&mut single, true);
+    assert_eq!(single, vec![0.0], "a single sample is its own mean");
+}
@@ -0,0 +1,114 @@
+//! Checks that both velocity conventions and both Doppler models agree on the physics of the
+//! Doppler shift.
+
+use fmcw_radar_demo0::dsp::{
+    DopplerModel, SPEED_OF_LIGHT, VelocityConvention, doppler_for_beat, doppler_shift,
+    velocity_for_doppler,
+};
+
+const FREQUENCY: f64 = 77e9;
+const SPEED: f64 = 20.0;
+
+#[test]
+fn approaching_target_shifts_the_echo_up() {
+    let approaching = [
+        (VelocityConvention::Receding, -SPEED),
+        (VelocityConvention::Approaching, SPEED),
+    ];
+    for model in DopplerModel::ALL {
+        for (convention, velocity) in approaching {
+            let shift = doppler_shift(FREQUENCY, velocity, convention, model);
+            assert!(
+                shift > 0.0,
+                "approaching target under {convention:?} and {model:?} shifted by {shift} Hz"
+            );
+        }
+    }
+}
+
+#[test]
+fn receding_target_shifts_the_echo_down() {
+    let receding = [
+        (VelocityConvention::Receding, SPEED),
+        (VelocityConvention::Approaching, -SPEED),
+    ];
+    for model in DopplerModel::ALL {
+        for (convention, velocity) in receding {
+            let shift = doppler_shift(FREQUENCY, velocity, convention, model);
+            assert!(
+                shift < 0.0,
+                "receding target under {convention:?} and {model:?} shifted by {shift} Hz"
+            );
+        }
+    }
+}
+
+#[test]
+fn conventions_mirror_each_other() {
+    for model in DopplerModel::ALL {
+        for velocity in [-50.0, -1.0, 0.0, 3.5, 50.0] {
+            let receding = doppler_shift(FREQUENCY, velocity, VelocityConvention::Receding, model);
+            let approaching =
+                doppler_shift(FREQUENCY, -velocity, VelocityConvention::Approaching, model);
+            assert!(
+                (receding - approaching).abs() < 1e-9,
+                "{velocity} m/s under {model:?}: {receding} Hz vs {approaching} Hz"
+            );
+        }
+    }
+}
+
+#[test]
+fn two_way_classical_is_twice_v_over_c() {
+    let shift = doppler_shift(
+        FREQUENCY,
+        SPEED,
+        VelocityConvention::Approaching,
+        DopplerModel::TwoWayClassical,
+    );
+    let expected = 2.0 * SPEED * FREQUENCY / SPEED_OF_LIGHT;
+    assert!(
+        (shift - expected).abs() < 1e-9,
+        "{shift} Hz instead of {expected} Hz"
+    );
+    // 20 m/s at 77 GHz
+    assert!((shift - 10_273.77).abs() < 0.01, "{shift} Hz at 77 GHz");
+}
+
+#[test]
+fn relativistic_is_the_moving_mirror_formula() {
+    let shift = doppler_shift(
+        FREQUENCY,
+        SPEED,
+        VelocityConvention::Approaching,
+        DopplerModel::Relativistic,
+    );
+    let expected = FREQUENCY * ((SPEED_OF_LIGHT + SPEED) / (SPEED_OF_LIGHT - SPEED) - 1.0);
+    assert!(
+        (shift - expected).abs() < 1e-9,
+        "{shift} Hz instead of {expected} Hz"
+    );
+    // Far below the speed of light the models agree to within v/c
+    let classical = 2.0 * SPEED * FREQUENCY / SPEED_OF_LIGHT;
+    assert!(
+        (shift - classical).abs() < classical * 2.0 * SPEED / SPEED_OF_LIGHT,
+        "relativistic {shift} Hz, classical {classical} Hz"
+    );
+}
+
+#[test]
+fn velocity_is_recovered_from_the_beat_under_either_model() {
+    let (range, chirp, bandwidth) = (30.0, 40e-6, 1.6e9);
+    let range_beat = 2.0 * range * bandwidth / (SPEED_OF_LIGHT * chirp);
+    for model in DopplerModel::ALL {
+        let doppler = doppler_shift(FREQUENCY, SPEED, VelocityConvention::Receding, model);
+        let beat = doppler - range_beat;
+        let doppler = doppler_for_beat(beat, range, chirp, false, bandwidth);
+        let velocity =
+            velocity_for_doppler(doppler, FREQUENCY, VelocityConvention::Receding, model);
+        assert!(
+            (velocity - SPEED).abs() < 1e-6,
+            "{velocity} m/s instead of {SPEED} m/s under {model:?}"
+        );
+    }
+}
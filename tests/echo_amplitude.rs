@@ -0,0 +1,32 @@
+//! The radar cross section of an object scales its echo.
+
+use fmcw_radar_demo0::{App, RadarObject};
+
+/// Largest magnitude in the spectrum of the first chirp with a single object of `rcs`.
+fn peak_with_rcs(rcs: f64) -> f64 {
+    let mut app = App::default();
+    let mut scene = app.scene();
+    scene.objects = vec![RadarObject {
+        rcs,
+        ..RadarObject::new(20.0, 0.0, egui::Color32::WHITE, true)
+    }];
+    app.set_scene(scene);
+    app.update();
+    app.simulation_result()
+        .spectra
+        .first()
+        .map_or(0.0, |spectrum| {
+            spectrum.iter().map(|&(_, mag)| mag).fold(0.0, f64::max)
+        })
+}
+
+#[test]
+fn amplitude_goes_with_the_square_root_of_the_rcs() {
+    let unit = peak_with_rcs(1.0);
+    let quadrupled = peak_with_rcs(4.0);
+    assert!(unit > 0.0, "no echo of the unit target");
+    assert!(
+        (quadrupled / unit - 2.0).abs() < 1e-9,
+        "peaks {unit} and {quadrupled} for 1 m² and 4 m²"
+    );
+}
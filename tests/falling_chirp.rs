@@ -0,0 +1,109 @@
+//! Chirps that ramp down instead of up flip the range part of the beat but not the Doppler part.
+
+use fmcw_radar_demo0::dsp::doppler_shift;
+use fmcw_radar_demo0::{App, Chirp, RadarConfig, RadarObject, Scene, SimulationResult};
+
+const CHIRP: f64 = 40e-6;
+const RANGE: f64 = 30.0;
+
+/// A target at `velocity` m/s, seen on a rising chirp followed by a falling one.
+fn simulate(velocity: f64) -> SimulationResult {
+    let mut app = App::default();
+    app.set_scene(Scene {
+        config: RadarConfig {
+            chirps: vec![
+                Chirp::new(CHIRP),
+                Chirp {
+                    falling: true,
+                    ..Chirp::new(CHIRP)
+                },
+            ],
+            ..RadarConfig::default()
+        },
+        objects: vec![RadarObject::new(
+            RANGE,
+            velocity,
+            egui::Color32::WHITE,
+            true,
+        )],
+    });
+    app.update();
+    app.simulation_result()
+}
+
+/// Beat of the only target halfway through the rising and the falling chirp.
+fn mid_chirp_beats(result: &SimulationResult) -> (f64, f64) {
+    let beat_at = |time: f64| {
+        let idx = result
+            .t
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| (*a - time).abs().total_cmp(&(*b - time).abs()))
+            .map_or(0, |(i, _)| i);
+        result
+            .targets
+            .first()
+            .and_then(|target| target.beat_freqs.get(idx))
+            .copied()
+            .unwrap_or(f64::NAN)
+    };
+    (beat_at(CHIRP / 2.0), beat_at(1.5 * CHIRP))
+}
+
+#[test]
+fn stationary_target_beats_mirror_each_other() {
+    let (rising, falling) = mid_chirp_beats(&simulate(0.0));
+    assert!(rising < 0.0, "rising chirp beat {rising} Hz");
+    assert!(
+        (rising + falling).abs() < 1e-6 * rising.abs(),
+        "rising {rising} Hz, falling {falling} Hz"
+    );
+}
+
+#[test]
+fn doppler_shift_keeps_its_sign_on_a_falling_chirp() {
+    let result = simulate(20.0);
+    let (rising, falling) = mid_chirp_beats(&result);
+    let config = &result.config;
+    // Halfway through either chirp the radar transmits at the middle of the band
+    let expected = doppler_shift(
+        config.carrier_frequency + config.bandwidth / 2.0,
+        20.0,
+        config.velocity_convention,
+        config.doppler_model,
+    );
+    let doppler = (rising + falling) / 2.0;
+    assert!(
+        (doppler - expected).abs() < 0.01 * expected.abs(),
+        "Doppler part {doppler} Hz, expected {expected} Hz"
+    );
+}
+
+#[test]
+fn scenes_without_slopes_ramp_up() {
+    let config = RadarConfig {
+        chirps: vec![Chirp {
+            falling: true,
+            ..Chirp::new(CHIRP)
+        }],
+        ..RadarConfig::default()
+    };
+    let mut config = serde_json::to_value(config).unwrap_or_default();
+    let chirps = config
+        .get_mut("chirps")
+        .and_then(|chirps| chirps.as_array_mut());
+    for chirp in chirps.into_iter().flatten() {
+        if let Some(chirp) = chirp.as_object_mut() {
+            chirp.remove("falling");
+        }
+    }
+    let json = serde_json::json!({ "config": config, "objects": [] }).to_string();
+    let mut app = App::default();
+    assert!(app.import_scene_json(&json).is_ok(), "import of {json}");
+    let config = app.scene().config;
+    assert!(
+        config.chirps.iter().all(|chirp| !chirp.falling),
+        "slopes of {:?}",
+        config.chirps
+    );
+}
@@ -0,0 +1,65 @@
+//! Guards the spectrum of the default scene against unintended numerical changes.
+//!
+//! After an intended change, regenerate the reference with
+//! `UPDATE_GOLDEN=1 cargo test --test golden` and review the diff of the CSV.
+
+use fmcw_radar_demo0::App;
+
+const GOLDEN_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/default_spectrum.csv");
+
+/// Largest allowed difference of a magnitude, relative to the peak of the spectrum.
+const TOLERANCE: f64 = 1e-9;
+
+fn default_spectrum() -> Vec<(f64, f64)> {
+    let mut app = App::default();
+    app.update();
+    app.simulation_result()
+        .spectra
+        .into_iter()
+        .next()
+        .expect("the default scene has chirps")
+}
+
+fn write_golden(spectrum: &[(f64, f64)]) {
+    let mut csv = String::from("frequency_hz,magnitude\n");
+    for (freq, mag) in spectrum {
+        csv.push_str(&format!("{freq},{mag}\n"));
+    }
+    std::fs::write(GOLDEN_PATH, csv).expect("failed to write the golden spectrum");
+}
+
+fn read_golden() -> Vec<(f64, f64)> {
+    let csv = std::fs::read_to_string(GOLDEN_PATH).expect("failed to read the golden spectrum");
+    csv.lines()
+        .skip(1)
+        .map(|line| {
+            let (freq, mag) = line.split_once(',').expect("expected two columns");
+            (
+                freq.parse().expect("invalid frequency"),
+                mag.parse().expect("invalid magnitude"),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn default_spectrum_matches_golden() {
+    let spectrum = default_spectrum();
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        write_golden(&spectrum);
+    }
+    let golden = read_golden();
+
+    assert_eq!(spectrum.len(), golden.len(), "number of bins changed");
+    let peak = golden.iter().fold(0.0_f64, |max, &(_, mag)| max.max(mag));
+    for (&(freq, mag), &(golden_freq, golden_mag)) in spectrum.iter().zip(&golden) {
+        assert!(
+            (freq - golden_freq).abs() < 1e-6,
+            "bin at {freq} Hz moved from {golden_freq} Hz"
+        );
+        assert!(
+            (mag - golden_mag).abs() <= TOLERANCE * peak,
+            "magnitude at {freq} Hz drifted from {golden_mag} to {mag}"
+        );
+    }
+}
@@ -0,0 +1,92 @@
+//! The headless [`simulate`] and the interactive app that runs it.
+
+use fmcw_radar_demo0::dsp::Window;
+use fmcw_radar_demo0::{AnalysisSettings, App, RadarConfig, RadarObject, simulate};
+
+/// Hann-windowed and noisy, unlike the defaults.
+fn noisy_settings(seed: u64) -> AnalysisSettings {
+    AnalysisSettings {
+        window: Window::Hann,
+        no_noise: false,
+        snr_db: 10.0,
+        seed,
+        ..AnalysisSettings::default()
+    }
+}
+
+#[test]
+fn app_runs_the_pipeline_with_its_analysis_settings() {
+    let settings = noisy_settings(7);
+    let state = serde_json::json!({ "analysis": settings });
+    let mut app: App = serde_json::from_value(state).unwrap_or_default();
+    app.update();
+    let scene = app.scene();
+
+    let headless = simulate(&scene.config, &settings, &scene.objects);
+    assert_eq!(app.simulation_result(), headless, "result of the app");
+    assert_ne!(
+        simulate(&scene.config, &AnalysisSettings::default(), &scene.objects).spectra,
+        headless.spectra,
+        "spectra with the default settings"
+    );
+}
+
+#[test]
+fn noise_is_reproducible_from_its_seed() {
+    let config = RadarConfig::default();
+    let objects = [RadarObject::new(20.0, 0.0, egui::Color32::WHITE, true)];
+    let spectra = |seed: u64| simulate(&config, &noisy_settings(seed), &objects).spectra;
+    assert_eq!(spectra(1), spectra(1), "spectra from the same seed");
+    assert_ne!(spectra(1), spectra(2), "spectra from different seeds");
+}
+
+#[test]
+fn broken_configuration_is_sanitized() {
+    let config = RadarConfig {
+        sampling_frequency: f64::NAN,
+        rx_elements: 10_000,
+        ..RadarConfig::default()
+    };
+    let objects = [RadarObject::new(20.0, 0.0, egui::Color32::WHITE, true)];
+    let result = simulate(&config, &AnalysisSettings::default(), &objects);
+    assert_eq!(
+        result.config,
+        config.sanitized(),
+        "configuration of the result"
+    );
+    assert!(
+        result
+            .spectra
+            .iter()
+            .flatten()
+            .all(|&(freq, mag)| freq.is_finite() && mag.is_finite()),
+        "spectra {:?}",
+        result.spectra
+    );
+}
+
+#[test]
+fn detection_follows_the_range_of_the_object() {
+    let config = App::default().scene().config;
+    let strongest_beat = |range: f64| {
+        let object = RadarObject {
+            range,
+            velocity: 0.0,
+            ..RadarObject::default()
+        };
+        let result = simulate(&config, &AnalysisSettings::default(), &[object]);
+        let detections = result.detections.first().expect("first chirp");
+        detections
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("a detection")
+            .0
+    };
+
+    let near = strongest_beat(20.0);
+    let far = strongest_beat(60.0);
+    assert!(
+        far.abs() > near.abs(),
+        "beat at 60 m ({far} Hz) is not above the beat at 20 m ({near} Hz)"
+    );
+}
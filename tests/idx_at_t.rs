@@ -0,0 +1,56 @@
+//! `idx_at_t` bisects the sorted time steps; it must find the same index as a linear scan.
+
+use fmcw_radar_demo0::dsp::idx_at_t;
+use rand::{Rng as _, SeedableRng as _};
+
+/// The closest index by scanning every time, the first one on ties.
+fn linear_idx_at_t(v: &[f64], t: f64) -> usize {
+    v.iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| (*a - t).abs().total_cmp(&(*b - t).abs()))
+        .map_or(0, |(i, _)| i)
+}
+
+#[test]
+fn matches_the_linear_scan_at_random_times() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+    // Unevenly spaced, like no particular grid
+    let mut t = 0.0;
+    let times: Vec<f64> = (0..500)
+        .map(|_| {
+            t += rng.random_range(1e-9..1e-6);
+            t
+        })
+        .collect();
+    let end = times.last().copied().unwrap_or_default();
+    for _ in 0..10_000 {
+        // Also before the first and after the last time
+        let query = rng.random_range(-0.1 * end..1.1 * end);
+        assert_eq!(
+            idx_at_t(&times, query),
+            linear_idx_at_t(&times, query),
+            "query at {query} s"
+        );
+    }
+}
+
+#[test]
+fn picks_the_nearer_neighbor() {
+    let times = [0.0, 1.0, 2.0, 3.0];
+    assert_eq!(idx_at_t(&times, 1.4), 1, "closer to 1");
+    assert_eq!(idx_at_t(&times, 1.6), 2, "closer to 2");
+    assert_eq!(idx_at_t(&times, 1.5), 1, "tie goes to the earlier time");
+    assert_eq!(idx_at_t(&times, 2.0), 2, "exact time");
+    assert_eq!(idx_at_t(&times, -5.0), 0, "before the first time");
+    assert_eq!(idx_at_t(&times, 9.0), 3, "after the last time");
+}
+
+#[test]
+fn empty_times_give_the_first_index() {
+    assert_eq!(idx_at_t(&[], 1.0), 0, "empty time vector");
+}
+
+#[test]
+fn nan_time_gives_the_first_index() {
+    assert_eq!(idx_at_t(&[0.0, 1.0, 2.0], f64::NAN), 0, "NaN query");
+}
@@ -0,0 +1,50 @@
+//! Parabolic interpolation of peak frequencies between FFT bins.
+
+use fmcw_radar_demo0::dsp::{Normalization, Window, fftspectrum, refine_peak};
+
+#[test]
+fn log_parabola_recovers_a_gaussian_exactly() {
+    let mags: Vec<f64> = (0..5)
+        .map(|i| (-(f64::from(i) - 2.3).powi(2)).exp())
+        .collect();
+    let (bin, mag) = refine_peak(&mags, 2);
+    assert!((bin - 2.3).abs() < 1e-12, "refined bin {bin}");
+    assert!((mag - 1.0).abs() < 1e-12, "refined magnitude {mag}");
+}
+
+#[test]
+fn tone_between_bins_is_recovered_to_a_tenth_of_a_bin() {
+    const N: usize = 256;
+    for frequency in [20.1, 20.25, 20.4, 20.5] {
+        // One bin per Hz
+        let signal: Vec<f64> = (0..N)
+            .map(|i| (2.0 * std::f64::consts::PI * frequency * i as f64 / N as f64).sin())
+            .collect();
+        let mags: Vec<f64> =
+            fftspectrum(&signal, N as f64, N, Window::Hann, Normalization::Amplitude)
+                .into_iter()
+                .map(|(_, mag)| mag)
+                .collect();
+        let peak_idx = mags
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map_or(0, |(i, _)| i);
+        let (bin, _) = refine_peak(&mags, peak_idx);
+        assert!(
+            (bin - frequency).abs() < 0.1,
+            "tone at bin {frequency} refined to {bin}"
+        );
+    }
+}
+
+#[test]
+fn edge_and_empty_neighbours_keep_the_bin() {
+    assert_eq!(refine_peak(&[3.0, 2.0, 1.0], 0), (0.0, 3.0), "first bin");
+    assert_eq!(
+        refine_peak(&[0.0, 3.0, 1.0], 1),
+        (1.0, 3.0),
+        "empty neighbour"
+    );
+    assert_eq!(refine_peak(&[], 0), (0.0, 0.0), "empty spectrum");
+}
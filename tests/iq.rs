@@ -0,0 +1,55 @@
+//! IQ imbalance mirrors tones to their negative frequency.
+
+use fmcw_radar_demo0::dsp::{Complex, apply_iq_imbalance, two_sided_spectrum};
+
+const N: usize = 256;
+const FS: f64 = 256.0;
+const TONE: f64 = 32.0;
+
+fn tone() -> Vec<Complex<f64>> {
+    (0..N)
+        .map(|i| Complex::from_polar(1.0, 2.0 * std::f64::consts::PI * TONE * i as f64 / FS))
+        .collect()
+}
+
+fn magnitude_at(spectrum: &[(f64, f64)], freq: f64) -> f64 {
+    spectrum
+        .iter()
+        .find(|&&(f, _)| (f - freq).abs() < 1e-9)
+        .map_or(f64::NAN, |&(_, mag)| mag)
+}
+
+#[test]
+fn balanced_receiver_has_no_image() {
+    let mut iq = tone();
+    apply_iq_imbalance(&mut iq, 0.0, 0.0);
+    let spectrum = two_sided_spectrum(&iq, FS);
+    assert!(
+        (magnitude_at(&spectrum, TONE) - 1.0).abs() < 1e-9,
+        "tone magnitude {}",
+        magnitude_at(&spectrum, TONE)
+    );
+    assert!(
+        magnitude_at(&spectrum, -TONE) < 1e-9,
+        "image magnitude {}",
+        magnitude_at(&spectrum, -TONE)
+    );
+}
+
+#[test]
+fn imbalance_creates_image_at_negative_frequency() {
+    let mut iq = tone();
+    apply_iq_imbalance(&mut iq, 1.0, 5.0);
+    let spectrum = two_sided_spectrum(&iq, FS);
+    let image = magnitude_at(&spectrum, -TONE);
+    assert!(image > 0.01 && image < 0.2, "image magnitude {image}");
+}
+
+#[test]
+fn two_sided_spectrum_spans_negative_to_positive_nyquist() {
+    let spectrum = two_sided_spectrum(&tone(), FS);
+    let first = spectrum.first().map(|&(f, _)| f);
+    let last = spectrum.last().map(|&(f, _)| f);
+    assert_eq!(first, Some(-FS / 2.0), "first bin");
+    assert_eq!(last, Some(FS / 2.0 - 1.0), "last bin");
+}
@@ -0,0 +1,48 @@
+//! The anti-aliasing filter passes low frequencies and suppresses those above its cutoff.
+
+use fmcw_radar_demo0::dsp::lowpass;
+
+fn tone(cycles_per_sample: f64, n: usize) -> Vec<f64> {
+    (0..n)
+        .map(|i| (2.0 * std::f64::consts::PI * cycles_per_sample * i as f64).sin())
+        .collect()
+}
+
+/// Largest magnitude away from the edges, where the filter runs out of samples.
+fn amplitude(signal: &[f64]) -> f64 {
+    signal
+        .iter()
+        .skip(100)
+        .take(signal.len() - 200)
+        .fold(0.0, |max, x| max.max(x.abs()))
+}
+
+#[test]
+fn constant_signal_passes_unchanged() {
+    let filtered = lowpass(&[1.0; 300], 0.1, 33);
+    assert!(
+        (amplitude(&filtered) - 1.0).abs() < 1e-9,
+        "DC gain is {}",
+        amplitude(&filtered)
+    );
+}
+
+#[test]
+fn tone_below_cutoff_passes() {
+    let filtered = lowpass(&tone(0.02, 1000), 0.0625, 65);
+    assert!(
+        amplitude(&filtered) > 0.95,
+        "passband amplitude {}",
+        amplitude(&filtered)
+    );
+}
+
+#[test]
+fn tone_above_cutoff_is_suppressed() {
+    let filtered = lowpass(&tone(0.2, 1000), 0.0625, 65);
+    assert!(
+        amplitude(&filtered) < 0.01,
+        "stopband amplitude {}",
+        amplitude(&filtered)
+    );
+}
@@ -0,0 +1,81 @@
+//! A vibrating object wobbles its beat by the Doppler shift of its vibration speed, which spreads
+//! its spectrum into micro-Doppler sidebands.
+
+use fmcw_radar_demo0::dsp::SPEED_OF_LIGHT;
+use fmcw_radar_demo0::{
+    AnalysisSettings, RadarConfig, RadarObject, beat_frequencies, saw, simulate,
+};
+
+fn sweep(config: &RadarConfig) -> (Vec<f64>, Vec<f64>) {
+    let t: Vec<f64> = (0..2000).map(|i| f64::from(i) * 0.1e-6).collect();
+    let f = saw(&t, config)
+        .iter()
+        .map(|&s| config.sweep_frequency(s))
+        .collect();
+    (t, f)
+}
+
+fn vibrating(micro_amp: f64, micro_freq: f64) -> RadarObject {
+    RadarObject {
+        micro_amp,
+        micro_freq,
+        ..RadarObject::new(20.0, 0.0, egui::Color32::WHITE, true)
+    }
+}
+
+#[test]
+fn rigid_object_keeps_its_beats() {
+    let config = RadarConfig::default();
+    let (t, f) = sweep(&config);
+    let object = vibrating(0.0, 1e3);
+    assert_eq!(
+        object.reflection_beats(&t, &f, object.range, &config),
+        beat_frequencies(&t, &f, object.range, object.velocity, &config),
+        "beats without vibration"
+    );
+}
+
+#[test]
+fn beat_swings_by_the_doppler_of_the_vibration_speed() {
+    let config = RadarConfig::default();
+    let (t, f) = sweep(&config);
+    let object = vibrating(1e-3, 10e3);
+    let rigid = beat_frequencies(&t, &f, object.range, object.velocity, &config);
+    let wobble: Vec<f64> = object
+        .reflection_beats(&t, &f, object.range, &config)
+        .iter()
+        .zip(&rigid)
+        .map(|(beat, rigid)| beat - rigid)
+        .collect();
+    let swing = wobble.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b))
+        - wobble.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+    let speed = object.micro_amp * 2.0 * std::f64::consts::PI * object.micro_freq;
+    let expected = 2.0 * 2.0 * speed * config.carrier_frequency / SPEED_OF_LIGHT;
+    assert!(
+        (swing - expected).abs() < 0.05 * expected,
+        "beat swings by {swing} Hz, expected about {expected} Hz"
+    );
+}
+
+#[test]
+fn vibration_spreads_the_peak_into_sidebands() {
+    let config = RadarConfig::default();
+    let strongest = |micro_amp: f64| {
+        let result = simulate(
+            &config,
+            &AnalysisSettings::default(),
+            &[vibrating(micro_amp, 100e3)],
+        );
+        result.spectra.first().map_or(0.0, |spectrum| {
+            spectrum.iter().fold(0.0_f64, |max, &(_, mag)| max.max(mag))
+        })
+    };
+    // A millimeter at 77 GHz modulates the phase by about 3 rad, which leaves less than half of
+    // the power in any one line
+    let still = strongest(1e-9);
+    let shaking = strongest(1e-3);
+    assert!(
+        shaking < 0.8 * still,
+        "strongest bin {shaking} while vibrating, {still} nearly still"
+    );
+}
@@ -0,0 +1,68 @@
+//! With multipath every object gets a weaker ghost behind it, from its echo bouncing off the
+//! ground on one way.
+
+use fmcw_radar_demo0::dsp::range_for_beat;
+use fmcw_radar_demo0::{AnalysisSettings, RadarConfig, RadarObject, multipath_range, simulate};
+
+#[test]
+fn bounced_path_lengthens_the_range() {
+    assert_eq!(multipath_range(20.0, 0.0), 20.0, "radar on the ground");
+    // 20 m away and 2 × 7.5 m above its mirror image: a 3-4-5 triangle
+    let range = multipath_range(20.0, 7.5);
+    assert!((range - 22.5).abs() < 1e-12, "range {range}");
+    assert!(
+        multipath_range(80.0, 7.5) - 80.0 < range - 20.0,
+        "the detour shrinks with range"
+    );
+}
+
+#[test]
+fn ghost_is_weaker_and_slower() {
+    let obj = RadarObject::new(20.0, 10.0, egui::Color32::WHITE, true);
+    let ghost = obj.ground_bounce(7.5, 0.5);
+    assert!((ghost.range - 22.5).abs() < 1e-12, "range {}", ghost.range);
+    assert!((ghost.rcs - 0.25).abs() < 1e-12, "rcs {}", ghost.rcs);
+    // The bounced path changes by 20 / 25 of the direct one
+    assert!(
+        (ghost.velocity - 9.0).abs() < 1e-12,
+        "velocity {}",
+        ghost.velocity
+    );
+}
+
+/// Ranges of the peaks detected on the first chirp for an object at 20 m, with the radar 7.5 m
+/// above the ground and multipath toggled by `multipath`.
+fn detected_ranges(multipath: bool) -> Vec<f64> {
+    let settings = AnalysisSettings {
+        multipath,
+        radar_height: 7.5,
+        ..AnalysisSettings::default()
+    };
+    let object = RadarObject::new(20.0, 0.0, egui::Color32::WHITE, true);
+    let result = simulate(&RadarConfig::default(), &settings, &[object]);
+    let Some(chirp) = result.config.chirps.first() else {
+        return vec![];
+    };
+    let bandwidth = result.config.chirp_bandwidth(0);
+    result
+        .detections
+        .first()
+        .into_iter()
+        .flatten()
+        .map(|&(freq, _)| range_for_beat(freq, chirp.duration, bandwidth))
+        .collect()
+}
+
+#[test]
+fn ghost_appears_as_an_extra_peak() {
+    let near = |ranges: &[f64], range: f64| ranges.iter().any(|r| (r - range).abs() < 0.5);
+    let direct = detected_ranges(false);
+    assert!(near(&direct, 20.0), "object in {direct:?}");
+    assert!(
+        !near(&direct, 22.5),
+        "ghost without multipath in {direct:?}"
+    );
+    let bounced = detected_ranges(true);
+    assert!(near(&bounced, 20.0), "object in {bounced:?}");
+    assert!(near(&bounced, 22.5), "ghost in {bounced:?}");
+}
@@ -0,0 +1,62 @@
+//! Pins the peak of a unit sine under every FFT normalization.
+
+use fmcw_radar_demo0::dsp::{Normalization, Window, fftspectrum};
+
+const N: usize = 1024;
+const SAMPLING_RATE: f64 = 1024.0;
+/// Exactly on bin 64, so there is no leakage.
+const FREQUENCY: f64 = 64.0;
+
+fn unit_sine_peak(normalization: Normalization) -> f64 {
+    let signal: Vec<f64> = (0..N)
+        .map(|i| (2.0 * std::f64::consts::PI * FREQUENCY * i as f64 / SAMPLING_RATE).sin())
+        .collect();
+    fftspectrum(
+        &signal,
+        SAMPLING_RATE,
+        N,
+        Window::Rectangular,
+        normalization,
+    )
+    .into_iter()
+    .map(|(_, mag)| mag)
+    .fold(0.0, f64::max)
+}
+
+#[test]
+fn unit_sine_peak_under_each_normalization() {
+    let n = N as f64;
+    let expected = [
+        (Normalization::None, n / 2.0),
+        (Normalization::PerSample, 0.5),
+        (Normalization::Unitary, n.sqrt() / 2.0),
+        (Normalization::Amplitude, 1.0),
+    ];
+    for (normalization, expected) in expected {
+        let peak = unit_sine_peak(normalization);
+        assert!(
+            (peak - expected).abs() < 1e-9 * expected,
+            "{normalization:?}: peak {peak}, expected {expected}"
+        );
+    }
+}
+
+#[test]
+fn dc_bin_shows_the_mean() {
+    let mean = 0.75;
+    let signal: Vec<f64> = (0..N)
+        .map(|i| mean + (2.0 * std::f64::consts::PI * FREQUENCY * i as f64 / SAMPLING_RATE).sin())
+        .collect();
+    let spectrum = fftspectrum(
+        &signal,
+        SAMPLING_RATE,
+        N,
+        Window::Rectangular,
+        Normalization::Amplitude,
+    );
+    let dc = spectrum.first().map_or(f64::NAN, |&(_, mag)| mag);
+    assert!((dc - mean).abs() < 1e-9, "DC bin {dc}, mean {mean}");
+    // The tone keeps its amplitude next to the offset
+    let tone = spectrum.get(64).map_or(f64::NAN, |&(_, mag)| mag);
+    assert!((tone - 1.0).abs() < 1e-9, "tone bin {tone}");
+}
@@ -0,0 +1,36 @@
+//! The app works with any number of objects, not just the three of the default scene.
+
+use fmcw_radar_demo0::{App, RadarObject};
+
+fn app_with_objects(count: usize) -> App {
+    let mut app = App::default();
+    let mut scene = app.scene();
+    scene.objects = (0..count)
+        .map(|i| RadarObject::new(5.0 * (i + 1) as f64, 0.0, egui::Color32::WHITE, true))
+        .collect();
+    app.set_scene(scene);
+    app
+}
+
+#[test]
+fn persisted_state_round_trips_ten_objects() {
+    let app = app_with_objects(10);
+    let json = serde_json::to_string(&app).unwrap_or_default();
+    let restored: App = serde_json::from_str(&json).unwrap_or_default();
+    assert_eq!(restored.scene(), app.scene(), "scene after the round trip");
+}
+
+#[test]
+fn simulates_without_and_with_many_objects() {
+    for count in [0, 1, 10] {
+        let mut app = app_with_objects(count);
+        app.update();
+        let result = app.simulation_result();
+        assert_eq!(result.targets.len(), count, "targets of {count} objects");
+        assert_eq!(
+            result.spectra.len(),
+            result.config.chirps.len(),
+            "spectra of {count} objects"
+        );
+    }
+}
@@ -0,0 +1,47 @@
+//! Computing the beats of all objects at once, in parallel with the `parallel` feature, gives
+//! exactly what computing them one by one does.
+
+use fmcw_radar_demo0::{RadarConfig, RadarObject, compute_beats, saw};
+
+#[test]
+fn all_objects_match_one_by_one() {
+    let config = RadarConfig::default();
+    let duration: f64 = config
+        .chirps
+        .iter()
+        .map(|chirp| chirp.duration)
+        .sum::<f64>()
+        * 3.0;
+    let t: Vec<f64> = (0..1000)
+        .map(|i| f64::from(i) * duration / 1000.0)
+        .collect();
+    let f: Vec<f64> = saw(&t, &config)
+        .iter()
+        .map(|&s| config.sweep_frequency(s))
+        .collect();
+    let range_bias = 0.3;
+    let objects: Vec<RadarObject> = (0..64)
+        .map(|i| RadarObject {
+            extended: i % 2 == 0,
+            ..RadarObject::new(
+                1.5 * f64::from(i + 1),
+                f64::from(i) - 32.0,
+                egui::Color32::WHITE,
+                true,
+            )
+        })
+        .collect();
+
+    let mut all = objects.clone();
+    compute_beats(&mut all, &t, &f, range_bias, &config);
+    let mut one_by_one = objects;
+    for obj in &mut one_by_one {
+        obj.compute_beats(&t, &f, range_bias, &config);
+    }
+
+    assert_eq!(all, one_by_one, "objects after computing their beats");
+    assert!(
+        all.iter().all(|obj| obj.beat_freqs.len() == t.len()),
+        "one beat per time step"
+    );
+}
@@ -0,0 +1,60 @@
+//! Pins down the contract of the peak finder with synthetic spectra.
+
+use fmcw_radar_demo0::dsp::multiple_peak_finding;
+
+const BINS: usize = 256;
+
+fn gaussian(center: f64, width: f64) -> impl Fn(usize) -> f64 {
+    move |i| (-((i as f64 - center) / width).powi(2) / 2.0).exp()
+}
+
+fn spectrum(f: impl Fn(usize) -> f64) -> Vec<f64> {
+    (0..BINS).map(f).collect()
+}
+
+#[test]
+fn single_bump_has_one_peak_at_its_center() {
+    let bump = gaussian(100.0, 4.0);
+    let peaks = multiple_peak_finding(&spectrum(bump));
+    assert_eq!(peaks, vec![100], "expected one peak at the center bin");
+}
+
+#[test]
+fn separated_bumps_have_two_peaks() {
+    let (a, b) = (gaussian(60.0, 4.0), gaussian(180.0, 4.0));
+    let peaks = multiple_peak_finding(&spectrum(|i| a(i) + 0.5 * b(i)));
+    assert_eq!(peaks, vec![60, 180], "expected one peak per bump");
+}
+
+#[test]
+fn flat_spectrum_has_no_peaks() {
+    let peaks = multiple_peak_finding(&spectrum(|_| 1.0));
+    assert!(peaks.is_empty(), "flat spectrum produced peaks {peaks:?}");
+}
+
+#[test]
+fn rising_ramp_has_no_peaks() {
+    let peaks = multiple_peak_finding(&spectrum(|i| i as f64));
+    assert!(peaks.is_empty(), "rising ramp produced peaks {peaks:?}");
+}
+
+#[test]
+fn empty_spectrum_has_no_peaks() {
+    let peaks = multiple_peak_finding(&[]);
+    assert!(peaks.is_empty(), "empty spectrum produced peaks {peaks:?}");
+}
+
+#[test]
+fn finds_exactly_the_interior_peaks_above_the_baseline() {
+    // Mean 3.5: the ripple at 5 stays below it, and the large first and last samples sit on
+    // the boundary without a neighbor on one side.
+    let signal = [6.0, 1.0, 4.0, 1.0, 1.0, 2.0, 1.0, 7.0, 3.0, 9.0];
+    let peaks = multiple_peak_finding(&signal);
+    assert_eq!(peaks, vec![2, 7], "expected the peaks at 4 and 7 only");
+}
+
+#[test]
+fn flat_top_is_one_peak() {
+    let peaks = multiple_peak_finding(&[0.0, 0.0, 5.0, 5.0, 5.0, 0.0, 0.0]);
+    assert_eq!(peaks, vec![4], "expected one peak on the flat top");
+}
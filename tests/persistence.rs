@@ -0,0 +1,103 @@
+//! The state eframe saves on shutdown and restores on the next start.
+
+use fmcw_radar_demo0::{App, Chirp, RadarConfig, Waveform};
+
+fn customized_app() -> App {
+    let mut app = App::default();
+    let mut scene = app.scene();
+    scene.config = RadarConfig {
+        bandwidth: 0.8e9,
+        chirps: [30e-6, 45e-6, 50e-6, 25e-6]
+            .into_iter()
+            .enumerate()
+            .map(|(i, duration)| Chirp {
+                falling: i % 2 == 1,
+                ..Chirp::new(duration)
+            })
+            .collect(),
+        rx_elements: 8,
+        waveform: Waveform::Triangle,
+        ..RadarConfig::default()
+    };
+    if let Some(obj) = scene.objects.first_mut() {
+        obj.extended = true;
+    }
+    app.set_scene(scene);
+    app.update();
+    app
+}
+
+fn round_trip(app: &App) -> App {
+    let json = serde_json::to_string(app).unwrap_or_default();
+    serde_json::from_str(&json).unwrap_or_default()
+}
+
+#[test]
+fn configuration_survives_a_restart() {
+    let app = customized_app();
+    let restored = round_trip(&app);
+    assert_eq!(restored.scene(), app.scene(), "scene after the round trip");
+}
+
+#[test]
+fn every_persisted_setting_survives_a_restart() {
+    let app = customized_app();
+    let restored = round_trip(&app);
+    assert_eq!(
+        serde_json::to_value(&restored).ok(),
+        serde_json::to_value(&app).ok(),
+        "persisted state after the round trip"
+    );
+}
+
+#[test]
+fn simulation_results_are_recomputed_instead_of_saved() {
+    let app = customized_app();
+    let mut restored = round_trip(&app);
+    assert!(
+        restored.simulation_result().spectra.is_empty(),
+        "spectra were restored instead of recomputed"
+    );
+    restored.update();
+    assert_eq!(
+        restored.simulation_result(),
+        app.simulation_result(),
+        "simulation after the restart"
+    );
+}
+
+#[test]
+fn beat_frequencies_are_recomputed_instead_of_saved() {
+    let state = serde_json::to_value(customized_app()).unwrap_or_default();
+    let objects = state
+        .get("objects")
+        .and_then(|objects| objects.as_array())
+        .cloned()
+        .unwrap_or_default();
+    assert!(!objects.is_empty(), "persisted objects in {state}");
+    assert!(
+        objects.iter().all(|obj| {
+            obj.get("beat_freqs").is_none() && obj.get("scatterer_beat_freqs").is_none()
+        }),
+        "beat frequencies were saved: {state}"
+    );
+}
+
+#[test]
+fn state_without_newer_fields_loads_with_their_defaults() {
+    let mut state = serde_json::to_value(App::default()).unwrap_or_default();
+    if let Some(fields) = state.as_object_mut() {
+        // Chirps were only their durations
+        fields.insert(
+            "chirps".to_owned(),
+            serde_json::json!([40e-6, 20e-6, 60e-6]),
+        );
+        fields.remove("waveform");
+    }
+    let restored: Result<App, _> = serde_json::from_value(state);
+    assert_eq!(
+        restored.map(|app| app.scene()).ok(),
+        Some(App::default().scene()),
+        "state saved before chirp settings and waveforms"
+    );
+}
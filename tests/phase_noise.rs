@@ -0,0 +1,69 @@
+//! Phase noise must be reproducible from its seed and scale with its level.
+
+use fmcw_radar_demo0::dsp::{
+    Normalization, Window, fftspectrum, phase_noise, phase_noise_level, sample_noisy_signal,
+};
+use rand::SeedableRng as _;
+
+#[test]
+fn zero_level_has_no_phase_noise() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    let phase = phase_noise(100, 0.0, &mut rng);
+    assert_eq!(phase, vec![0.0; 100], "phase drifted without noise");
+}
+
+#[test]
+fn same_seed_gives_same_phase_noise() {
+    let noise = || phase_noise(100, 0.1, &mut rand::rngs::StdRng::seed_from_u64(42));
+    assert_eq!(noise(), noise(), "phase noise is not reproducible");
+}
+
+#[test]
+fn phase_noise_drifts_with_level() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+    let phase = phase_noise(10_000, 0.1, &mut rng);
+    // A random walk of n steps spreads by level·√n, here 10 rad
+    let rms = (phase.iter().map(|p| p * p).sum::<f64>() / phase.len() as f64).sqrt();
+    assert!(rms > 0.5 && rms < 50.0, "unexpected phase spread {rms}");
+}
+
+#[test]
+fn level_follows_the_dbc_per_hz() {
+    // 20 dB more phase noise is ten times the phase steps
+    let ratio = phase_noise_level(-70.0, 1e6, 10e6) / phase_noise_level(-90.0, 1e6, 10e6);
+    assert!((ratio - 10.0).abs() < 1e-9, "step ratio {ratio} per 20 dB");
+}
+
+/// Mean magnitude 100 to 300 kHz away from a 1 MHz beat whose carrier has `dbc_per_hz` of phase
+/// noise.
+fn pedestal(dbc_per_hz: f64) -> f64 {
+    let sampling_rate = 10.24e6;
+    let n = 4096;
+    let t: Vec<f64> = (0..n).map(|i| i as f64 / sampling_rate).collect();
+    let level = phase_noise_level(dbc_per_hz, 1e6, sampling_rate);
+    let phase = phase_noise(n, level, &mut rand::rngs::StdRng::seed_from_u64(7));
+    let signal = sample_noisy_signal(&t, &[(1e6, 1.0)], &phase);
+    let spectrum = fftspectrum(
+        &signal,
+        sampling_rate,
+        n,
+        Window::Rectangular,
+        Normalization::default(),
+    );
+    let skirt: Vec<f64> = spectrum
+        .iter()
+        .filter(|&&(f, _)| (100e3..=300e3).contains(&(f - 1e6).abs()))
+        .map(|&(_, mag)| mag)
+        .collect();
+    skirt.iter().sum::<f64>() / skirt.len() as f64
+}
+
+#[test]
+fn pedestal_rises_with_phase_noise() {
+    let low = pedestal(-120.0);
+    let high = pedestal(-90.0);
+    assert!(
+        high > 3.0 * low,
+        "pedestal {high} at -90 dBc/Hz vs {low} at -120 dBc/Hz"
+    );
+}
@@ -0,0 +1,74 @@
+//! Structural invariants of the simulation pipeline of the default app.
+
+use fmcw_radar_demo0::{App, SimulationResult};
+
+fn default_result() -> SimulationResult {
+    let mut app = App::default();
+    app.update();
+    app.simulation_result()
+}
+
+#[test]
+fn every_chirp_has_its_own_spectrum() {
+    let result = default_result();
+    assert_eq!(
+        result.spectra.len(),
+        result.config.chirps.len(),
+        "one spectrum per chirp"
+    );
+    for (i, a) in result.spectra.iter().enumerate() {
+        for b in result.spectra.iter().skip(i + 1) {
+            assert_ne!(
+                a,
+                b,
+                "chirp {} has the same spectrum as a later chirp",
+                i + 1
+            );
+        }
+    }
+}
+
+#[test]
+fn spectra_have_half_the_window_samples_as_bins() {
+    let result = default_result();
+    let config = &result.config;
+    let samples = (config.sampling_duration * config.sampling_frequency).round() as usize;
+    for (i, spectrum) in result.spectra.iter().enumerate() {
+        assert!(!spectrum.is_empty(), "spectrum of chirp {} is empty", i + 1);
+        assert_eq!(spectrum.len(), samples / 2, "bin count of chirp {}", i + 1);
+    }
+}
+
+#[test]
+fn exported_json_contains_the_beats_of_every_target() {
+    let mut app = App::default();
+    let mut scene = app.scene();
+    if let Some(obj) = scene.objects.first_mut() {
+        obj.extended = true;
+    }
+    app.set_scene(scene);
+    app.update();
+    let result = app.simulation_result();
+    let json = serde_json::to_value(&result).unwrap_or_default();
+    let length = |pointer: &str| {
+        json.pointer(pointer)
+            .and_then(serde_json::Value::as_array)
+            .map_or(0, Vec::len)
+    };
+
+    assert_eq!(
+        length("/beat_freqs"),
+        result.targets.len(),
+        "beat arrays of the targets"
+    );
+    assert_eq!(
+        length("/beat_freqs/0"),
+        result.t.len(),
+        "beats of the first target"
+    );
+    assert_eq!(
+        length("/scatterer_beat_freqs/0"),
+        2,
+        "scatterers of the extended target"
+    );
+}
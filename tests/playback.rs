@@ -0,0 +1,48 @@
+//! Playback moves the objects by their velocity and wraps them around the scene.
+
+use fmcw_radar_demo0::dsp::VelocityConvention;
+use fmcw_radar_demo0::{App, RadarConfig, RadarObject, Scene};
+
+fn app_with(convention: VelocityConvention, range: f64, velocity: f64) -> App {
+    let mut app = App::default();
+    app.set_scene(Scene {
+        config: RadarConfig {
+            velocity_convention: convention,
+            ..RadarConfig::default()
+        },
+        objects: vec![RadarObject::new(
+            range,
+            velocity,
+            egui::Color32::WHITE,
+            true,
+        )],
+    });
+    app
+}
+
+fn range(app: &App) -> Option<f64> {
+    app.scene().objects.first().map(|obj| obj.range)
+}
+
+#[test]
+fn receding_objects_move_away() {
+    let mut app = app_with(VelocityConvention::Receding, 10.0, 5.0);
+    app.advance_objects(2.0);
+    assert_eq!(range(&app), Some(20.0), "range after 2 s at 5 m/s");
+}
+
+#[test]
+fn approaching_convention_moves_positive_velocities_closer() {
+    let mut app = app_with(VelocityConvention::Approaching, 10.0, 5.0);
+    app.advance_objects(1.0);
+    assert_eq!(range(&app), Some(5.0), "range after 1 s at 5 m/s");
+}
+
+#[test]
+fn objects_wrap_around_the_scene() {
+    let mut app = app_with(VelocityConvention::Receding, 3.0, -5.0);
+    app.advance_objects(1.0);
+    assert_eq!(range(&app), Some(98.0), "range after passing the radar");
+    app.advance_objects(-1.0);
+    assert_eq!(range(&app), Some(3.0), "range after going back");
+}
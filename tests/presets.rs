@@ -0,0 +1,31 @@
+//! The presets are valid scenes that the app can load and detect something in.
+
+use fmcw_radar_demo0::{AnalysisSettings, App, Preset, simulate};
+
+#[test]
+fn presets_lie_within_the_sliders() {
+    for preset in Preset::ALL {
+        let scene = preset.scene();
+        let json = serde_json::to_string(&scene).unwrap_or_default();
+        let mut app = App::default();
+        assert!(app.import_scene_json(&json).is_ok(), "import of {json}");
+        // Importing clamps into the slider bounds, so anything outside them would change
+        assert_eq!(app.scene(), scene, "{} after the import", preset.name());
+    }
+}
+
+#[test]
+fn every_chirp_of_every_preset_detects_something() {
+    for preset in Preset::ALL {
+        let scene = preset.scene();
+        let result = simulate(&scene.config, &AnalysisSettings::default(), &scene.objects);
+        for (i, detections) in result.detections.iter().enumerate() {
+            assert!(
+                !detections.is_empty(),
+                "no detections on chirp {} of {}",
+                i + 1,
+                preset.name()
+            );
+        }
+    }
+}
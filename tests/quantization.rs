@@ -0,0 +1,74 @@
+//! ADC quantization rounds to the closest level and saturates beyond full scale.
+
+use fmcw_radar_demo0::dsp::{Window, beat_bins, quantize};
+
+const N: usize = 1024;
+/// Cycles of the test sine in the window, so it sits exactly on a bin. The samples sit half a
+/// sample off the zero crossings, where a 1-bit ADC would have to pick a sign.
+const CYCLES: usize = 16;
+
+fn sine(amplitude: f64) -> Vec<f64> {
+    (0..N)
+        .map(|i| {
+            amplitude
+                * (2.0 * std::f64::consts::PI * CYCLES as f64 * (i as f64 + 0.5) / N as f64).sin()
+        })
+        .collect()
+}
+
+#[test]
+fn one_bit_turns_a_sine_into_a_square_wave_with_odd_harmonics() {
+    let mut signal = sine(1.0);
+    quantize(&mut signal, 1, 1.0);
+    let bins: Vec<f64> = beat_bins(&signal, N, Window::Rectangular)
+        .iter()
+        .map(|bin| bin.norm())
+        .collect();
+    let magnitude = |bin: usize| bins.get(bin).copied().unwrap_or_default();
+    let fundamental = magnitude(CYCLES);
+    // A square wave has the harmonics 1/3, 1/5, 1/7, ... of its fundamental, and no even ones
+    for harmonic in [3, 5, 7] {
+        let ratio = magnitude(harmonic * CYCLES) / fundamental;
+        assert!(
+            (ratio - 1.0 / harmonic as f64).abs() < 0.01,
+            "harmonic {harmonic} is {ratio} of the fundamental"
+        );
+    }
+    for harmonic in [2, 4, 6] {
+        let ratio = magnitude(harmonic * CYCLES) / fundamental;
+        assert!(
+            ratio < 1e-9,
+            "even harmonic {harmonic} is {ratio} of the fundamental"
+        );
+    }
+}
+
+#[test]
+fn error_stays_within_half_a_step() {
+    let clean = sine(0.9);
+    let mut quantized = clean.clone();
+    let (bits, full_scale) = (8, 1.0);
+    quantize(&mut quantized, bits, full_scale);
+    let half_step = full_scale / 2f64.powi(bits);
+    for (i, (q, x)) in quantized.iter().zip(&clean).enumerate() {
+        assert!(
+            (q - x).abs() <= half_step,
+            "sample {i}: {x} quantized to {q}"
+        );
+    }
+}
+
+#[test]
+fn saturates_beyond_full_scale() {
+    let mut signal = vec![-5.0, 5.0];
+    quantize(&mut signal, 2, 1.0);
+    assert_eq!(signal, vec![-0.75, 0.75], "outermost levels of a 2-bit ADC");
+}
+
+#[test]
+fn zero_bits_do_not_quantize() {
+    let clean = sine(3.0);
+    let mut signal = clean.clone();
+    quantize(&mut signal, 0, 1.0);
+    assert_eq!(signal, clean, "signal after quantizing to 0 bits");
+}
@@ -0,0 +1,47 @@
+//! Random scenes are reproducible from their seed and stay within the bounds of the sliders.
+
+use fmcw_radar_demo0::App;
+
+fn random_scene(count: usize, seed: u64) -> App {
+    let mut app = App::default();
+    app.randomize_scene(count, seed);
+    app
+}
+
+#[test]
+fn same_seed_draws_the_same_scene() {
+    assert_eq!(
+        random_scene(20, 7).scene(),
+        random_scene(20, 7).scene(),
+        "scenes from seed 7"
+    );
+    assert_ne!(
+        random_scene(20, 7).scene(),
+        random_scene(20, 8).scene(),
+        "scenes from seeds 7 and 8"
+    );
+}
+
+#[test]
+fn objects_lie_within_the_sliders() {
+    let app = random_scene(100, 1);
+    let objects = app.scene().objects;
+    assert_eq!(objects.len(), 100, "object count");
+    // The range and velocity sliders span 0 to 100 m and ±50 m/s
+    assert!(
+        objects.iter().all(|obj| obj.enabled
+            && (0.0..=100.0).contains(&obj.range)
+            && (-50.0..=50.0).contains(&obj.velocity)),
+        "objects outside the sliders"
+    );
+}
+
+#[test]
+fn random_scene_is_simulated() {
+    let result = random_scene(10, 3).simulation_result();
+    assert_eq!(result.targets.len(), 10, "simulated targets");
+    assert!(
+        result.targets.iter().all(|obj| !obj.beat_freqs.is_empty()),
+        "beats of every target"
+    );
+}
@@ -0,0 +1,75 @@
+//! The range-Doppler map puts a target at its beat bin and the bin of its chirp-to-chirp phase
+//! advance.
+
+use fmcw_radar_demo0::dsp::{Window, range_doppler};
+
+const SAMPLES: usize = 128;
+const CHIRPS: usize = 32;
+
+/// Frames of a tone in beat bin `beat_bin` whose phase advances by `doppler_bin / CHIRPS` cycles
+/// from one frame to the next.
+fn frames(beat_bin: f64, doppler_bin: f64) -> Vec<Vec<f64>> {
+    (0..CHIRPS)
+        .map(|k| {
+            (0..SAMPLES)
+                .map(|i| {
+                    let cycles = beat_bin * i as f64 / SAMPLES as f64
+                        + doppler_bin * k as f64 / CHIRPS as f64;
+                    (2.0 * std::f64::consts::PI * cycles).cos()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// `(row, column)` of the strongest cell.
+fn strongest(map: &[Vec<f64>]) -> Option<(usize, usize)> {
+    map.iter()
+        .enumerate()
+        .flat_map(|(row, mags)| {
+            mags.iter()
+                .enumerate()
+                .map(move |(col, &mag)| (row, col, mag))
+        })
+        .max_by(|a, b| a.2.total_cmp(&b.2))
+        .map(|(row, col, _)| (row, col))
+}
+
+#[test]
+fn map_has_a_row_per_chirp_and_a_column_per_beat_bin() {
+    let map = range_doppler(&frames(10.0, 0.0), Window::Hann);
+    assert_eq!(map.len(), CHIRPS, "Doppler bins");
+    assert!(
+        map.iter().all(|row| row.len() == SAMPLES / 2),
+        "beat bins per row"
+    );
+}
+
+#[test]
+fn moving_targets_land_at_their_doppler_bin() {
+    for (beat_bin, doppler_bin) in [(10.0, 0.0), (20.0, 5.0), (40.0, -7.0)] {
+        let map = range_doppler(&frames(beat_bin, doppler_bin), Window::Hann);
+        // The rows start at -CHIRPS/2
+        let row = (doppler_bin + (CHIRPS / 2) as f64) as usize;
+        assert_eq!(
+            strongest(&map),
+            Some((row, beat_bin as usize)),
+            "target in beat bin {beat_bin}, Doppler bin {doppler_bin}"
+        );
+    }
+}
+
+#[test]
+fn map_is_normalized_to_its_peak() {
+    let map = range_doppler(&frames(12.0, 3.0), Window::Rectangular);
+    let peak = map.iter().flatten().fold(0.0_f64, |max, &mag| max.max(mag));
+    assert!((peak - 1.0).abs() < 1e-12, "peak of {peak}");
+}
+
+#[test]
+fn no_frames_give_an_empty_map() {
+    assert!(
+        range_doppler(&[], Window::Hann).is_empty(),
+        "map without frames"
+    );
+}
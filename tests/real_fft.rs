@@ -0,0 +1,56 @@
+//! `fftspectrum` runs a real-to-complex FFT; it must match the full complex FFT of the signal.
+
+use fmcw_radar_demo0::dsp::{Complex, Normalization, Window, fftspectrum};
+
+const SAMPLING_RATE: f64 = 1000.0;
+
+/// Two tones and a ramp, so no bin is trivially zero.
+fn signal(n: usize) -> Vec<f64> {
+    (0..n)
+        .map(|i| {
+            let t = i as f64 / SAMPLING_RATE;
+            (2.0 * std::f64::consts::PI * 123.4 * t).sin()
+                + 0.3 * (2.0 * std::f64::consts::PI * 321.0 * t).cos()
+                + 0.01 * i as f64
+        })
+        .collect()
+}
+
+/// Single-sided magnitudes of the complex FFT of `signal`.
+fn complex_magnitudes(signal: &[f64]) -> Vec<f64> {
+    let mut buffer: Vec<Complex<f64>> = signal.iter().map(|&x| Complex::new(x, 0.0)).collect();
+    rustfft::FftPlanner::<f64>::new()
+        .plan_fft_forward(buffer.len())
+        .process(&mut buffer);
+    buffer
+        .iter()
+        .take(signal.len() / 2)
+        .map(|c| c.norm())
+        .collect()
+}
+
+#[test]
+fn matches_the_complex_fft_for_even_and_odd_lengths() {
+    for n in [1024, 1000, 999] {
+        let signal = signal(n);
+        let real = fftspectrum(
+            &signal,
+            SAMPLING_RATE,
+            n,
+            Window::Rectangular,
+            Normalization::None,
+        );
+        let complex = complex_magnitudes(&signal);
+        assert_eq!(real.len(), complex.len(), "n = {n}: bin count");
+        for (i, (&(freq, mag), expected)) in real.iter().zip(complex).enumerate() {
+            assert!(
+                (freq - i as f64 * SAMPLING_RATE / n as f64).abs() < 1e-9,
+                "n = {n}: bin {i} at {freq} Hz"
+            );
+            assert!(
+                (mag - expected).abs() < 1e-9 * expected.max(1.0),
+                "n = {n}: bin {i} has {mag}, expected {expected}"
+            );
+        }
+    }
+}
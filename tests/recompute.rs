@@ -0,0 +1,38 @@
+//! The frame loop only reruns the simulation when something it depends on changed.
+
+use fmcw_radar_demo0::{App, Preset};
+
+#[test]
+fn unchanged_state_is_not_recomputed() {
+    let mut app = App::default();
+    assert!(app.update_if_changed(), "first frame");
+    assert!(!app.update_if_changed(), "second frame without changes");
+}
+
+#[test]
+fn changed_scene_is_recomputed() {
+    let mut app = App::default();
+    app.update_if_changed();
+    app.set_scene(Preset::TwoCloseTargets.scene());
+    assert!(app.update_if_changed(), "frame after loading a preset");
+    let mut fresh = App::default();
+    fresh.set_scene(Preset::TwoCloseTargets.scene());
+    fresh.update();
+    assert_eq!(
+        app.simulation_result(),
+        fresh.simulation_result(),
+        "results of the skipping and the unconditional update"
+    );
+}
+
+#[test]
+fn moving_objects_are_recomputed() {
+    let mut app = App::default();
+    app.update_if_changed();
+    app.advance_objects(0.1);
+    assert!(
+        app.update_if_changed(),
+        "frame after playback moved the objects"
+    );
+    assert!(!app.update_if_changed(), "frame after that without changes");
+}
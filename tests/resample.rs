@@ -0,0 +1,41 @@
+//! Interpolation of spectra onto a common frequency axis.
+
+use fmcw_radar_demo0::dsp::resample_spectrum;
+
+fn assert_close(actual: &[f64], expected: &[f64]) {
+    assert_eq!(actual.len(), expected.len(), "length of {actual:?}");
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-12, "{actual:?} != {expected:?}");
+    }
+}
+
+#[test]
+fn samples_on_the_bins_are_kept() {
+    let spectrum = [(0.0, 1.0), (10.0, 3.0), (20.0, 2.0)];
+    assert_close(
+        &resample_spectrum(&spectrum, &[0.0, 10.0, 20.0]),
+        &[1.0, 3.0, 2.0],
+    );
+}
+
+#[test]
+fn linear_spectrum_is_reproduced_between_bins() {
+    let spectrum: Vec<(f64, f64)> = (0..=10).map(|i| (i as f64, 2.0 * i as f64 + 1.0)).collect();
+    let axis: Vec<f64> = (0..=40).map(|i| i as f64 * 0.25).collect();
+    let expected: Vec<f64> = axis.iter().map(|f| 2.0 * f + 1.0).collect();
+    assert_close(&resample_spectrum(&spectrum, &axis), &expected);
+}
+
+#[test]
+fn frequencies_outside_the_spectrum_are_zero() {
+    let spectrum = [(10.0, 1.0), (20.0, 2.0)];
+    assert_close(
+        &resample_spectrum(&spectrum, &[0.0, 9.99, 15.0, 20.01, 100.0]),
+        &[0.0, 0.0, 1.5, 0.0, 0.0],
+    );
+}
+
+#[test]
+fn empty_spectrum_is_zero_everywhere() {
+    assert_close(&resample_spectrum(&[], &[0.0, 1.0]), &[0.0, 0.0]);
+}
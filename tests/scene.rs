@@ -0,0 +1,69 @@
+//! Scenes saved to and loaded from JSON files.
+
+use fmcw_radar_demo0::{App, Chirp, RadarConfig, RadarObject, Scene, Waveform};
+
+#[test]
+fn config_round_trips_through_json() {
+    let config = RadarConfig {
+        bandwidth: 0.8e9,
+        chirps: vec![Chirp::new(10e-6), Chirp::new(33e-6)],
+        sweep_nonlinearity: 0.01,
+        waveform: Waveform::Triangle,
+        ..RadarConfig::default()
+    };
+    let json = serde_json::to_string(&config).unwrap_or_default();
+    let restored: Result<RadarConfig, _> = serde_json::from_str(&json);
+    assert_eq!(restored.ok(), Some(config), "config after the round trip");
+}
+
+#[test]
+fn app_scene_round_trips_through_import() {
+    let app = App::default();
+    let json = serde_json::to_string(&app.scene()).unwrap_or_default();
+    let mut imported = App::default();
+    imported.set_scene(Scene::default());
+    assert!(
+        imported.import_scene_json(&json).is_ok(),
+        "import of {json}"
+    );
+    assert_eq!(imported.scene(), app.scene(), "scene after the round trip");
+}
+
+#[test]
+fn import_clamps_into_the_slider_bounds() {
+    let scene = Scene {
+        config: RadarConfig {
+            carrier_frequency: 1e12,
+            chirps: vec![Chirp::new(0.0)],
+            rx_elements: 0,
+            ..RadarConfig::default()
+        },
+        objects: vec![RadarObject::new(1e4, -1e3, egui::Color32::WHITE, true)],
+    };
+    let json = serde_json::to_string(&scene).unwrap_or_default();
+    let mut app = App::default();
+    assert!(app.import_scene_json(&json).is_ok(), "import of {json}");
+
+    let imported = app.scene();
+    assert_eq!(
+        imported.config.carrier_frequency, 100e9,
+        "carrier frequency"
+    );
+    assert_eq!(
+        imported.config.chirps,
+        vec![Chirp::new(1e-6)],
+        "chirp durations"
+    );
+    assert_eq!(imported.config.rx_elements, 1, "RX elements");
+    let obj = imported.objects.first();
+    assert_eq!(obj.map(|obj| obj.range), Some(100.0), "range");
+    assert_eq!(obj.map(|obj| obj.velocity), Some(-50.0), "velocity");
+}
+
+#[test]
+fn invalid_json_is_rejected() {
+    let mut app = App::default();
+    let before = app.scene();
+    assert!(app.import_scene_json("{ not json").is_err(), "invalid JSON");
+    assert_eq!(app.scene(), before, "scene after a failed import");
+}
@@ -0,0 +1,130 @@
+//! The pure signal model behind the app: the sweep, the beats it produces and their samples.
+
+use fmcw_radar_demo0::dsp::{
+    SPEED_OF_LIGHT, sample_array_signal, sample_noisy_signal, sample_signal,
+};
+use fmcw_radar_demo0::{Chirp, RadarConfig, beat_frequencies, saw};
+
+fn config() -> RadarConfig {
+    RadarConfig {
+        chirps: vec![Chirp::new(10e-6), Chirp::new(20e-6)],
+        ..RadarConfig::default()
+    }
+}
+
+/// Times from 0 to `duration` seconds, `step` seconds apart.
+fn times(duration: f64, step: f64) -> Vec<f64> {
+    (0..)
+        .map(|i| i as f64 * step)
+        .take_while(|&t| t < duration)
+        .collect()
+}
+
+#[test]
+fn saw_ramps_through_each_chirp_and_wraps() {
+    let config = config();
+    // Away from the chirp boundaries, where rounding could pick either chirp
+    let ramp = saw(&[0.0, 5e-6, 12e-6, 20e-6, 32e-6, 35e-6], &config);
+    let expected = [0.0, 0.5, 0.1, 0.5, 0.2, 0.5];
+    for (value, expected) in ramp.iter().zip(expected) {
+        assert!(
+            (value - expected).abs() < 1e-9,
+            "ramp {value}, expected {expected}"
+        );
+    }
+}
+
+#[test]
+fn saw_falls_on_falling_chirps() {
+    let config = RadarConfig {
+        chirps: vec![
+            Chirp::new(10e-6),
+            Chirp {
+                falling: true,
+                ..Chirp::new(20e-6)
+            },
+        ],
+        ..config()
+    };
+    let ramp = saw(&[2.5e-6, 15e-6], &config);
+    assert!(
+        ramp.first().is_some_and(|&r| (r - 0.25).abs() < 1e-9),
+        "rising chirp at {ramp:?}"
+    );
+    assert!(
+        ramp.get(1).is_some_and(|&r| (r - 0.75).abs() < 1e-9),
+        "falling chirp at {ramp:?}"
+    );
+}
+
+#[test]
+fn stationary_beat_is_the_slope_times_the_delay() {
+    let config = config();
+    let t = times(30e-6, 0.1e-6);
+    let f: Vec<f64> = saw(&t, &config)
+        .iter()
+        .map(|&s| config.sweep_frequency(s))
+        .collect();
+    let range = 15.0;
+    let beats = beat_frequencies(&t, &f, range, 0.0, &config);
+    let delay = 2.0 * range / SPEED_OF_LIGHT;
+    // Well inside both chirps, away from the reset of the ramp
+    for (time, chirp) in [(5e-6, 10e-6), (20e-6, 20e-6)] {
+        let idx = t
+            .iter()
+            .position(|&ti| ti >= time)
+            .expect("time inside the simulation");
+        let expected = -config.bandwidth / chirp * delay;
+        let beat = beats.get(idx).copied().unwrap_or(f64::NAN);
+        assert!(
+            (beat - expected).abs() < 1e-3 * expected.abs(),
+            "beat {beat} Hz at {time} s, expected {expected} Hz"
+        );
+    }
+}
+
+#[test]
+fn sampled_signal_sums_the_reflections() {
+    let t = [0.0, 0.25, 0.5];
+    let signal = sample_signal(&t, &[(1.0, 2.0), (0.5, 1.0)]);
+    // 2·sin(2πt) + sin(πt)
+    let expected = [0.0, 2.0 + (0.25 * std::f64::consts::PI).sin(), 1.0];
+    for (value, expected) in signal.iter().zip(expected) {
+        assert!(
+            (value - expected).abs() < 1e-12,
+            "{value}, expected {expected}"
+        );
+    }
+}
+
+#[test]
+fn noisy_signal_without_phase_noise_is_the_clean_one() {
+    let t = times(1.0, 0.01);
+    let reflections = [(3.0, 1.0), (7.0, 0.5)];
+    let noisy = sample_noisy_signal(&t, &reflections, &vec![0.0; t.len()]);
+    assert_eq!(noisy, sample_signal(&t, &reflections), "zero phase");
+}
+
+#[test]
+fn array_signal_delays_the_echo_across_the_elements() {
+    let t = times(1.0, 0.01);
+    let channels = sample_array_signal(&t, &[(5.0, 1.0, 30.0)], 3, 0.5);
+    assert_eq!(channels.len(), 3, "one signal per element");
+    assert_eq!(
+        channels.first(),
+        Some(&sample_signal(&t, &[(5.0, 1.0)])),
+        "first element"
+    );
+    // sin 30° = 1/2, so every element is a quarter cycle further along, 1/20 s at 5 Hz
+    let lag = 0.05;
+    for (element, channel) in channels.iter().enumerate() {
+        let shifted: Vec<f64> = t.iter().map(|&t| t + element as f64 * lag).collect();
+        let expected = sample_signal(&shifted, &[(5.0, 1.0)]);
+        for (value, expected) in channel.iter().zip(expected) {
+            assert!(
+                (value - expected).abs() < 1e-9,
+                "element {element}: {value}, expected {expected}"
+            );
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! The spectrogram follows a beat that changes over time, one frame per hop.
+
+use fmcw_radar_demo0::dsp::{Normalization, Window, spectrogram};
+
+const SAMPLING_RATE: f64 = 1024.0;
+const WINDOW: usize = 64;
+
+/// Index of the strongest bin of `frame`.
+fn peak_bin(frame: &[f64]) -> Option<usize> {
+    frame
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(bin, _)| bin)
+}
+
+/// A tone at bin 8 of the window for the first half of `n` samples and at bin 20 for the rest.
+fn stepped_tone(n: usize) -> Vec<f64> {
+    (0..n)
+        .map(|i| {
+            let bin = if i < n / 2 { 8.0 } else { 20.0 };
+            let frequency = bin * SAMPLING_RATE / WINDOW as f64;
+            (2.0 * std::f64::consts::PI * frequency * i as f64 / SAMPLING_RATE).sin()
+        })
+        .collect()
+}
+
+#[test]
+fn one_frame_per_hop_and_half_a_window_of_bins() {
+    let map = spectrogram(
+        &stepped_tone(1000),
+        SAMPLING_RATE,
+        WINDOW,
+        16,
+        Window::Hann,
+        Normalization::default(),
+    );
+    assert_eq!(map.len(), 1000usize.div_ceil(16), "frames");
+    assert!(
+        map.iter().all(|frame| frame.len() == WINDOW / 2),
+        "bins per frame"
+    );
+}
+
+#[test]
+fn frames_follow_the_beat() {
+    let map = spectrogram(
+        &stepped_tone(1024),
+        SAMPLING_RATE,
+        WINDOW,
+        32,
+        Window::Hann,
+        Normalization::default(),
+    );
+    assert_eq!(map.get(8).and_then(|f| peak_bin(f)), Some(8), "early frame");
+    assert_eq!(
+        map.get(24).and_then(|f| peak_bin(f)),
+        Some(20),
+        "late frame"
+    );
+}
+
+#[test]
+fn edge_frames_are_zero_padded() {
+    let map = spectrogram(
+        &stepped_tone(1024),
+        SAMPLING_RATE,
+        WINDOW,
+        32,
+        Window::Rectangular,
+        Normalization::default(),
+    );
+    // The first window is centered on the first sample, so only its second half holds the tone
+    let edge = map.first().and_then(|f| f.get(8)).copied().unwrap_or(0.0);
+    let inner = map.get(8).and_then(|f| f.get(8)).copied().unwrap_or(0.0);
+    assert!(
+        (edge - inner / 2.0).abs() < 1e-9,
+        "edge frame {edge} against half of {inner}"
+    );
+}
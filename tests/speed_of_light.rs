@@ -0,0 +1,68 @@
+//! Ranges are derived from the round-trip delay of the echo, so they hinge on the speed of light.
+
+use fmcw_radar_demo0::dsp::{SPEED_OF_LIGHT, beat_per_meter, range_for_beat};
+use fmcw_radar_demo0::{App, Chirp, RadarConfig, RadarObject, Scene};
+
+const CHIRP: f64 = 40e-6;
+const BANDWIDTH: f64 = 1.6e9;
+/// Round-trip delay of the echo.
+const DELAY: f64 = 200e-9;
+/// Beat of an echo delayed by `DELAY`, i.e. `BANDWIDTH / CHIRP · DELAY`.
+const BEAT: f64 = 8e6;
+
+fn range() -> f64 {
+    DELAY * SPEED_OF_LIGHT / 2.0
+}
+
+#[test]
+fn speed_of_light_is_exact() {
+    assert_eq!(SPEED_OF_LIGHT, 299_792_458.0, "speed of light in m/s");
+}
+
+#[test]
+fn round_trip_delay_gives_the_expected_beat() {
+    let beat = beat_per_meter(CHIRP, BANDWIDTH) * range();
+    assert!((beat - BEAT).abs() < 1e-6, "beat of {beat} Hz");
+    let range_back = range_for_beat(BEAT, CHIRP, BANDWIDTH);
+    assert!(
+        (range_back - range()).abs() < 1e-9,
+        "range of {range_back} m"
+    );
+}
+
+#[test]
+fn simulated_target_beats_at_the_delay() {
+    let mut app = App::default();
+    app.set_scene(Scene {
+        config: RadarConfig {
+            bandwidth: BANDWIDTH,
+            chirps: vec![Chirp::new(CHIRP)],
+            ..RadarConfig::default()
+        },
+        objects: vec![RadarObject::new(range(), 0.0, egui::Color32::WHITE, true)],
+    });
+    app.update();
+    let result = app.simulation_result();
+    // Halfway through the chirp, well clear of the ramp's reset
+    let idx = result
+        .t
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| {
+            (*a - CHIRP / 2.0)
+                .abs()
+                .total_cmp(&(*b - CHIRP / 2.0).abs())
+        })
+        .map_or(0, |(i, _)| i);
+    let beat = result
+        .targets
+        .first()
+        .and_then(|target| target.beat_freqs.get(idx))
+        .copied();
+    // The echo lags behind the rising ramp, so its beat is negative
+    assert!(
+        beat.is_some_and(|beat| (beat + BEAT).abs() < 1.0),
+        "beat of {beat:?} Hz, expected {} Hz",
+        -BEAT
+    );
+}
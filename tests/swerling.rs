@@ -0,0 +1,87 @@
+//! Fluctuating targets draw their cross section from the distribution of their Swerling model.
+
+use fmcw_radar_demo0::{AnalysisSettings, RadarConfig, RadarObject, SwerlingModel, simulate};
+use rand::SeedableRng as _;
+
+const DRAWS: usize = 100_000;
+
+/// Mean and variance of `DRAWS` cross sections of `model` around `rcs`.
+fn moments(model: SwerlingModel, rcs: f64) -> (f64, f64) {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    let draws: Vec<f64> = (0..DRAWS)
+        .map(|_| model.sample_rcs(rcs, &mut rng))
+        .collect();
+    let mean = draws.iter().sum::<f64>() / DRAWS as f64;
+    let variance = draws.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / DRAWS as f64;
+    (mean, variance)
+}
+
+#[test]
+fn draws_average_to_the_set_cross_section() {
+    for model in SwerlingModel::ALL {
+        let (mean, _) = moments(model, 5.0);
+        assert!(
+            (mean - 5.0).abs() < 0.03 * 5.0,
+            "mean {mean} m² of {}",
+            model.name()
+        );
+    }
+}
+
+#[test]
+fn a_dominant_scatterer_fluctuates_less() {
+    // The variance over the squared mean is 1/k for the chi-square distribution with 2k degrees
+    // of freedom
+    for (model, expected) in [
+        (SwerlingModel::Zero, 0.0),
+        (SwerlingModel::One, 1.0),
+        (SwerlingModel::Two, 1.0),
+        (SwerlingModel::Three, 0.5),
+        (SwerlingModel::Four, 0.5),
+    ] {
+        let (mean, variance) = moments(model, 2.0);
+        let spread = variance / (mean * mean);
+        assert!(
+            (spread - expected).abs() < 0.05,
+            "variance over squared mean {spread} of {}, expected {expected}",
+            model.name()
+        );
+    }
+}
+
+#[test]
+fn only_swerling_two_and_four_draw_per_chirp() {
+    let per_chirp: Vec<bool> = SwerlingModel::ALL
+        .into_iter()
+        .map(SwerlingModel::per_chirp)
+        .collect();
+    assert_eq!(
+        per_chirp,
+        [false, false, true, false, true],
+        "per-chirp draws of 0, I, II, III and IV"
+    );
+}
+
+/// Spectra of a single target fluctuating by `model`.
+fn spectra_with(model: SwerlingModel) -> Vec<Vec<(f64, f64)>> {
+    let object = RadarObject {
+        swerling: model,
+        ..RadarObject::new(20.0, 0.0, egui::Color32::WHITE, true)
+    };
+    simulate(
+        &RadarConfig::default(),
+        &AnalysisSettings::default(),
+        &[object],
+    )
+    .spectra
+}
+
+#[test]
+fn fluctuating_echoes_are_reproducible() {
+    let steady = spectra_with(SwerlingModel::Zero);
+    for model in [SwerlingModel::One, SwerlingModel::Two] {
+        let spectra = spectra_with(model);
+        assert_eq!(spectra, spectra_with(model), "{} twice", model.name());
+        assert_ne!(spectra, steady, "{} against a steady target", model.name());
+    }
+}
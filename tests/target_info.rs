@@ -0,0 +1,35 @@
+//! The per-target CSV export of the true and derived parameters.
+
+use fmcw_radar_demo0::{App, RadarObject};
+
+#[test]
+fn every_row_lists_the_rcs_of_its_object() {
+    let mut app = App::default();
+    let mut scene = app.scene();
+    scene.objects = [(20.0, 0.5), (40.0, 10.0)]
+        .into_iter()
+        .map(|(range, rcs)| RadarObject {
+            rcs,
+            ..RadarObject::new(range, 0.0, egui::Color32::WHITE, true)
+        })
+        .collect();
+    app.set_scene(scene);
+    app.update();
+    let csv = app.target_info_csv();
+    let mut lines = csv.lines();
+
+    let header: Vec<&str> = lines.next().unwrap_or_default().split(',').collect();
+    let column = |name: &str| header.iter().position(|&column| column == name);
+    assert_eq!(column("rcs_m2"), Some(3), "RCS column in {header:?}");
+
+    let rows: Vec<Vec<&str>> = lines.map(|line| line.split(',').collect()).collect();
+    assert_eq!(rows.len(), 2 * app.scene().config.chirps.len(), "rows");
+    for row in rows {
+        let expected = match row.first() {
+            Some(&"1") => Some("0.5"),
+            Some(&"2") => Some("10"),
+            _ => None,
+        };
+        assert_eq!(row.get(3).copied(), expected, "RCS in {row:?}");
+    }
+}
@@ -0,0 +1,83 @@
+//! The phase of a beat bin across repeated chirps gives the velocity of the target in it.
+
+use fmcw_radar_demo0::dsp::{
+    DopplerModel, VelocityConvention, Window, doppler_per_chirp, doppler_shift,
+    velocity_for_doppler,
+};
+
+const SAMPLES: usize = 128;
+const CHIRPS: usize = 32;
+
+/// Frames of tones at `(beat bin, Doppler shift in cycles per chirp)`.
+fn frames(tones: &[(f64, f64)]) -> Vec<Vec<f64>> {
+    (0..CHIRPS)
+        .map(|k| {
+            (0..SAMPLES)
+                .map(|i| {
+                    tones
+                        .iter()
+                        .map(|&(beat_bin, doppler)| {
+                            let cycles = beat_bin * i as f64 / SAMPLES as f64 + doppler * k as f64;
+                            (2.0 * std::f64::consts::PI * cycles).cos()
+                        })
+                        .sum()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn doppler_is_found_between_the_bins() {
+    for doppler_bin in [0.0, 5.0, -7.0, 3.4, -12.7] {
+        let doppler = doppler_bin / CHIRPS as f64;
+        let estimate = doppler_per_chirp(&frames(&[(20.0, doppler)]), &[20], SAMPLES, Window::Hann);
+        assert!(
+            estimate
+                .first()
+                .is_some_and(|&d| (d - doppler).abs() < 0.1 / CHIRPS as f64),
+            "estimate {estimate:?}, expected {doppler} cycles per chirp"
+        );
+    }
+}
+
+#[test]
+fn every_bin_gets_the_doppler_of_its_own_target() {
+    let tones = [(10.0, 4.0 / CHIRPS as f64), (40.0, -9.0 / CHIRPS as f64)];
+    let estimate = doppler_per_chirp(&frames(&tones), &[10, 40], SAMPLES, Window::Hann);
+    assert_eq!(estimate.len(), 2, "one estimate per bin");
+    for (&d, &(_, doppler)) in estimate.iter().zip(&tones) {
+        assert!(
+            (d - doppler).abs() < 0.1 / CHIRPS as f64,
+            "estimate {d}, expected {doppler} cycles per chirp"
+        );
+    }
+}
+
+#[test]
+fn velocity_round_trips_through_the_chirp_phase() {
+    let (carrier, chirp) = (77e9, 40e-6);
+    let convention = VelocityConvention::default();
+    let model = DopplerModel::default();
+    for velocity in [-15.0, 0.0, 8.0] {
+        let doppler = doppler_shift(carrier, velocity, convention, model) * chirp;
+        let estimate = doppler_per_chirp(&frames(&[(30.0, doppler)]), &[30], SAMPLES, Window::Hann);
+        let estimate = estimate
+            .first()
+            .map(|&d| velocity_for_doppler(d / chirp, carrier, convention, model));
+        // A tenth of the velocity resolution λ/2MTc
+        let tolerance = 0.1 * 3e8 / carrier / (2.0 * CHIRPS as f64 * chirp);
+        assert!(
+            estimate.is_some_and(|v| (v - velocity).abs() < tolerance),
+            "estimate {estimate:?}, expected {velocity} m/s"
+        );
+    }
+}
+
+#[test]
+fn no_frames_give_no_estimates() {
+    assert!(
+        doppler_per_chirp(&[], &[3], SAMPLES, Window::Hann).is_empty(),
+        "estimates without frames"
+    );
+}
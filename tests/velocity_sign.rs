@@ -0,0 +1,64 @@
+//! One sign convention from the velocity slider through the Doppler shift to the beat.
+//!
+//! On a rising chirp the echo lags the transmitted frequency by the range, so the signed beat
+//! `f_rx - f_tx` is negative. Approaching targets shift the echo up, which raises the signed beat
+//! towards zero, and receding ones lower it.
+
+use fmcw_radar_demo0::dsp::{DopplerModel, VelocityConvention};
+use fmcw_radar_demo0::{AnalysisSettings, Chirp, RadarConfig, RadarObject, simulate};
+
+const CHIRP: f64 = 40e-6;
+const RANGE: f64 = 30.0;
+const SPEED: f64 = 30.0;
+
+/// Signed beat halfway through a rising chirp of a target at `velocity` m/s.
+fn mid_chirp_beat(convention: VelocityConvention, model: DopplerModel, velocity: f64) -> f64 {
+    let config = RadarConfig {
+        chirps: vec![Chirp::new(CHIRP)],
+        velocity_convention: convention,
+        doppler_model: model,
+        ..RadarConfig::default()
+    };
+    let object = RadarObject::new(RANGE, velocity, egui::Color32::WHITE, true);
+    let result = simulate(&config, &AnalysisSettings::default(), &[object]);
+    let idx = result.t.partition_point(|&t| t < CHIRP / 2.0);
+    result
+        .targets
+        .first()
+        .and_then(|target| target.beat_freqs.get(idx))
+        .copied()
+        .unwrap_or(f64::NAN)
+}
+
+#[test]
+fn approaching_target_raises_the_beat_on_a_rising_chirp() {
+    for model in DopplerModel::ALL {
+        for convention in VelocityConvention::ALL {
+            let approaching = convention.receding(-SPEED);
+            let stationary = mid_chirp_beat(convention, model, 0.0);
+            let beat = mid_chirp_beat(convention, model, approaching);
+            assert!(stationary < 0.0, "stationary beat {stationary} Hz");
+            assert!(
+                beat > stationary,
+                "approaching at {approaching} m/s under {convention:?} and {model:?}: \
+                 {beat} Hz, stationary {stationary} Hz"
+            );
+        }
+    }
+}
+
+#[test]
+fn receding_target_lowers_the_beat_on_a_rising_chirp() {
+    for model in DopplerModel::ALL {
+        for convention in VelocityConvention::ALL {
+            let receding = convention.receding(SPEED);
+            let stationary = mid_chirp_beat(convention, model, 0.0);
+            let beat = mid_chirp_beat(convention, model, receding);
+            assert!(
+                beat < stationary,
+                "receding at {receding} m/s under {convention:?} and {model:?}: \
+                 {beat} Hz, stationary {stationary} Hz"
+            );
+        }
+    }
+}
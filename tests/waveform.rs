@@ -0,0 +1,54 @@
+//! Ramp shapes of the frequency sweep within a chirp.
+
+use fmcw_radar_demo0::Waveform;
+
+const STEPS: usize = 1000;
+
+fn progress(i: usize) -> f64 {
+    i as f64 / STEPS as f64
+}
+
+#[test]
+fn triangle_is_symmetric_within_a_period() {
+    for i in 0..=STEPS {
+        let p = progress(i);
+        let rising = Waveform::Triangle.ramp(p);
+        let falling = Waveform::Triangle.ramp(1.0 - p);
+        assert!(
+            (rising - falling).abs() < 1e-12,
+            "ramp({p}) = {rising} but ramp({}) = {falling}",
+            1.0 - p
+        );
+    }
+    assert_eq!(Waveform::Triangle.ramp(0.0), 0.0, "start of the chirp");
+    assert_eq!(Waveform::Triangle.ramp(0.5), 1.0, "turnaround");
+}
+
+#[test]
+fn triangle_is_continuous_across_the_period_wrap() {
+    // A triangle swept twice in a row, with the second period starting where the first ends
+    let values: Vec<f64> = (0..2 * STEPS)
+        .map(|i| Waveform::Triangle.ramp(progress(i % STEPS)))
+        .collect();
+    // The ramp sweeps the bandwidth twice per period, so a step moves it by 2/STEPS at most
+    let max_step = 2.0 / STEPS as f64 + 1e-12;
+    for (i, pair) in values.windows(2).enumerate() {
+        if let [a, b] = pair {
+            assert!((b - a).abs() <= max_step, "jump of {} at step {i}", b - a);
+        }
+    }
+}
+
+#[test]
+fn sawtooth_is_the_identity_within_a_period() {
+    for i in 0..=STEPS {
+        let p = progress(i);
+        assert_eq!(Waveform::Sawtooth.ramp(p), p, "ramp({p})");
+    }
+}
+
+#[test]
+fn triangle_ramps_over_half_the_chirp() {
+    assert_eq!(Waveform::Sawtooth.ramp_duration(10e-6), 10e-6, "sawtooth");
+    assert_eq!(Waveform::Triangle.ramp_duration(10e-6), 5e-6, "triangle");
+}
@@ -0,0 +1,131 @@
+//! Averaging the spectra of overlapping segments (Welch's method) steadies the noise floor
+//! without changing the height of a tone.
+
+use fmcw_radar_demo0::dsp::{
+    Normalization, Window, add_white_noise, fftspectrum, noise_floor, range_for_beat,
+    welch_spectrum,
+};
+use fmcw_radar_demo0::{AnalysisSettings, RadarConfig, RadarObject, simulate};
+use rand::SeedableRng as _;
+
+const N: usize = 4096;
+const SEGMENT: usize = 256;
+const SAMPLING_RATE: f64 = 256.0;
+
+/// `N` samples of a unit sine at `frequency` Hz.
+fn sine(frequency: f64) -> Vec<f64> {
+    (0..N)
+        .map(|i| (2.0 * std::f64::consts::PI * frequency * i as f64 / SAMPLING_RATE).sin())
+        .collect()
+}
+
+fn welch(signal: &[f64]) -> Vec<(f64, f64)> {
+    welch_spectrum(
+        signal,
+        SAMPLING_RATE,
+        SEGMENT,
+        0.5,
+        Window::Hann,
+        Normalization::Amplitude,
+    )
+}
+
+#[test]
+fn averaging_keeps_the_tone_height() {
+    let spectrum = welch(&sine(16.0));
+    assert_eq!(spectrum.len(), SEGMENT / 2, "bins of one segment");
+    let peak = spectrum.iter().map(|&(_, mag)| mag).fold(0.0, f64::max);
+    assert!((peak - 1.0).abs() < 1e-2, "peak {peak}");
+    let freq = spectrum
+        .iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(0.0, |&(freq, _)| freq);
+    assert_eq!(freq, 16.0, "frequency of the peak");
+}
+
+#[test]
+fn short_signal_is_one_padded_segment() {
+    let signal: Vec<f64> = sine(16.0).into_iter().take(SEGMENT / 2).collect();
+    let expected = fftspectrum(
+        &signal,
+        SAMPLING_RATE,
+        SEGMENT,
+        Window::Hann,
+        Normalization::Amplitude,
+    );
+    assert_eq!(welch(&signal), expected, "spectrum of the padded signal");
+}
+
+/// Mean and variance of `values`.
+fn mean_and_variance(values: &[f64]) -> (f64, f64) {
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / values.len() as f64;
+    (mean, variance)
+}
+
+#[test]
+fn averaging_steadies_the_noise_floor_estimate() {
+    // The noise floor of 40 noisy realizations, from one segment and from all of them
+    let (single, averaged): (Vec<f64>, Vec<f64>) = (0..40)
+        .map(|seed| {
+            let mut signal = sine(16.0);
+            add_white_noise(
+                &mut signal,
+                0.0,
+                &mut rand::rngs::StdRng::seed_from_u64(seed),
+            );
+            let first = signal.get(..SEGMENT).unwrap_or(&signal);
+            let single = fftspectrum(
+                first,
+                SAMPLING_RATE,
+                SEGMENT,
+                Window::Hann,
+                Normalization::Amplitude,
+            );
+            (noise_floor(&single), noise_floor(&welch(&signal)))
+        })
+        .unzip();
+    let (single_mean, single_variance) = mean_and_variance(&single);
+    let (averaged_mean, averaged_variance) = mean_and_variance(&averaged);
+    assert!(
+        averaged_variance < single_variance / 4.0,
+        "variance {averaged_variance} averaged, {single_variance} from one segment"
+    );
+    // Averaging magnitudes steadies the floor but does not lower its level
+    assert!(
+        (averaged_mean / single_mean - 1.0).abs() < 0.2,
+        "mean {averaged_mean} averaged, {single_mean} from one segment"
+    );
+}
+
+#[test]
+fn averaged_simulated_spectrum_still_finds_the_object() {
+    let settings = AnalysisSettings {
+        welch: true,
+        welch_segment: SEGMENT,
+        ..AnalysisSettings::default()
+    };
+    let object = RadarObject::new(20.0, 0.0, egui::Color32::WHITE, true);
+    let result = simulate(&RadarConfig::default(), &settings, &[object]);
+    let spectrum = result.spectra.first().map_or(0, Vec::len);
+    assert_eq!(spectrum, SEGMENT / 2, "bins of the first chirp");
+    let bandwidth = result.config.chirp_bandwidth(0);
+    let ranges: Vec<f64> = result
+        .config
+        .chirps
+        .first()
+        .into_iter()
+        .flat_map(|chirp| {
+            result
+                .detections
+                .first()
+                .into_iter()
+                .flatten()
+                .map(move |&(freq, _)| range_for_beat(freq, chirp.duration, bandwidth))
+        })
+        .collect();
+    assert!(
+        ranges.iter().any(|range| (range - 20.0).abs() < 1.0),
+        "object in {ranges:?}"
+    );
+}
@@ -0,0 +1,46 @@
+//! Window functions applied by `fftspectrum`.
+
+use fmcw_radar_demo0::dsp::{Normalization, Window, fftspectrum};
+
+const N: usize = 256;
+const SAMPLING_RATE: f64 = 256.0;
+
+fn spectrum(frequency: f64, window: Window) -> Vec<f64> {
+    let signal: Vec<f64> = (0..N)
+        .map(|i| (2.0 * std::f64::consts::PI * frequency * i as f64 / SAMPLING_RATE).sin())
+        .collect();
+    fftspectrum(&signal, SAMPLING_RATE, N, window, Normalization::Amplitude)
+        .into_iter()
+        .map(|(_, mag)| mag)
+        .collect()
+}
+
+/// Number of bins within 40 dB of the peak.
+fn lobe_width(magnitudes: &[f64]) -> usize {
+    let peak = magnitudes.iter().copied().fold(0.0, f64::max);
+    magnitudes.iter().filter(|&&mag| mag > peak * 1e-2).count()
+}
+
+#[test]
+fn hann_narrows_the_lobe_of_an_off_bin_tone() {
+    // Halfway between two bins, where the rectangular window leaks the most
+    let rectangular = lobe_width(&spectrum(16.5, Window::Rectangular));
+    let hann = lobe_width(&spectrum(16.5, Window::Hann));
+    assert!(
+        hann < rectangular,
+        "Hann spans {hann} bins, rectangular {rectangular}"
+    );
+}
+
+#[test]
+fn coherent_gain_keeps_peak_heights_comparable() {
+    for window in Window::ALL {
+        let peak = spectrum(16.0, window).into_iter().fold(0.0, f64::max);
+        assert!((peak - 1.0).abs() < 1e-2, "{window:?}: peak {peak}");
+    }
+}
+
+#[test]
+fn rectangular_window_has_unit_gain() {
+    assert_eq!(Window::Rectangular.coherent_gain(N), 1.0, "coherent gain");
+}
@@ -0,0 +1,66 @@
+//! Zero padding and truncation of the FFT input by `fftspectrum`.
+
+use fmcw_radar_demo0::dsp::{Normalization, Window, fftspectrum};
+
+const SAMPLING_RATE: f64 = 1024.0;
+/// On bin 100 of a 1024-point FFT.
+const FREQUENCY: f64 = 100.0;
+
+fn sine(n: usize) -> Vec<f64> {
+    (0..n)
+        .map(|i| (2.0 * std::f64::consts::PI * FREQUENCY * i as f64 / SAMPLING_RATE).sin())
+        .collect()
+}
+
+#[test]
+fn padded_signal_has_bins_of_the_padded_length() {
+    let spectrum = fftspectrum(
+        &sine(500),
+        SAMPLING_RATE,
+        1024,
+        Window::Rectangular,
+        Normalization::Amplitude,
+    );
+    assert_eq!(spectrum.len(), 512, "single-sided bins");
+    let spacing = spectrum.get(1).map(|&(freq, _)| freq);
+    assert_eq!(spacing, Some(1.0), "bin spacing");
+}
+
+#[test]
+fn padding_keeps_the_peak_height() {
+    let spectrum = fftspectrum(
+        &sine(500),
+        SAMPLING_RATE,
+        1024,
+        Window::Rectangular,
+        Normalization::Amplitude,
+    );
+    let (freq, peak) = spectrum
+        .into_iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or_default();
+    assert_eq!(freq, FREQUENCY, "peak frequency");
+    assert!((peak - 1.0).abs() < 1e-2, "peak {peak}");
+}
+
+#[test]
+fn longer_signal_is_truncated() {
+    let truncated = fftspectrum(
+        &sine(2048),
+        SAMPLING_RATE,
+        1024,
+        Window::Hann,
+        Normalization::Amplitude,
+    );
+    let exact = fftspectrum(
+        &sine(1024),
+        SAMPLING_RATE,
+        1024,
+        Window::Hann,
+        Normalization::Amplitude,
+    );
+    assert_eq!(
+        truncated, exact,
+        "only the first 1024 samples are transformed"
+    );
+}